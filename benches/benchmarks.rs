@@ -27,6 +27,7 @@ pub fn parse_asctime(c: &mut Criterion) {
     });
 }
 
+#[allow(dead_code)]
 struct BlackBoxWrite;
 
 impl std::fmt::Write for BlackBoxWrite {
@@ -36,6 +37,51 @@ impl std::fmt::Write for BlackBoxWrite {
     }
 }
 
+pub fn parse_invalid(c: &mut Criterion) {
+    // One input per way a header can be rejected, from cheapest to most expensive to detect
+    let cases = [
+        ("parse_invalid_length", "yesterday"),
+        ("parse_invalid_garbage", "GET /index.html HTTP/1.1\r\nHos"),
+        ("parse_invalid_rfc850_prefix", "Caturday, 06-Nov-94 08:49:37 GMT"),
+        ("parse_invalid_month", "Sun, 06 Nox 1994 08:49:37 GMT"),
+        ("parse_invalid_time", "Sun, 06 Nov 1994 24:49:37 GMT"),
+        ("parse_invalid_day_of_month", "Sun, 31 Nov 1994 08:49:37 GMT"),
+        ("parse_invalid_weekday", "Mon, 06 Nov 1994 08:49:37 GMT"),
+    ];
+
+    for (name, input) in cases {
+        c.bench_function(name, |b| {
+            b.iter(|| {
+                let d = black_box(input);
+                black_box(date_header::parse(d.as_bytes())).unwrap_err();
+            })
+        });
+    }
+}
+
+pub fn parse_mixed(c: &mut Criterion) {
+    // Mostly IMF-fixdates with a few of the obsolete formats and some junk, as a server would see,
+    // so the dispatch between formats can't be perfectly predicted
+    let headers = [
+        "Sun, 06 Nov 1994 08:49:37 GMT",
+        "Fri, 15 May 2015 15:34:21 GMT",
+        "Sunday, 06-Nov-94 08:49:37 GMT",
+        "Thu, 01 Jan 1970 00:00:00 GMT",
+        "Sun Nov  6 08:49:37 1994",
+        "Wed, 21 Oct 2015 07:28:00 GMT",
+        "0",
+        "Sat, 29 Feb 2020 23:59:59 GMT",
+    ];
+
+    c.bench_function("parse_mixed", |b| {
+        b.iter(|| {
+            for d in black_box(&headers) {
+                let _ = black_box(date_header::parse(d.as_bytes()));
+            }
+        })
+    });
+}
+
 pub fn encode_date(c: &mut Criterion) {
     let time = 1691891847;
     let mut buffer = [0u8; 29];
@@ -46,11 +92,69 @@ pub fn encode_date(c: &mut Criterion) {
     });
 }
 
+#[cfg_attr(not(feature = "std"), allow(unused_variables))]
+pub fn encode_date_cached(c: &mut Criterion) {
+    // The per-second cache makes repeated calls within the same second a copy
+    #[cfg(feature = "std")]
+    {
+        let mut buffer = [0u8; 29];
+        c.bench_function("encode_date_cached", |b| {
+            b.iter(|| {
+                date_header::format_now(black_box(&mut buffer));
+            });
+        });
+    }
+
+    #[cfg(feature = "http")]
+    c.bench_function("encode_date_header_value", |b| {
+        b.iter(|| {
+            black_box(date_header::http::date_now());
+        });
+    });
+}
+
+pub fn batch(c: &mut Criterion) {
+    let timestamps: Vec<u64> = (0..10_000u64).map(|i| i * 86413).collect();
+    let headers: Vec<[u8; 29]> = timestamps.iter().map(|&timestamp| {
+        let mut buffer = [0; 29];
+        date_header::format(timestamp, &mut buffer).unwrap();
+        buffer
+    }).collect();
+    let headers: Vec<&[u8]> = headers.iter().map(|header| &header[..]).collect();
+
+    c.bench_function("batch_parse_sequential", |b| {
+        b.iter(|| {
+            for header in black_box(&headers) {
+                black_box(date_header::parse(header)).unwrap();
+            }
+        })
+    });
+
+    #[cfg(feature = "rayon")]
+    {
+        c.bench_function("batch_par_parse", |b| {
+            b.iter(|| black_box(date_header::rayon::par_parse(black_box(&headers))))
+        });
+
+        c.bench_function("batch_par_format", |b| {
+            b.iter(|| black_box(date_header::rayon::par_format(black_box(&timestamps))))
+        });
+
+        c.bench_function("batch_par_normalize", |b| {
+            b.iter(|| black_box(date_header::rayon::par_normalize(black_box(&headers))))
+        });
+    }
+}
+
 criterion_group!(
     benches,
     parse_imf_fixdate,
     parse_rfc850_date,
     parse_asctime,
-    encode_date
+    parse_invalid,
+    parse_mixed,
+    encode_date,
+    encode_date_cached,
+    batch
 );
 criterion_main!(benches);
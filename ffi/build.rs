@@ -0,0 +1,19 @@
+// Regenerates include/date_header.h from the ffi module of the main crate
+fn main() {
+    let source = "../src/ffi.rs";
+    println!("cargo:rerun-if-changed={source}");
+
+    let mut config = cbindgen::Config::default();
+    config.language = cbindgen::Language::C;
+    config.include_guard = Some("DATE_HEADER_H".into());
+    config.autogen_warning = Some("/* Generated by cbindgen from src/ffi.rs. Do not edit. */".into());
+    config.cpp_compat = true;
+    config.usize_is_size_t = true;
+
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(source)
+        .generate()
+        .expect("Unable to generate the C header")
+        .write_to_file("include/date_header.h");
+}
@@ -0,0 +1,66 @@
+#ifndef DATE_HEADER_H
+#define DATE_HEADER_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The function succeeded.
+ */
+#define DATE_HEADER_OK 0
+
+/**
+ * The timestamp was after the year 9999, or the header was not a valid date.
+ */
+#define DATE_HEADER_INVALID -1
+
+/**
+ * A required pointer was null.
+ */
+#define DATE_HEADER_NULL_POINTER -2
+
+/**
+ * The number of bytes [date_header_format] writes.
+ */
+#define DATE_HEADER_FORMATTED_LEN 29
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Format a unix timestamp as an IMF-fixdate into `out`, which is not null terminated.
+ *
+ * Returns [DATE_HEADER_OK], [DATE_HEADER_INVALID] if the timestamp is after the year 9999,
+ * or [DATE_HEADER_NULL_POINTER]. Nothing is written unless formatting succeeds.
+ *
+ * # Safety
+ *
+ * `out` must be null or valid for writing [DATE_HEADER_FORMATTED_LEN] bytes.
+ */
+int32_t date_header_format(uint64_t secs_since_epoch, uint8_t *out);
+
+/**
+ * Parse an HTTP date of `len` bytes in any of the three formats into a unix timestamp in `out`.
+ *
+ * Returns [DATE_HEADER_OK], [DATE_HEADER_INVALID] if the header isn't a valid date,
+ * or [DATE_HEADER_NULL_POINTER]. `out` is only written if parsing succeeds.
+ *
+ * # Safety
+ *
+ * `header` must be null or valid for reading `len` bytes, and `out` must be null or valid for writing a `uint64_t`.
+ */
+int32_t date_header_parse(const uint8_t *header,
+                          size_t len,
+                          uint64_t *out);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* DATE_HEADER_H */
@@ -0,0 +1,4 @@
+//! Builds the C interface of date_header as a shared and static library.
+//! The functions themselves live in `date_header::ffi`.
+
+pub use date_header::ffi::*;
@@ -0,0 +1,20 @@
+#![no_main]
+
+use date_header::{delta_seconds, format_epoch_decimal, parse_epoch_decimal};
+use libfuzzer_sys::fuzz_target;
+
+// The plain decimal header values: `Age`, `Retry-After` seconds, and epoch timestamps
+fuzz_target!(|value: &[u8]| {
+    let mut buffer = [0; delta_seconds::MAX_LEN];
+
+    if let Ok(seconds) = delta_seconds::parse(value) {
+        assert!(seconds <= delta_seconds::MAX);
+        let len = delta_seconds::format(seconds, &mut buffer);
+        assert_eq!(delta_seconds::parse(&buffer[..len]), Ok(seconds));
+    }
+
+    if let Ok(secs_since_epoch) = parse_epoch_decimal(value) {
+        let len = format_epoch_decimal(secs_since_epoch, &mut buffer);
+        assert_eq!(parse_epoch_decimal(&buffer[..len]), Ok(secs_since_epoch));
+    }
+});
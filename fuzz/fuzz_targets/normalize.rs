@@ -0,0 +1,17 @@
+#![no_main]
+
+use date_header::{normalize, parse, FORMATTED_LEN};
+use libfuzzer_sys::fuzz_target;
+
+// Normalizing must accept exactly what parse accepts, and normalizing twice changes nothing
+fuzz_target!(|header: &[u8]| {
+    let mut buffer = [0; FORMATTED_LEN];
+    assert_eq!(normalize(header, &mut buffer).is_ok(), parse(header).is_ok());
+
+    if let Ok(timestamp) = parse(header) {
+        let mut again = [0; FORMATTED_LEN];
+        normalize(&buffer, &mut again).unwrap();
+        assert_eq!(again, buffer);
+        assert_eq!(parse(&buffer), Ok(timestamp));
+    }
+});
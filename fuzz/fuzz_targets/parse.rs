@@ -0,0 +1,23 @@
+#![no_main]
+
+use date_header::{format, parse, HttpDate, FORMATTED_LEN};
+use libfuzzer_sys::fuzz_target;
+
+// Any bytes at all: parsing must never panic, and anything accepted must survive formatting
+fuzz_target!(|header: &[u8]| {
+    let Ok(timestamp) = parse(header) else {
+        assert!(HttpDate::parse(header).is_err());
+        return;
+    };
+
+    assert_eq!(HttpDate::parse(header).map(u64::from), Ok(timestamp));
+
+    let mut buffer = [0; FORMATTED_LEN];
+    format(timestamp, &mut buffer).expect("every parsed date is formattable");
+    assert_eq!(parse(&buffer), Ok(timestamp));
+
+    // IMF-fixdate is the only format of this length, and there's one way to write each date in it
+    if header.len() == FORMATTED_LEN {
+        assert_eq!(&buffer[..], header);
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use date_header::{format, in_formattable_range, parse, FORMATTED_LEN};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|timestamp: u64| {
+    let mut buffer = [0; FORMATTED_LEN];
+    match format(timestamp, &mut buffer) {
+        Ok(()) => assert_eq!(parse(&buffer), Ok(timestamp)),
+        Err(_) => assert!(!in_formattable_range(timestamp)),
+    }
+});
@@ -0,0 +1,125 @@
+//! [quickcheck] `Arbitrary` implementations for the public types.
+//!
+//! Timestamps and dates shrink toward the unix epoch, and formats shrink toward IMF-fixdate,
+//! so a failing case is reported as the earliest, most canonical date that still fails.
+
+use ::quickcheck::{Arbitrary, Gen};
+use crate::{Format, HttpDate, Month, Timestamp, Weekday, MAX_FORMATTABLE};
+
+
+
+
+impl Arbitrary for Timestamp {
+    fn arbitrary(g: &mut Gen) -> Timestamp {
+        Timestamp::new(u64::arbitrary(g) % (MAX_FORMATTABLE + 1)).expect("Reduced into the formattable range")
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Timestamp>> {
+        // Every shrunk value is smaller, so it stays in range
+        Box::new(self.as_secs().shrink().map(|secs| Timestamp::new(secs).expect("Smaller than a valid timestamp")))
+    }
+}
+
+
+impl Arbitrary for HttpDate {
+    fn arbitrary(g: &mut Gen) -> HttpDate {
+        HttpDate::from(Timestamp::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = HttpDate>> {
+        Box::new(Timestamp::from(*self).shrink().map(HttpDate::from))
+    }
+}
+
+
+impl Arbitrary for Format {
+    fn arbitrary(g: &mut Gen) -> Format {
+        *g.choose(&[Format::ImfFixdate, Format::Rfc850, Format::Asctime]).expect("Not empty")
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Format>> {
+        match self {
+            Format::ImfFixdate => ::quickcheck::empty_shrinker(),
+            _ => ::quickcheck::single_shrinker(Format::ImfFixdate),
+        }
+    }
+}
+
+
+impl Arbitrary for Weekday {
+    fn arbitrary(g: &mut Gen) -> Weekday {
+        *g.choose(&[
+            Weekday::Monday, Weekday::Tuesday, Weekday::Wednesday, Weekday::Thursday,
+            Weekday::Friday, Weekday::Saturday, Weekday::Sunday,
+        ]).expect("Not empty")
+    }
+
+    // Toward Thursday, the weekday of the epoch
+    fn shrink(&self) -> Box<dyn Iterator<Item = Weekday>> {
+        match self {
+            Weekday::Thursday => ::quickcheck::empty_shrinker(),
+            _ => ::quickcheck::single_shrinker(Weekday::Thursday),
+        }
+    }
+}
+
+
+impl Arbitrary for Month {
+    fn arbitrary(g: &mut Gen) -> Month {
+        Month::from_number(*g.choose(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]).expect("Not empty")).expect("A valid month")
+    }
+
+    // Toward January, the month of the epoch
+    fn shrink(&self) -> Box<dyn Iterator<Item = Month>> {
+        let number = self.number();
+        Box::new((1..number).rev().map(|number| Month::from_number(number).expect("A valid month")))
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use ::quickcheck::{quickcheck, Arbitrary, Gen};
+    use crate::*;
+
+
+
+    #[test]
+    fn test_arbitrary_values_are_valid() {
+        fn formats(timestamp: Timestamp) -> bool {
+            let mut buffer = [0; FORMATTED_LEN];
+            format(timestamp.as_secs(), &mut buffer).is_ok()
+        }
+
+        fn round_trips(date: HttpDate) -> bool {
+            HttpDate::parse(date.to_string().as_bytes()) == Ok(date)
+        }
+
+        quickcheck(formats as fn(Timestamp) -> bool);
+        quickcheck(round_trips as fn(HttpDate) -> bool);
+    }
+
+
+    #[test]
+    fn test_shrinking() {
+        let timestamp = Timestamp::new(784111777).unwrap();
+        assert!(timestamp.shrink().all(|shrunk| shrunk < timestamp));
+        assert!(timestamp.shrink().any(|shrunk| shrunk == Timestamp::MIN));
+
+        let date = HttpDate::from(Timestamp::MAX);
+        assert!(date.shrink().all(|shrunk| shrunk.timestamp() < date.timestamp()));
+
+        assert_eq!(Format::Asctime.shrink().collect::<Vec<_>>(), [Format::ImfFixdate]);
+        assert_eq!(Format::ImfFixdate.shrink().count(), 0);
+        assert_eq!(Weekday::Monday.shrink().collect::<Vec<_>>(), [Weekday::Thursday]);
+        assert_eq!(Month::March.shrink().collect::<Vec<_>>(), [Month::February, Month::January]);
+        assert_eq!(Month::January.shrink().count(), 0);
+
+        let mut g = Gen::new(100);
+        for _ in 0..100 {
+            assert!(Timestamp::arbitrary(&mut g) <= Timestamp::MAX);
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! [axum](https://docs.rs/axum) support for conditional GET handling.
+//!
+//! [IfModifiedSince] extracts the request's condition, and [LastModified] and [Expires]
+//! add their headers to a response as part of a tuple.
+//!
+//! ```rust
+//! use axum_core::response::{IntoResponse, Response};
+//! use date_header::axum::{IfModifiedSince, LastModified};
+//! use date_header::Timestamp;
+//!
+//! async fn handler(if_modified_since: IfModifiedSince) -> Response {
+//!     let modified = Timestamp::new(1431704061).unwrap();
+//!     if if_modified_since.is_not_modified(modified) {
+//!         return (http::StatusCode::NOT_MODIFIED, LastModified(modified), ()).into_response();
+//!     }
+//!
+//!     (LastModified(modified), "Hello, world!").into_response()
+//! }
+//! ```
+
+use core::convert::Infallible;
+use ::axum_core::extract::FromRequestParts;
+use ::axum_core::response::{IntoResponseParts, ResponseParts};
+use ::http::header::{EXPIRES, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use ::http::request::Parts;
+use ::http::{HeaderValue, Method};
+use crate::Timestamp;
+
+
+
+
+/// Extractor for the request's `If-Modified-Since` header.
+///
+/// Holds `None` whenever [RFC9110](https://datatracker.ietf.org/doc/html/rfc9110#section-13.1.3) says
+/// to ignore the header: when it is missing or not a valid HTTP date, when the request also has
+/// `If-None-Match`, or when the method is not GET or HEAD. Extracting it never fails.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct IfModifiedSince(pub Option<Timestamp>);
+
+impl IfModifiedSince {
+    /// Whether a representation last modified at the given time is unchanged,
+    /// meaning the handler should respond with `304 Not Modified`.
+    pub fn is_not_modified(self, last_modified: Timestamp) -> bool {
+        self.0.is_some_and(|since| last_modified <= since)
+    }
+}
+
+impl<S: Sync> FromRequestParts<S> for IfModifiedSince {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<IfModifiedSince, Infallible> {
+        if !(parts.method == Method::GET || parts.method == Method::HEAD) || parts.headers.contains_key(IF_NONE_MATCH) {
+            return Ok(IfModifiedSince(None));
+        }
+
+        let since = parts.headers.get(IF_MODIFIED_SINCE).and_then(|value| Timestamp::try_from(value).ok());
+        Ok(IfModifiedSince(since))
+    }
+}
+
+
+/// Response part setting the `Last-Modified` header.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct LastModified(pub Timestamp);
+
+impl IntoResponseParts for LastModified {
+    type Error = Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Infallible> {
+        res.headers_mut().insert(LAST_MODIFIED, HeaderValue::from(self.0));
+        Ok(res)
+    }
+}
+
+
+/// Response part setting the `Expires` header.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Expires(pub Timestamp);
+
+impl IntoResponseParts for Expires {
+    type Error = Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Infallible> {
+        res.headers_mut().insert(EXPIRES, HeaderValue::from(self.0));
+        Ok(res)
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use core::future::Future;
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+    use ::axum_core::response::IntoResponse;
+    use ::http::{Request, StatusCode};
+    use super::*;
+
+
+
+    // The extractor never awaits anything, so it's ready on the first poll
+    fn extract(method: Method, headers: &[(&str, &str)]) -> IfModifiedSince {
+        let mut request = Request::builder().method(method);
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        let (mut parts, ()) = request.body(()).unwrap().into_parts();
+
+        let future = pin!(IfModifiedSince::from_request_parts(&mut parts, &()));
+        match future.poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(Ok(extracted)) => extracted,
+            Poll::Ready(Err(infallible)) => match infallible {},
+            Poll::Pending => panic!("The extractor should be ready immediately"),
+        }
+    }
+
+
+    #[test]
+    fn test_if_modified_since() {
+        let since = Some(Timestamp::new(784111777).unwrap());
+
+        assert_eq!(extract(Method::GET, &[("if-modified-since", "Sun, 06 Nov 1994 08:49:37 GMT")]), IfModifiedSince(since));
+        assert_eq!(extract(Method::HEAD, &[("if-modified-since", "Sunday, 06-Nov-94 08:49:37 GMT")]), IfModifiedSince(since));
+
+        assert_eq!(extract(Method::GET, &[]), IfModifiedSince(None));
+        assert_eq!(extract(Method::GET, &[("if-modified-since", "yesterday")]), IfModifiedSince(None));
+        assert_eq!(extract(Method::POST, &[("if-modified-since", "Sun, 06 Nov 1994 08:49:37 GMT")]), IfModifiedSince(None));
+        assert_eq!(extract(Method::GET, &[("if-modified-since", "Sun, 06 Nov 1994 08:49:37 GMT"), ("if-none-match", "\"abc\"")]), IfModifiedSince(None));
+    }
+
+
+    #[test]
+    fn test_is_not_modified() {
+        let since = IfModifiedSince(Some(Timestamp::new(784111777).unwrap()));
+        assert!(since.is_not_modified(Timestamp::new(784111777).unwrap()));
+        assert!(since.is_not_modified(Timestamp::new(784111776).unwrap()));
+        assert!(!since.is_not_modified(Timestamp::new(784111778).unwrap()));
+        assert!(!IfModifiedSince(None).is_not_modified(Timestamp::MIN));
+    }
+
+
+    #[test]
+    fn test_response_parts() {
+        let modified = Timestamp::new(784111777).unwrap();
+        let response = (StatusCode::NOT_MODIFIED, LastModified(modified), Expires(Timestamp::MAX), ()).into_response();
+
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[LAST_MODIFIED], "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(response.headers()[EXPIRES], "Fri, 31 Dec 9999 23:59:59 GMT");
+    }
+}
@@ -0,0 +1,85 @@
+//! Convert between unix timestamps and HTTP dates on the command line.
+//!
+//! Each argument, or each line of standard input if there are none, is converted on its own line.
+//! Plain digits are read as a unix timestamp and printed as an IMF-fixdate, and anything else
+//! is parsed as an HTTP date in any of the formats the crate accepts and printed as a timestamp.
+//!
+//! ```text
+//! $ http-date 1431704061 'Sunday, 06-Nov-94 08:49:37 GMT'
+//! Fri, 15 May 2015 15:34:21 GMT
+//! 784111777
+//! ```
+
+use std::io::BufRead;
+use std::process::ExitCode;
+use date_header::{format, parse, parse_epoch_decimal, FORMATTED_LEN};
+
+
+
+
+fn main() -> ExitCode {
+    let mut inputs: Vec<String> = std::env::args().skip(1).collect();
+    if inputs.is_empty() {
+        match std::io::stdin().lock().lines().collect() {
+            Ok(lines) => inputs = lines,
+            Err(error) => {
+                eprintln!("http-date: {}", error);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let mut status = ExitCode::SUCCESS;
+    for input in inputs {
+        match convert(&input) {
+            Ok(output) => println!("{}", output),
+            Err(message) => {
+                eprintln!("http-date: {:?}: {}", input, message);
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    status
+}
+
+
+// Convert a timestamp to an HTTP date or an HTTP date to a timestamp, depending on which the input is
+fn convert(input: &str) -> Result<String, &'static str> {
+    let input = input.trim();
+
+    if !input.is_empty() && input.bytes().all(|byte| byte.is_ascii_digit()) {
+        let secs = parse_epoch_decimal(input.as_bytes()).map_err(|_| "timestamp is too large")?;
+        let mut buffer = [0; FORMATTED_LEN];
+        format(secs, &mut buffer).map_err(|_| "timestamp is after the year 9999")?;
+        Ok(String::from_utf8(buffer.to_vec()).expect("IMF-fixdate is ASCII"))
+    } else {
+        parse(input.as_bytes()).map(|secs| secs.to_string()).map_err(|_| "not a timestamp or an HTTP date")
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+
+
+    #[test]
+    fn test_convert() {
+        assert_eq!(convert("1431704061"), Ok("Fri, 15 May 2015 15:34:21 GMT".to_string()));
+        assert_eq!(convert("0"), Ok("Thu, 01 Jan 1970 00:00:00 GMT".to_string()));
+        assert_eq!(convert(" 784111777\n"), Ok("Sun, 06 Nov 1994 08:49:37 GMT".to_string()));
+
+        assert_eq!(convert("Fri, 15 May 2015 15:34:21 GMT"), Ok("1431704061".to_string()));
+        assert_eq!(convert("Sunday, 06-Nov-94 08:49:37 GMT"), Ok("784111777".to_string()));
+        assert_eq!(convert("Sun Nov  6 08:49:37 1994"), Ok("784111777".to_string()));
+
+        assert_eq!(convert("253402300800"), Err("timestamp is after the year 9999"));
+        assert_eq!(convert("99999999999999999999"), Err("timestamp is too large"));
+        assert_eq!(convert("-1"), Err("not a timestamp or an HTTP date"));
+        assert_eq!(convert(""), Err("not a timestamp or an HTTP date"));
+    }
+}
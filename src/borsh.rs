@@ -0,0 +1,64 @@
+//! [borsh](::borsh) support for [Timestamp], as its unix timestamp in a fixed little-endian `u64`.
+//!
+//! Deserializing rejects timestamps after the year 9999 as invalid data.
+//!
+//! ```rust
+//! use date_header::Timestamp;
+//!
+//! let timestamp = Timestamp::new(1431704061).unwrap();
+//! let bytes = borsh::to_vec(&timestamp).unwrap();
+//! assert_eq!(bytes, 1431704061u64.to_le_bytes());
+//! assert_eq!(timestamp, borsh::from_slice(&bytes).unwrap());
+//! ```
+
+use ::borsh::io::{Error, ErrorKind, Read, Result, Write};
+use ::borsh::{BorshDeserialize, BorshSerialize};
+use crate::Timestamp;
+
+
+
+
+impl BorshSerialize for Timestamp {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.as_secs().serialize(writer)
+    }
+}
+
+impl BorshDeserialize for Timestamp {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Timestamp> {
+        let secs = u64::deserialize_reader(reader)?;
+        Timestamp::new(secs).map_err(|_| Error::new(ErrorKind::InvalidData, "timestamp is after the year 9999"))
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::MAX_FORMATTABLE;
+    use super::*;
+
+
+
+    #[test]
+    fn test_round_trip() {
+        for secs in [0, 784111777, MAX_FORMATTABLE] {
+            let timestamp = Timestamp::new(secs).unwrap();
+            let bytes = ::borsh::to_vec(&timestamp).unwrap();
+            assert_eq!(bytes, secs.to_le_bytes());
+            assert_eq!(::borsh::from_slice::<Timestamp>(&bytes).unwrap(), timestamp);
+        }
+    }
+
+
+    #[test]
+    fn test_invalid() {
+        let error = ::borsh::from_slice::<Timestamp>(&(MAX_FORMATTABLE + 1).to_le_bytes()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), "timestamp is after the year 9999");
+
+        assert!(::borsh::from_slice::<Timestamp>(&[0; 7]).is_err()); // Too short
+        assert!(::borsh::from_slice::<Timestamp>(&[0; 9]).is_err()); // Trailing bytes
+    }
+}
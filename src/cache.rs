@@ -0,0 +1,397 @@
+//! Policy helpers for caches and proxies built on top of the raw `Date` header.
+//!
+//! See [RFC9111](https://datatracker.ietf.org/doc/html/rfc9111) for more information.
+
+use crate::{parse, YEAR_1971};
+
+
+
+
+/// Check an origin's parsed `Date` value against the local clock.
+///
+/// Dates more than `max_future` seconds ahead of `now` are clamped to `now`, as are dates that
+/// fall within the year 1970, which almost always indicates an origin with an unset clock
+/// or a default value rather than a real timestamp.
+///
+/// ```rust
+/// use date_header::{DateCheck, Correction};
+///
+/// let now = 1431704061;
+/// assert_eq!(DateCheck::Plausible(now - 5), date_header::check_date(now - 5, now, 60));
+/// assert_eq!(DateCheck::Corrected(now, Correction::InFuture), date_header::check_date(now + 3600, now, 60));
+/// assert_eq!(DateCheck::Corrected(now, Correction::Epoch), date_header::check_date(0, now, 60));
+/// ```
+pub fn check_date(date: u64, now: u64, max_future: u64) -> DateCheck {
+    if date < YEAR_1971 {
+        DateCheck::Corrected(now, Correction::Epoch)
+    } else if date > now.saturating_add(max_future) {
+        DateCheck::Corrected(now, Correction::InFuture)
+    } else {
+        DateCheck::Plausible(date)
+    }
+}
+
+
+/// Parse and check an origin's `Date` header against the local clock.
+///
+/// RFC9110 requires a recipient with a clock to replace an invalid `Date` with the time the
+/// message was received, so unparseable headers are corrected to `now`.
+/// Otherwise this behaves the same as [check_date].
+///
+/// ```rust
+/// use date_header::{DateCheck, Correction};
+///
+/// let now = 1431704061;
+/// assert_eq!(DateCheck::Plausible(now - 21600), date_header::check_date_header(b"Fri, 15 May 2015 09:34:21 GMT", now, 60));
+/// assert_eq!(DateCheck::Corrected(now, Correction::Invalid), date_header::check_date_header(b"yesterday", now, 60));
+/// ```
+pub fn check_date_header(header: &[u8], now: u64, max_future: u64) -> DateCheck {
+    match parse(header) {
+        Ok(date) => check_date(date, now, max_future),
+        Err(_) => DateCheck::Corrected(now, Correction::Invalid),
+    }
+}
+
+
+/// Estimate the offset of an origin's clock from the local clock, in seconds.
+///
+/// `request_time` and `response_time` are the local times at which the request was sent
+/// and the response was received. The origin is assumed to have generated its `Date` value
+/// halfway between the two, which compensates for the network and processing delay.
+///
+/// A positive result means the origin's clock is ahead of the local clock.
+/// Results that don't fit in an `i64` saturate.
+///
+/// ```rust
+/// // Request sent at 100, response received at 110, origin claims it was 125
+/// assert_eq!(20, date_header::clock_skew(125, 100, 110));
+/// assert_eq!(-5, date_header::clock_skew(100, 100, 110));
+/// ```
+pub fn clock_skew(date: u64, request_time: u64, response_time: u64) -> i64 {
+    let midpoint = request_time + response_time.saturating_sub(request_time) / 2;
+    let skew = i128::from(date) - i128::from(midpoint);
+    skew.clamp(i64::MIN.into(), i64::MAX.into()) as i64
+}
+
+
+/// Check whether an `Expires` header has passed.
+///
+/// RFC9111 requires an invalid `Expires` value, such as the commonly seen `0`,
+/// to be treated as representing a time in the past, so unparseable headers are expired.
+/// An empty header carries no information and is not expired, as [parse_expires] decides.
+///
+/// ```rust
+/// let now = 1431704061;
+/// assert!(date_header::is_expired(b"Fri, 15 May 2015 15:34:21 GMT", now));
+/// assert!(!date_header::is_expired(b"Fri, 15 May 2015 15:34:22 GMT", now));
+/// assert!(date_header::is_expired(b"0", now));
+/// assert!(!date_header::is_expired(b"", now));
+/// ```
+pub fn is_expired(header: &[u8], now: u64) -> bool {
+    parse_expires(header, now) == Expiry::Expired
+}
+
+
+/// Interpret an `Expires` header according to the caching rules of RFC9111.
+///
+/// Invalid values, especially the commonly seen `0` and `-1`, must be treated as a time in the past,
+/// so they are [Expiry::Expired] rather than an error. Valid dates that are not after `now` are also expired.
+/// An empty field value carries no information, and results in [Expiry::NoInformation].
+/// See [RFC9111 Section 5.3](https://datatracker.ietf.org/doc/html/rfc9111#section-5.3) for more information.
+///
+/// ```rust
+/// use date_header::{parse_expires, Expiry};
+///
+/// let now = 1431704061;
+/// assert_eq!(Expiry::At(1431704062), parse_expires(b"Fri, 15 May 2015 15:34:22 GMT", now));
+/// assert_eq!(Expiry::Expired, parse_expires(b"Fri, 15 May 2015 15:34:21 GMT", now));
+/// assert_eq!(Expiry::Expired, parse_expires(b"0", now));
+/// assert_eq!(Expiry::NoInformation, parse_expires(b"", now));
+/// ```
+pub fn parse_expires(header: &[u8], now: u64) -> Expiry {
+    if header.iter().all(|&byte| byte == b' ' || byte == b'\t') {
+        return Expiry::NoInformation;
+    }
+
+    match parse(header) {
+        Ok(expires) if expires > now => Expiry::At(expires),
+        _ => Expiry::Expired,
+    }
+}
+
+
+/// Result of [parse_expires].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Expiry {
+    /// The response is stale, either because the date has passed or because the value was invalid.
+    Expired,
+    /// The response is fresh until the given unix timestamp.
+    At(u64),
+    /// The header was empty and says nothing about the response's freshness.
+    NoInformation,
+}
+
+
+/// Number of seconds from `now` until the date in the header.
+///
+/// Returns `Some(0)` if the date has already passed, and `None` if the header is invalid.
+///
+/// ```rust
+/// let now = 1431704061;
+/// assert_eq!(Some(60), date_header::seconds_until(b"Fri, 15 May 2015 15:35:21 GMT", now));
+/// assert_eq!(Some(0), date_header::seconds_until(b"Fri, 15 May 2015 15:33:21 GMT", now));
+/// assert_eq!(None, date_header::seconds_until(b"0", now));
+/// ```
+pub fn seconds_until(header: &[u8], now: u64) -> Option<u64> {
+    parse(header).ok().map(|date| date.saturating_sub(now))
+}
+
+
+/// Number of seconds from the date in the header until `now`.
+///
+/// Returns `Some(0)` if the date is in the future, and `None` if the header is invalid.
+///
+/// ```rust
+/// let now = 1431704061;
+/// assert_eq!(Some(60), date_header::age_of(b"Fri, 15 May 2015 15:33:21 GMT", now));
+/// assert_eq!(Some(0), date_header::age_of(b"Fri, 15 May 2015 15:35:21 GMT", now));
+/// assert_eq!(None, date_header::age_of(b"0", now));
+/// ```
+pub fn age_of(header: &[u8], now: u64) -> Option<u64> {
+    parse(header).ok().map(|date| now.saturating_sub(date))
+}
+
+
+/// Calculate a response's explicit freshness lifetime in seconds.
+///
+/// The sources are checked in order of precedence for the kind of cache, and the first one present is used:
+///
+/// 1. `Surrogate-Control: max-age`, for [CacheKind::Surrogate] only
+/// 2. `Cache-Control: s-maxage`, for [CacheKind::Shared] and [CacheKind::Surrogate]
+/// 3. `Cache-Control: max-age`
+/// 4. `Expires` minus `Date`, or `0` if `Expires` is earlier than `Date`
+///
+/// Returns `None` if the response has no explicit lifetime, in which case a cache may
+/// use a heuristic lifetime instead.
+/// See [RFC9111 Section 4.2.1](https://datatracker.ietf.org/doc/html/rfc9111#section-4.2.1) and the
+/// [Edge Architecture Specification](https://www.w3.org/TR/edge-arch/) for more information.
+///
+/// ```rust
+/// use date_header::{freshness_lifetime, CacheKind, FreshnessSources};
+///
+/// let sources = FreshnessSources {
+///     surrogate_max_age: Some(86400),
+///     max_age: Some(60),
+///     ..FreshnessSources::default()
+/// };
+/// assert_eq!(Some(86400), freshness_lifetime(&sources, CacheKind::Surrogate));
+/// assert_eq!(Some(60), freshness_lifetime(&sources, CacheKind::Shared));
+/// ```
+pub fn freshness_lifetime(sources: &FreshnessSources, cache: CacheKind) -> Option<u64> {
+    if let (CacheKind::Surrogate, Some(surrogate_max_age)) = (cache, sources.surrogate_max_age) {
+        return Some(surrogate_max_age);
+    }
+
+    if let (CacheKind::Shared | CacheKind::Surrogate, Some(s_maxage)) = (cache, sources.s_maxage) {
+        return Some(s_maxage);
+    }
+
+    if let Some(max_age) = sources.max_age {
+        return Some(max_age);
+    }
+
+    match (sources.expires, sources.date) {
+        (Some(expires), Some(date)) => Some(expires.saturating_sub(date)),
+        _ => None,
+    }
+}
+
+
+/// Header values used by [freshness_lifetime], with `None` for those missing from the response.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct FreshnessSources {
+    /// The `max-age` directive of the `Surrogate-Control` header.
+    pub surrogate_max_age: Option<u64>,
+    /// The `s-maxage` directive of the `Cache-Control` header.
+    pub s_maxage: Option<u64>,
+    /// The `max-age` directive of the `Cache-Control` header.
+    pub max_age: Option<u64>,
+    /// The parsed `Expires` header.
+    pub expires: Option<u64>,
+    /// The parsed `Date` header.
+    pub date: Option<u64>,
+}
+
+
+/// Kind of cache calculating a freshness lifetime, which determines the directives it honors.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CacheKind {
+    /// A cache used by a single user, such as a browser cache.
+    Private,
+    /// A cache shared between users, such as a proxy.
+    Shared,
+    /// A shared cache operated on behalf of the origin, such as a CDN edge.
+    Surrogate,
+}
+
+
+/// Result of [check_date] and [check_date_header].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DateCheck {
+    /// The origin's date looks reasonable and is returned unchanged.
+    Plausible(u64),
+    /// The origin's date was replaced with the local time for the given reason.
+    Corrected(u64, Correction),
+}
+
+impl DateCheck {
+    /// The date to use, whether or not it was corrected.
+    pub fn date(self) -> u64 {
+        match self {
+            DateCheck::Plausible(date) => date,
+            DateCheck::Corrected(date, _) => date,
+        }
+    }
+}
+
+
+/// Reason a date was corrected by [check_date] or [check_date_header].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Correction {
+    /// The date was further in the future than the allowed tolerance.
+    InFuture,
+    /// The date was within the year 1970, indicating an unset clock.
+    Epoch,
+    /// The header could not be parsed.
+    Invalid,
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+
+
+    #[test]
+    fn test_check_date() {
+        let now = 784111777;
+
+        assert_eq!(check_date(now, now, 0), DateCheck::Plausible(now));
+        assert_eq!(check_date(now - 86400, now, 0), DateCheck::Plausible(now - 86400)); // Dates in the past are fine
+        assert_eq!(check_date(now + 60, now, 60), DateCheck::Plausible(now + 60)); // Right at the tolerance
+        assert_eq!(check_date(now + 61, now, 60), DateCheck::Corrected(now, Correction::InFuture)); // One second past the tolerance
+        assert_eq!(check_date(u64::MAX, u64::MAX, u64::MAX), DateCheck::Plausible(u64::MAX)); // Tolerance doesn't overflow
+
+        assert_eq!(check_date(0, now, 60), DateCheck::Corrected(now, Correction::Epoch));
+        assert_eq!(check_date(31535999, now, 60), DateCheck::Corrected(now, Correction::Epoch)); // Last second of 1970
+        assert_eq!(check_date(31536000, now, 60), DateCheck::Plausible(31536000)); // First second of 1971
+
+        assert_eq!(check_date_header(b"Sun, 06 Nov 1994 08:49:37 GMT", now, 0).date(), now);
+        assert_eq!(check_date_header(b"Thu, 01 Jan 1970 00:00:00 GMT", now, 0), DateCheck::Corrected(now, Correction::Epoch));
+        assert_eq!(check_date_header(b"0", now, 0), DateCheck::Corrected(now, Correction::Invalid));
+    }
+
+
+
+    #[test]
+    fn test_clock_skew() {
+        assert_eq!(clock_skew(100, 100, 100), 0);
+        assert_eq!(clock_skew(105, 100, 110), 0); // Exactly the midpoint
+        assert_eq!(clock_skew(106, 100, 111), 1); // Midpoint rounds down
+        assert_eq!(clock_skew(0, 100, 110), -105);
+        assert_eq!(clock_skew(1000, 110, 100), 890); // Response before request is treated as no delay
+
+        assert_eq!(clock_skew(u64::MAX, 0, 0), i64::MAX); // Saturates
+        assert_eq!(clock_skew(0, u64::MAX, u64::MAX), i64::MIN);
+    }
+
+
+
+    #[test]
+    fn test_expiry_helpers() {
+        let date = b"Sun, 06 Nov 1994 08:49:37 GMT";
+        let timestamp = 784111777;
+
+        assert!(is_expired(date, timestamp));
+        assert!(is_expired(date, timestamp + 1));
+        assert!(!is_expired(date, timestamp - 1));
+        assert!(!is_expired(b"", 0));
+        assert!(!is_expired(b" \t", 0));
+        assert!(is_expired(b"-1", 0));
+
+        assert_eq!(seconds_until(date, timestamp), Some(0));
+        assert_eq!(seconds_until(date, timestamp - 1), Some(1));
+        assert_eq!(seconds_until(date, timestamp + 1), Some(0)); // No underflow
+        assert_eq!(seconds_until(date, 0), Some(timestamp));
+        assert_eq!(seconds_until(b"-1", 0), None);
+
+        assert_eq!(age_of(date, timestamp), Some(0));
+        assert_eq!(age_of(date, timestamp + 1), Some(1));
+        assert_eq!(age_of(date, timestamp - 1), Some(0)); // No underflow
+        assert_eq!(age_of(date, u64::MAX), Some(u64::MAX - timestamp));
+        assert_eq!(age_of(b"-1", 0), None);
+    }
+
+
+
+    #[test]
+    fn test_freshness_lifetime() {
+        let all = FreshnessSources {
+            surrogate_max_age: Some(1),
+            s_maxage: Some(2),
+            max_age: Some(3),
+            expires: Some(104),
+            date: Some(100),
+        };
+
+        assert_eq!(freshness_lifetime(&all, CacheKind::Surrogate), Some(1));
+        assert_eq!(freshness_lifetime(&all, CacheKind::Shared), Some(2));
+        assert_eq!(freshness_lifetime(&all, CacheKind::Private), Some(3));
+
+        let no_surrogate = FreshnessSources { surrogate_max_age: None, ..all };
+        assert_eq!(freshness_lifetime(&no_surrogate, CacheKind::Surrogate), Some(2));
+
+        let no_shared = FreshnessSources { s_maxage: None, ..no_surrogate };
+        assert_eq!(freshness_lifetime(&no_shared, CacheKind::Surrogate), Some(3));
+        assert_eq!(freshness_lifetime(&no_shared, CacheKind::Shared), Some(3));
+
+        let expires_only = FreshnessSources { max_age: None, ..no_shared };
+        assert_eq!(freshness_lifetime(&expires_only, CacheKind::Surrogate), Some(4));
+        assert_eq!(freshness_lifetime(&expires_only, CacheKind::Private), Some(4));
+
+        let expires_before_date = FreshnessSources { expires: Some(99), ..expires_only };
+        assert_eq!(freshness_lifetime(&expires_before_date, CacheKind::Private), Some(0));
+
+        let no_date = FreshnessSources { date: None, ..expires_only };
+        assert_eq!(freshness_lifetime(&no_date, CacheKind::Private), None);
+
+        assert_eq!(freshness_lifetime(&FreshnessSources::default(), CacheKind::Surrogate), None);
+
+        // A private cache never honors the shared directives
+        let shared_only = FreshnessSources { surrogate_max_age: Some(1), s_maxage: Some(2), ..FreshnessSources::default() };
+        assert_eq!(freshness_lifetime(&shared_only, CacheKind::Private), None);
+    }
+
+
+
+    #[test]
+    fn test_parse_expires() {
+        let now = 784111777;
+
+        assert_eq!(parse_expires(b"Sun, 06 Nov 1994 08:49:38 GMT", now), Expiry::At(now + 1));
+        assert_eq!(parse_expires(b"Sun Nov  6 08:49:38 1994", now), Expiry::At(now + 1));
+        assert_eq!(parse_expires(b"Sun, 06 Nov 1994 08:49:37 GMT", now), Expiry::Expired);
+        assert_eq!(parse_expires(b"Sun, 06 Nov 1994 08:49:36 GMT", now), Expiry::Expired);
+
+        assert_eq!(parse_expires(b"0", now), Expiry::Expired);
+        assert_eq!(parse_expires(b"-1", now), Expiry::Expired);
+        assert_eq!(parse_expires(b"Mon, 06 Nov 1994 08:49:38 GMT", now), Expiry::Expired); // Wrong weekday
+        assert_eq!(parse_expires(b"Fri, 01 Jan 10000 00:00:00 GMT", now), Expiry::Expired); // Unrepresentable, so invalid
+
+        assert_eq!(parse_expires(b"", now), Expiry::NoInformation);
+        assert_eq!(parse_expires(b" \t ", now), Expiry::NoInformation);
+    }
+}
@@ -0,0 +1,74 @@
+use std::cell::Cell;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::{format, MAX_FORMATTABLE};
+
+
+
+
+thread_local! {
+    static CACHE: Cell<(u64, [u8; 29])> = const { Cell::new((u64::MAX, [0u8; 29])) };
+}
+
+
+
+
+/// Format the current system time as an IMF-fixdate into the provided buffer.
+///
+/// The formatted value is cached per thread and only recomputed when the second changes,
+/// so this is cheap enough to call for every response a server sends.
+///
+/// ```rust
+/// let mut header = [0u8; 29];
+/// date_header::format_now(&mut header);
+/// assert!(date_header::parse(&header).is_ok());
+/// ```
+pub fn format_now(buffer: &mut [u8; 29]) {
+    let now = unix_now();
+
+    CACHE.with(|cache| {
+        let (cached_secs, cached_buffer) = cache.get();
+        if cached_secs == now {
+            *buffer = cached_buffer;
+        } else {
+            format(now, buffer).expect("unix_now is clamped to the formattable range");
+            cache.set((now, *buffer));
+        }
+    });
+}
+
+
+// Current system time in whole seconds, clamped to the range supported by format
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+        .min(MAX_FORMATTABLE)
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use super::*;
+
+
+
+    #[test]
+    fn test_format_now() {
+        let before = unix_now();
+
+        let mut first = [0u8; 29];
+        let mut second = [0u8; 29];
+        format_now(&mut first);
+        format_now(&mut second);
+
+        let after = unix_now();
+
+        let first = parse(&first).unwrap();
+        let second = parse(&second).unwrap();
+        assert!(before <= first && first <= second && second <= after);
+    }
+}
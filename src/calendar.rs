@@ -0,0 +1,724 @@
+//! Civil calendar calculations in UTC, shared by parsing and formatting.
+
+use crate::{in_formattable_range, TooFuturistic};
+
+
+
+
+/// Check whether a year is a leap year in the proleptic Gregorian calendar.
+///
+/// ```rust
+/// assert!(date_header::is_leap_year(2000));
+/// assert!(!date_header::is_leap_year(1900));
+/// assert!(date_header::is_leap_year(2024));
+/// ```
+pub const fn is_leap_year(year: u16) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+
+/// Number of days in a year: 366 for leap years, 365 otherwise.
+///
+/// ```rust
+/// assert_eq!(366, date_header::days_in_year(2000));
+/// assert_eq!(365, date_header::days_in_year(2015));
+/// ```
+pub const fn days_in_year(year: u16) -> u16 {
+    if is_leap_year(year) {
+        366
+    } else {
+        365
+    }
+}
+
+
+/// Number of days in a month, where `month` is 1 for January through 12 for December.
+///
+/// Returns `None` if the month is out of range.
+///
+/// ```rust
+/// assert_eq!(Some(29), date_header::days_in_month(2000, 2));
+/// assert_eq!(Some(28), date_header::days_in_month(2015, 2));
+/// assert_eq!(Some(31), date_header::days_in_month(2015, 12));
+/// assert_eq!(None, date_header::days_in_month(2015, 13));
+/// ```
+pub const fn days_in_month(year: u16, month: u8) -> Option<u8> {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => Some(31),
+        4 | 6 | 9 | 11 => Some(30),
+        2 if is_leap_year(year) => Some(29),
+        2 => Some(28),
+        _ => None,
+    }
+}
+
+
+/// Day of the week containing a unix timestamp.
+///
+/// ```rust
+/// use date_header::{weekday_of, Weekday};
+///
+/// assert_eq!(Weekday::Thursday, weekday_of(0)); // The epoch
+/// assert_eq!(Weekday::Friday, weekday_of(1431704061)); // Fri, 15 May 2015 15:34:21 GMT
+/// ```
+pub fn weekday_of(secs_since_epoch: u64) -> Weekday {
+    // The epoch was a Thursday
+    Weekday::from_number_from_sunday(((secs_since_epoch / 86400 + 4) % 7) as u8)
+}
+
+
+/// Day of the year containing a unix timestamp, from 1 for January 1st through 366.
+///
+/// Fails for times after the year 9999, as [format](crate::format) does.
+///
+/// ```rust
+/// assert_eq!(Ok(1), date_header::ordinal_day(0)); // Jan 1st, 1970
+/// assert_eq!(Ok(135), date_header::ordinal_day(1431704061)); // May 15th, 2015
+/// ```
+pub fn ordinal_day(secs_since_epoch: u64) -> Result<u16, TooFuturistic> {
+    if !in_formattable_range(secs_since_epoch) {
+        return Err(TooFuturistic);
+    }
+
+    let (days, _) = split_days(secs_since_epoch);
+    let (year, _, _) = civil_from_days(days);
+    Ok((days - civil_to_days(year, 1, 1)) as u16 + 1)
+}
+
+
+/// ISO 8601 week date containing a unix timestamp, as `(year, week, weekday)`.
+///
+/// ISO weeks start on Monday, and week 1 is the week containing the year's first Thursday.
+/// The ISO year therefore differs from the calendar year for a few days around January 1st.
+/// Fails for times after the year 9999, as [format](crate::format) does.
+///
+/// ```rust
+/// use date_header::Weekday;
+///
+/// assert_eq!(Ok((2015, 20, Weekday::Friday)), date_header::iso_week(1431704061)); // May 15th, 2015
+/// assert_eq!(Ok((2009, 53, Weekday::Friday)), date_header::iso_week(1262304000)); // Jan 1st, 2010
+/// ```
+pub fn iso_week(secs_since_epoch: u64) -> Result<(u16, u8, Weekday), TooFuturistic> {
+    let ordinal = ordinal_day(secs_since_epoch)?;
+    let weekday = weekday_of(secs_since_epoch);
+    let (year, _, _) = civil_from_days(split_days(secs_since_epoch).0);
+    let week = (ordinal + 10 - u16::from(weekday.number_from_monday())) / 7;
+
+    Ok(if week < 1 {
+        (year - 1, iso_weeks_in_year(year - 1), weekday)
+    } else if week > u16::from(iso_weeks_in_year(year)) {
+        (year + 1, 1, weekday)
+    } else {
+        (year, week as u8, weekday)
+    })
+}
+
+
+/// Round a unix timestamp down to midnight UTC of the day containing it.
+///
+/// ```rust
+/// assert_eq!(1431648000, date_header::start_of_day(1431704061)); // Fri, 15 May 2015 00:00:00 GMT
+/// ```
+pub const fn start_of_day(secs_since_epoch: u64) -> u64 {
+    secs_since_epoch - secs_since_epoch % 86400
+}
+
+
+/// Round a unix timestamp down to the start of the hour containing it.
+///
+/// ```rust
+/// assert_eq!(1431702000, date_header::start_of_hour(1431704061)); // Fri, 15 May 2015 15:00:00 GMT
+/// ```
+pub const fn start_of_hour(secs_since_epoch: u64) -> u64 {
+    secs_since_epoch - secs_since_epoch % 3600
+}
+
+
+/// Round a unix timestamp down to the start of the minute containing it.
+///
+/// ```rust
+/// assert_eq!(1431704040, date_header::start_of_minute(1431704061)); // Fri, 15 May 2015 15:34:00 GMT
+/// ```
+pub const fn start_of_minute(secs_since_epoch: u64) -> u64 {
+    secs_since_epoch - secs_since_epoch % 60
+}
+
+
+/// Julian Day Number of the UTC calendar day containing a unix timestamp.
+///
+/// The Julian Day Number counts days since noon on November 24th, 4714 BC in the proleptic Gregorian calendar.
+/// It is assigned to a calendar day as a whole, so the time of day is discarded.
+///
+/// ```rust
+/// assert_eq!(2440588, date_header::to_jdn(0)); // Jan 1st, 1970
+/// assert_eq!(2457158, date_header::to_jdn(1431704061)); // May 15th, 2015
+/// ```
+pub const fn to_jdn(secs_since_epoch: u64) -> u64 {
+    secs_since_epoch / 86400 + JDN_EPOCH
+}
+
+
+/// Convert a Julian Day Number and a number of seconds into that day into a unix timestamp.
+///
+/// Returns `None` if the day is before the unix epoch, or if `secs_of_day` is not less than 86400.
+///
+/// ```rust
+/// assert_eq!(Some(1431704061), date_header::from_jdn(2457158, 56061));
+/// assert_eq!(None, date_header::from_jdn(2440587, 0)); // Dec 31st, 1969
+/// ```
+pub const fn from_jdn(jdn: u64, secs_of_day: u32) -> Option<u64> {
+    if jdn < JDN_EPOCH || secs_of_day >= 86400 {
+        return None;
+    }
+
+    match (jdn - JDN_EPOCH).checked_mul(86400) {
+        Some(secs) => secs.checked_add(secs_of_day as u64),
+        None => None,
+    }
+}
+
+
+// Julian Day Number of the unix epoch
+const JDN_EPOCH: u64 = 2440588;
+
+
+// Number of ISO weeks in a year: 53 if it starts on a Thursday, or is a leap year starting on a Wednesday
+fn iso_weeks_in_year(year: u16) -> u8 {
+    // Sakamoto's method, since the year may be before the epoch
+    let y = year - 1;
+    let jan_1 = (y + y / 4 - y / 100 + y / 400 + 1) % 7; // 0 is Sunday
+
+    if jan_1 == 4 || (jan_1 == 3 && is_leap_year(year)) {
+        53
+    } else {
+        52
+    }
+}
+
+
+/// Split a formattable unix timestamp into days since the epoch and seconds of that day.
+///
+/// 86400 is 128 * 675, and any formattable timestamp shifted right by 7 fits in a u32,
+/// so this never divides a 64-bit value. Targets without hardware 64-bit division
+/// would otherwise call into a slow compiler intrinsic.
+#[inline]
+pub(crate) fn split_days(secs_since_epoch: u64) -> (u32, u32) {
+    debug_assert!(secs_since_epoch <= crate::MAX_FORMATTABLE);
+
+    let days = div_675((secs_since_epoch >> 7) as u32);
+    let secs_of_day = (secs_since_epoch - u64::from(days) * 86400) as u32;
+    (days, secs_of_day)
+}
+
+
+/// Split seconds of the day into `(hour, min, sec)`.
+#[inline]
+pub(crate) fn split_time(secs_of_day: u32) -> (u8, u8, u8) {
+    debug_assert!(secs_of_day < 86400);
+
+    let hour = div_3600(secs_of_day);
+    let secs_of_hour = secs_of_day - hour * 3600;
+    let min = div_60(secs_of_hour);
+    let sec = secs_of_hour - min * 60;
+    (hour as u8, min as u8, sec as u8)
+}
+
+
+/// Convert days since the epoch into a `(year, month, day)` civil date.
+///
+/// This is the Euclidean affine function algorithm from Neri and Schneider,
+/// "Euclidean affine functions and their application to calendar algorithms" (2022),
+/// which needs no loops, tables, or data-dependent branches.
+#[inline]
+pub(crate) fn civil_from_days(days: u32) -> (u16, u8, u8) {
+    /* Days from 0000-03-01 to the epoch. Starting years in March puts leap days at the end */
+    const DAYS_SINCE_MARCH_0000: u32 = 719468;
+
+    debug_assert!(days <= (crate::MAX_FORMATTABLE / 86400) as u32);
+    let days = days + DAYS_SINCE_MARCH_0000;
+
+    // Century, and day of the century
+    let n1 = 4 * days + 3;
+    let century = div_146097(n1);
+    let day_of_century = (n1 - century * 146097) / 4;
+
+    // Year of the century, and day of the year
+    let n2 = u64::from(4 * day_of_century + 3) * 2939745;
+    let year_of_century = (n2 >> 32) as u32;
+    let day_of_year = (n2 as u32) / 2939745 / 4;
+
+    // Month and day, counting March as month 3 through February as month 14
+    let n3 = 2141 * day_of_year + 197913;
+    let mon = n3 >> 16;
+    let mday = (n3 & 0xFFFF) / 2141 + 1;
+
+    // January and February belong to the next calendar year
+    let next_year = u32::from(day_of_year >= 306);
+    let year = 100 * century + year_of_century + next_year;
+    let mon = mon - 12 * next_year;
+
+    (year as u16, mon as u8, mday as u8)
+}
+
+
+// Division by the constants used in calendar math, as a multiply and a shift.
+//
+// Each multiplier is ceil(2^shift / divisor), which gives the exact quotient for every
+// numerator up to the bound in its debug assertion. Compilers usually do this themselves,
+// but spelling it out keeps the multiply in 32 bits where it fits and avoids relying on
+// the optimizer for targets with slow or missing dividers.
+
+#[inline]
+fn div_675(n: u32) -> u32 {
+    debug_assert!(n <= (crate::MAX_FORMATTABLE >> 7) as u32);
+    ((u64::from(n) * 407226529) >> 38) as u32
+}
+
+#[inline]
+fn div_3600(n: u32) -> u32 {
+    debug_assert!(n < 86400);
+    (n * 37283) >> 27
+}
+
+#[inline]
+fn div_60(n: u32) -> u32 {
+    debug_assert!(n < 3600);
+    (n * 2185) >> 17
+}
+
+// Exact for every u32
+#[inline]
+fn div_146097(n: u32) -> u32 {
+    ((u64::from(n) * 963315389) >> 47) as u32
+}
+
+
+/// Convert a civil date into days since the epoch.
+///
+/// The year must be at least 1970, the month 1 through 12, and the day at least 1.
+#[inline]
+pub(crate) fn civil_to_days(year: u16, mon: u8, day: u8) -> u32 {
+    days_before_year(year) + days_before_month(year, mon) + u32::from(day) - 1
+}
+
+
+// The small-table calendar math below is the default, since it's what embedded targets want.
+// The fast-tables feature swaps in precomputed tables for the year and month offsets instead.
+
+// Days from the epoch to January 1st of a year, counting the leap days in between
+#[cfg(not(feature = "fast-tables"))]
+#[inline]
+fn days_before_year(year: u16) -> u32 {
+    let leap_years = ((year - 1) - 1968) / 4 - ((year - 1) - 1900) / 100 + ((year - 1) - 1600) / 400;
+    (u32::from(year) - 1970) * 365 + u32::from(leap_years)
+}
+
+// Days from January 1st to the first of a month. Pretending February has 30 days makes the
+// month lengths follow a line, which is then corrected for the real length of February.
+#[cfg(not(feature = "fast-tables"))]
+#[inline]
+fn days_before_month(year: u16, mon: u8) -> u32 {
+    debug_assert!((1..=12).contains(&mon));
+
+    let days = (367 * u32::from(mon) - 362) / 12;
+    if mon <= 2 {
+        days
+    } else if is_leap_year(year) {
+        days - 1
+    } else {
+        days - 2
+    }
+}
+
+
+// Days from January 1st, 1600 to January 1st of each year of a 400 year cycle,
+// after which the calendar repeats with 146097 more days
+#[cfg(feature = "fast-tables")]
+static YEAR_STARTS: [u32; 400] = {
+    let mut starts = [0; 400];
+    let mut i = 1;
+    while i < 400 {
+        starts[i] = starts[i - 1] + days_in_year(1600 + i as u16 - 1) as u32;
+        i += 1;
+    }
+    starts
+};
+
+#[cfg(feature = "fast-tables")]
+#[inline]
+fn days_before_year(year: u16) -> u32 {
+    let cycles = u32::from(year - 1600) / 400;
+    cycles * 146097 + YEAR_STARTS[usize::from(year - 1600) % 400] - YEAR_STARTS[1970 - 1600]
+}
+
+// Days from January 1st to the first of each month, for common years and then leap years
+#[cfg(feature = "fast-tables")]
+static MONTH_STARTS: [[u16; 12]; 2] = {
+    let mut starts = [[0; 12]; 2];
+    let mut i = 1;
+    while i < 12 {
+        starts[0][i] = starts[0][i - 1] + days_in_month(2015, i as u8).unwrap() as u16;
+        starts[1][i] = starts[1][i - 1] + days_in_month(2016, i as u8).unwrap() as u16;
+        i += 1;
+    }
+    starts
+};
+
+#[cfg(feature = "fast-tables")]
+#[inline]
+fn days_before_month(year: u16, mon: u8) -> u32 {
+    u32::from(MONTH_STARTS[usize::from(is_leap_year(year))][usize::from(mon - 1)])
+}
+
+
+/// Three letter weekday names, in the order of [Weekday] starting from Monday.
+///
+/// The formatter, the parsers, and [Weekday::short_name] all share this one table.
+pub(crate) static WEEKDAY_NAMES: [[u8; 3]; 7] = [*b"Mon", *b"Tue", *b"Wed", *b"Thu", *b"Fri", *b"Sat", *b"Sun"];
+
+/// Three letter month names, from January.
+pub(crate) static MONTH_NAMES: [[u8; 3]; 12] = [
+    *b"Jan", *b"Feb", *b"Mar", *b"Apr", *b"May", *b"Jun",
+    *b"Jul", *b"Aug", *b"Sep", *b"Oct", *b"Nov", *b"Dec",
+];
+
+
+// View a name from the tables above as a string
+const fn name_str(name: &'static [u8; 3]) -> &'static str {
+    match core::str::from_utf8(name) {
+        Ok(name) => name,
+        Err(_) => unreachable!(),
+    }
+}
+
+
+/// A day of the week.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    // Weekday of a day since the epoch, which was a Thursday
+    #[inline]
+    pub(crate) const fn from_days(days: u32) -> Weekday {
+        Weekday::from_number_from_sunday(((days + 4) % 7) as u8)
+    }
+
+    // Inverse of number_from_sunday, for values 0 through 6
+    #[inline]
+    pub(crate) const fn from_number_from_sunday(number: u8) -> Weekday {
+        match number {
+            0 => Weekday::Sunday,
+            1 => Weekday::Monday,
+            2 => Weekday::Tuesday,
+            3 => Weekday::Wednesday,
+            4 => Weekday::Thursday,
+            5 => Weekday::Friday,
+            6 => Weekday::Saturday,
+            _ => unreachable!(),
+        }
+    }
+
+    /// ISO 8601 day number, from 1 for Monday through 7 for Sunday.
+    pub const fn number_from_monday(self) -> u8 {
+        self as u8 + 1
+    }
+
+    /// Day number from 0 for Sunday through 6 for Saturday, as used by C's `tm_wday`.
+    pub const fn number_from_sunday(self) -> u8 {
+        (self as u8 + 1) % 7
+    }
+
+    /// Three letter abbreviation, as used in IMF-fixdate: `Mon`, `Tue`, etc.
+    pub const fn short_name(self) -> &'static str {
+        name_str(&WEEKDAY_NAMES[self as usize])
+    }
+}
+
+
+
+/// A month of the year.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Month {
+    January = 1,
+    February,
+    March,
+    April,
+    May,
+    June,
+    July,
+    August,
+    September,
+    October,
+    November,
+    December,
+}
+
+impl Month {
+    /// Month from its number, from 1 for January through 12 for December.
+    pub const fn from_number(number: u8) -> Option<Month> {
+        match number {
+            1 => Some(Month::January),
+            2 => Some(Month::February),
+            3 => Some(Month::March),
+            4 => Some(Month::April),
+            5 => Some(Month::May),
+            6 => Some(Month::June),
+            7 => Some(Month::July),
+            8 => Some(Month::August),
+            9 => Some(Month::September),
+            10 => Some(Month::October),
+            11 => Some(Month::November),
+            12 => Some(Month::December),
+            _ => None,
+        }
+    }
+
+    /// Month number, from 1 for January through 12 for December.
+    pub const fn number(self) -> u8 {
+        self as u8
+    }
+
+    /// Three letter abbreviation, as used in IMF-fixdate: `Jan`, `Feb`, etc.
+    pub const fn short_name(self) -> &'static str {
+        name_str(&MONTH_NAMES[self as usize - 1])
+    }
+}
+
+
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+    use crate::*;
+    use super::*;
+
+
+
+    #[test]
+    fn test_weekday_of() {
+        let cases = [
+            (0, Weekday::Thursday), // The epoch
+            (86399, Weekday::Thursday), // Last second of the epoch's day
+            (86400, Weekday::Friday),
+            (784111777, Weekday::Sunday),
+            (951782400, Weekday::Tuesday), // Feb 29th, 2000
+            (253402300799, Weekday::Friday), // Last formattable second
+        ];
+
+        for (timestamp, weekday) in cases {
+            assert_eq!(weekday_of(timestamp), weekday, "{timestamp}");
+        }
+
+        assert_eq!(Weekday::Monday.number_from_monday(), 1);
+        assert_eq!(Weekday::Sunday.number_from_monday(), 7);
+        assert_eq!(Weekday::Sunday.number_from_sunday(), 0);
+        assert_eq!(Weekday::Saturday.number_from_sunday(), 6);
+        assert_eq!(Weekday::Wednesday.short_name(), "Wed");
+    }
+
+
+
+    #[test]
+    fn test_calendar_utilities() {
+        assert!(is_leap_year(1972));
+        assert!(is_leap_year(2000)); // Multiple of 400
+        assert!(is_leap_year(2400));
+        assert!(!is_leap_year(1970));
+        assert!(!is_leap_year(1900)); // Multiple of 100
+        assert!(!is_leap_year(2100));
+        assert!(!is_leap_year(9999));
+
+        assert_eq!(days_in_year(1970), 365);
+        assert_eq!(days_in_year(1972), 366);
+        assert_eq!(days_in_year(2100), 365);
+
+        // Each year's months add up to the year's length
+        for year in [1970, 1972, 1900, 2000, 2100, 9999] {
+            let total: u16 = (1..=12).map(|month| u16::from(days_in_month(year, month).unwrap())).sum();
+            assert_eq!(total, days_in_year(year), "{year}");
+        }
+
+        assert_eq!(days_in_month(1972, 2), Some(29));
+        assert_eq!(days_in_month(1900, 2), Some(28));
+        assert_eq!(days_in_month(2015, 4), Some(30));
+        assert_eq!(days_in_month(2015, 0), None);
+        assert_eq!(days_in_month(2015, 13), None);
+    }
+
+
+
+    #[test]
+    fn test_ordinal_day() {
+        let cases = [
+            (0, 1), // Jan 1st, 1970
+            (86400 * 31, 32), // Feb 1st, 1970
+            (31535999, 365), // Dec 31st, 1970
+            (68169600, 60), // Feb 29th, 1972
+            (94694399, 366), // Dec 31st, 1972
+            (978307199, 366), // Dec 31st, 2000
+            (253402300799, 365), // Dec 31st, 9999
+        ];
+
+        for (timestamp, ordinal) in cases {
+            assert_eq!(ordinal_day(timestamp), Ok(ordinal), "{timestamp}");
+        }
+
+        assert_eq!(ordinal_day(MAX_FORMATTABLE), Ok(365));
+        assert_eq!(ordinal_day(MAX_FORMATTABLE + 1), Err(TooFuturistic));
+        assert_eq!(ordinal_day(100000000000000), Err(TooFuturistic));
+        assert_eq!(ordinal_day(u64::MAX), Err(TooFuturistic));
+    }
+
+
+    #[test]
+    fn test_iso_week() {
+        let cases = [
+            (0, (1970, 1, Weekday::Thursday)), // Jan 1st, 1970
+            (86400 * 4, (1970, 2, Weekday::Monday)), // Jan 5th, 1970
+            (1104537600, (2004, 53, Weekday::Saturday)), // Jan 1st, 2005
+            (1230595200, (2009, 1, Weekday::Tuesday)), // Dec 30th, 2008
+            (1262304000, (2009, 53, Weekday::Friday)), // Jan 1st, 2010
+            (1451606400, (2015, 53, Weekday::Friday)), // Jan 1st, 2016
+            (1483142400, (2016, 52, Weekday::Saturday)), // Dec 31st, 2016
+            (1514678400, (2017, 52, Weekday::Sunday)), // Dec 31st, 2017
+            (1546214400, (2019, 1, Weekday::Monday)), // Dec 31st, 2018
+            (253402214400, (9999, 52, Weekday::Friday)), // Dec 31st, 9999
+        ];
+
+        for (timestamp, week) in cases {
+            assert_eq!(iso_week(timestamp), Ok(week), "{timestamp}");
+        }
+
+        assert_eq!(iso_week(MAX_FORMATTABLE), Ok((9999, 52, Weekday::Friday)));
+        assert_eq!(iso_week(MAX_FORMATTABLE + 1), Err(TooFuturistic));
+        assert_eq!(iso_week(100000000000000), Err(TooFuturistic));
+        assert_eq!(iso_week(u64::MAX), Err(TooFuturistic));
+    }
+
+
+
+    #[test]
+    fn test_jdn() {
+        assert_eq!(to_jdn(0), 2440588);
+        assert_eq!(to_jdn(86399), 2440588);
+        assert_eq!(to_jdn(86400), 2440589);
+        assert_eq!(to_jdn(946684800), 2451545); // Jan 1st, 2000
+        assert_eq!(to_jdn(253402300799), 5373484); // Dec 31st, 9999
+
+        assert_eq!(from_jdn(2440588, 0), Some(0));
+        assert_eq!(from_jdn(2440588, 86399), Some(86399));
+        assert_eq!(from_jdn(2451545, 43200), Some(946728000)); // J2000.0
+        assert_eq!(from_jdn(2440588, 86400), None);
+        assert_eq!(from_jdn(0, 0), None);
+        assert_eq!(from_jdn(u64::MAX, 0), None); // Overflows
+
+        for timestamp in [0, 784111777, 951782400, 253402300799] {
+            assert_eq!(from_jdn(to_jdn(timestamp), (timestamp % 86400) as u32), Some(timestamp));
+        }
+    }
+
+
+
+    #[test]
+    fn test_truncation() {
+        assert_eq!(start_of_day(0), 0);
+        assert_eq!(start_of_day(86399), 0);
+        assert_eq!(start_of_day(86400), 86400);
+        assert_eq!(start_of_day(u64::MAX), u64::MAX - u64::MAX % 86400);
+
+        assert_eq!(start_of_hour(3599), 0);
+        assert_eq!(start_of_hour(3600), 3600);
+        assert_eq!(start_of_hour(784111777), 784108800);
+
+        assert_eq!(start_of_minute(59), 0);
+        assert_eq!(start_of_minute(60), 60);
+        assert_eq!(start_of_minute(784111777), 784111740);
+
+        // Truncated timestamps format with zeroed fields
+        let mut buffer = [0u8; 29];
+        format(start_of_day(784111777), &mut buffer).unwrap();
+        assert_eq!(&buffer, b"Sun, 06 Nov 1994 00:00:00 GMT");
+        format(start_of_hour(784111777), &mut buffer).unwrap();
+        assert_eq!(&buffer, b"Sun, 06 Nov 1994 08:00:00 GMT");
+        format(start_of_minute(784111777), &mut buffer).unwrap();
+        assert_eq!(&buffer, b"Sun, 06 Nov 1994 08:49:00 GMT");
+    }
+
+
+
+    #[test]
+    fn test_month() {
+        for number in 1..=12 {
+            assert_eq!(Month::from_number(number).unwrap().number(), number);
+        }
+
+        assert_eq!(Month::from_number(0), None);
+        assert_eq!(Month::from_number(13), None);
+        assert_eq!(Month::May.short_name(), "May");
+        assert_eq!(Month::September.short_name(), "Sep");
+        assert!(Month::January < Month::December);
+    }
+
+
+
+    #[test]
+    fn test_reciprocal_division() {
+        // Every numerator each divisor is used with
+        for n in 0..86400 {
+            assert_eq!(div_3600(n), n / 3600);
+            assert_eq!(split_time(n), ((n / 3600) as u8, (n % 3600 / 60) as u8, (n % 60) as u8));
+        }
+        for n in 0..3600 {
+            assert_eq!(div_60(n), n / 60);
+        }
+        for n in (0..=14609459).chain([u32::MAX - 146097, u32::MAX - 1, u32::MAX]) {
+            assert_eq!(div_146097(n), n / 146097);
+        }
+
+        let max = (MAX_FORMATTABLE >> 7) as u32;
+        for n in [0, 674, 675, max - 675, max] {
+            assert_eq!(div_675(n), n / 675);
+        }
+    }
+
+
+
+    #[test]
+    fn test_civil_to_days() {
+        assert_eq!(civil_to_days(1970, 1, 1), 0);
+        assert_eq!(civil_to_days(1994, 11, 6), 784111777 / 86400);
+        assert_eq!(civil_to_days(2000, 2, 29), 11016);
+        assert_eq!(civil_to_days(2000, 3, 1), 11017);
+        assert_eq!(civil_to_days(2100, 3, 1), 47541);
+        assert_eq!(civil_to_days(9999, 12, 31), (MAX_FORMATTABLE / 86400) as u32);
+
+        for mon in 1..=12 {
+            let month_len = u32::from(days_in_month(2016, mon).unwrap());
+            assert_eq!(days_before_month(2016, mon) + month_len, if mon == 12 { 366 } else { days_before_month(2016, mon + 1) });
+        }
+    }
+
+
+
+    proptest! {
+        #[test]
+        fn test_civil_to_days_inverts_civil_from_days(days in 0..=(MAX_FORMATTABLE / 86400) as u32) {
+            let (year, mon, day) = civil_from_days(days);
+            assert_eq!(civil_to_days(year, mon, day), days);
+        }
+
+
+        #[test]
+        fn test_split_days(timestamp in 0..=MAX_FORMATTABLE) {
+            assert_eq!(split_days(timestamp), ((timestamp / 86400) as u32, (timestamp % 86400) as u32));
+        }
+    }
+}
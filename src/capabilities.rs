@@ -0,0 +1,158 @@
+/// One of the three date formats allowed in HTTP headers.
+///
+/// See [RFC9110 Section 5.6.7](https://datatracker.ietf.org/doc/html/rfc9110#section-5.6.7) for more information.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Format {
+    /// `Sun, 06 Nov 1994 08:49:37 GMT`, the preferred format and the only one produced by [format](crate::format)
+    ImfFixdate,
+    /// `Sunday, 06-Nov-94 08:49:37 GMT`, the obsolete RFC 850 format
+    Rfc850,
+    /// `Sun Nov  6 08:49:37 1994`, the format of C's `asctime()`
+    Asctime,
+}
+
+
+/// The date formats and optional cargo features compiled into this build, as returned by [capabilities].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Capabilities {
+    /// Formats accepted by [parse](crate::parse)
+    pub formats: &'static [Format],
+    /// Names of the enabled cargo features
+    pub features: &'static [&'static str],
+}
+
+impl Capabilities {
+    /// Check whether the named cargo feature was enabled.
+    pub fn has_feature(&self, name: &str) -> bool {
+        self.features.contains(&name)
+    }
+}
+
+
+
+
+/// Report which date formats and optional features this build supports,
+/// so a server can log them or a conformance suite can adapt to the build configuration.
+///
+/// ```rust
+/// use date_header::Format;
+///
+/// let capabilities = date_header::capabilities();
+/// assert!(capabilities.formats.contains(&Format::ImfFixdate));
+/// println!("date_header features: {:?}", capabilities.features);
+/// ```
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        formats: &[Format::ImfFixdate, Format::Rfc850, Format::Asctime],
+        features: &[
+            #[cfg(feature = "std")]
+            "std",
+            #[cfg(feature = "http")]
+            "http",
+            #[cfg(feature = "httparse")]
+            "httparse",
+            #[cfg(feature = "u32")]
+            "u32",
+            #[cfg(feature = "prost")]
+            "prost",
+            #[cfg(feature = "chrono")]
+            "chrono",
+            #[cfg(feature = "time")]
+            "time",
+            #[cfg(feature = "jiff")]
+            "jiff",
+            #[cfg(feature = "serde")]
+            "serde",
+            #[cfg(feature = "rkyv")]
+            "rkyv",
+            #[cfg(feature = "schemars")]
+            "schemars",
+            #[cfg(feature = "borsh")]
+            "borsh",
+            #[cfg(feature = "wasm")]
+            "wasm",
+            #[cfg(feature = "ffi")]
+            "ffi",
+            #[cfg(feature = "defmt")]
+            "defmt",
+            #[cfg(feature = "ufmt-write")]
+            "ufmt-write",
+            #[cfg(feature = "no-panic")]
+            "no-panic",
+            #[cfg(feature = "cli")]
+            "cli",
+            #[cfg(feature = "winnow")]
+            "winnow",
+            #[cfg(feature = "axum")]
+            "axum",
+            #[cfg(feature = "tracing")]
+            "tracing",
+            #[cfg(feature = "uuid")]
+            "uuid",
+            #[cfg(feature = "unsafe-fast")]
+            "unsafe-fast",
+            #[cfg(feature = "simd")]
+            "simd",
+            #[cfg(feature = "rayon")]
+            "rayon",
+            #[cfg(feature = "fast-tables")]
+            "fast-tables",
+            #[cfg(feature = "test-vectors")]
+            "test-vectors",
+            #[cfg(feature = "proptest")]
+            "proptest",
+            #[cfg(feature = "quickcheck")]
+            "quickcheck",
+            #[cfg(feature = "rand")]
+            "rand",
+        ],
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+
+
+    #[test]
+    fn test_capabilities() {
+        let capabilities = capabilities();
+        assert_eq!(capabilities.formats, [Format::ImfFixdate, Format::Rfc850, Format::Asctime]);
+
+        assert_eq!(capabilities.has_feature("std"), cfg!(feature = "std"));
+        assert_eq!(capabilities.has_feature("http"), cfg!(feature = "http"));
+        assert_eq!(capabilities.has_feature("httparse"), cfg!(feature = "httparse"));
+        assert_eq!(capabilities.has_feature("u32"), cfg!(feature = "u32"));
+        assert_eq!(capabilities.has_feature("prost"), cfg!(feature = "prost"));
+        assert_eq!(capabilities.has_feature("chrono"), cfg!(feature = "chrono"));
+        assert_eq!(capabilities.has_feature("time"), cfg!(feature = "time"));
+        assert_eq!(capabilities.has_feature("jiff"), cfg!(feature = "jiff"));
+        assert_eq!(capabilities.has_feature("serde"), cfg!(feature = "serde"));
+        assert_eq!(capabilities.has_feature("rkyv"), cfg!(feature = "rkyv"));
+        assert_eq!(capabilities.has_feature("schemars"), cfg!(feature = "schemars"));
+        assert_eq!(capabilities.has_feature("borsh"), cfg!(feature = "borsh"));
+        assert_eq!(capabilities.has_feature("wasm"), cfg!(feature = "wasm"));
+        assert_eq!(capabilities.has_feature("ffi"), cfg!(feature = "ffi"));
+        assert_eq!(capabilities.has_feature("defmt"), cfg!(feature = "defmt"));
+        assert_eq!(capabilities.has_feature("ufmt-write"), cfg!(feature = "ufmt-write"));
+        assert_eq!(capabilities.has_feature("no-panic"), cfg!(feature = "no-panic"));
+        assert_eq!(capabilities.has_feature("cli"), cfg!(feature = "cli"));
+        assert_eq!(capabilities.has_feature("winnow"), cfg!(feature = "winnow"));
+        assert_eq!(capabilities.has_feature("axum"), cfg!(feature = "axum"));
+        assert_eq!(capabilities.has_feature("tracing"), cfg!(feature = "tracing"));
+        assert_eq!(capabilities.has_feature("uuid"), cfg!(feature = "uuid"));
+        assert_eq!(capabilities.has_feature("unsafe-fast"), cfg!(feature = "unsafe-fast"));
+        assert_eq!(capabilities.has_feature("simd"), cfg!(feature = "simd"));
+        assert_eq!(capabilities.has_feature("rayon"), cfg!(feature = "rayon"));
+        assert_eq!(capabilities.has_feature("fast-tables"), cfg!(feature = "fast-tables"));
+        assert_eq!(capabilities.has_feature("test-vectors"), cfg!(feature = "test-vectors"));
+        assert_eq!(capabilities.has_feature("proptest"), cfg!(feature = "proptest"));
+        assert_eq!(capabilities.has_feature("quickcheck"), cfg!(feature = "quickcheck"));
+        assert_eq!(capabilities.has_feature("rand"), cfg!(feature = "rand"));
+        assert!(!capabilities.has_feature("default"));
+    }
+}
@@ -0,0 +1,137 @@
+//! Conversions between [chrono](::chrono)'s `DateTime<Utc>` and unix timestamps,
+//! for projects already using chrono that want this crate's header formatting.
+//!
+//! HTTP dates only have whole seconds, so converting from chrono truncates toward the past,
+//! which is the right direction for `Last-Modified` and other validators.
+//! A leap second is truncated to the second before it, since unix time can't represent it.
+//!
+//! ```rust
+//! use chrono::{TimeZone, Utc};
+//!
+//! let modified = Utc.with_ymd_and_hms(2015, 5, 15, 15, 34, 21).unwrap();
+//! assert_eq!(Ok(1431704061), date_header::chrono::from_chrono(&modified));
+//! assert_eq!(Ok(modified), date_header::chrono::to_chrono(1431704061));
+//!
+//! let mut header = [0u8; 29];
+//! date_header::chrono::format_chrono(&modified, &mut header).unwrap();
+//! assert_eq!(&header, b"Fri, 15 May 2015 15:34:21 GMT");
+//! ```
+
+use ::chrono::{DateTime, Utc};
+use crate::{format, in_formattable_range, Timestamp, TooFuturistic, FORMATTED_LEN};
+
+
+
+
+/// Convert a chrono date time into a unix timestamp, discarding any fractional second.
+///
+/// Fails for times before the unix epoch or after the year 9999.
+pub fn from_chrono(time: &DateTime<Utc>) -> Result<u64, OutOfRange> {
+    match u64::try_from(time.timestamp()) {
+        Ok(secs) if in_formattable_range(secs) => Ok(secs),
+        _ => Err(OutOfRange),
+    }
+}
+
+
+/// Convert a unix timestamp into a chrono date time.
+///
+/// Fails for times after the year 9999.
+pub fn to_chrono(secs_since_epoch: u64) -> Result<DateTime<Utc>, TooFuturistic> {
+    if !in_formattable_range(secs_since_epoch) {
+        return Err(TooFuturistic);
+    }
+
+    Ok(DateTime::from_timestamp(secs_since_epoch as i64, 0).expect("The year 9999 is within chrono's range"))
+}
+
+
+/// Format a chrono date time as an IMF-fixdate into the provided buffer, as [format] does.
+///
+/// Fails for times before the unix epoch or after the year 9999.
+pub fn format_chrono(time: &DateTime<Utc>, buffer: &mut [u8; FORMATTED_LEN]) -> Result<(), OutOfRange> {
+    let secs = from_chrono(time)?;
+    format(secs, buffer).map_err(|_| OutOfRange)
+}
+
+
+/// Error returned from [from_chrono] and [format_chrono] indicating that the time can't be an HTTP date.
+#[derive(Debug, Eq, PartialEq)]
+pub struct OutOfRange;
+
+
+
+
+impl TryFrom<DateTime<Utc>> for Timestamp {
+    type Error = OutOfRange;
+
+    fn try_from(time: DateTime<Utc>) -> Result<Timestamp, OutOfRange> {
+        from_chrono(&time).map(|secs| Timestamp::new(secs).expect("from_chrono is always in the formattable range"))
+    }
+}
+
+impl From<Timestamp> for DateTime<Utc> {
+    fn from(timestamp: Timestamp) -> DateTime<Utc> {
+        to_chrono(timestamp.as_secs()).expect("Timestamp is always in the formattable range")
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use ::chrono::{NaiveDate, TimeZone};
+    use crate::MAX_FORMATTABLE;
+    use super::*;
+
+
+
+    fn utc(seconds: i64, nanos: u32) -> DateTime<Utc> {
+        DateTime::from_timestamp(seconds, nanos).unwrap()
+    }
+
+
+    #[test]
+    fn test_from_chrono() {
+        assert_eq!(from_chrono(&utc(0, 0)), Ok(0));
+        assert_eq!(from_chrono(&utc(784111777, 999_999_999)), Ok(784111777)); // Truncated, not rounded
+        assert_eq!(from_chrono(&utc(MAX_FORMATTABLE as i64, 999_999_999)), Ok(MAX_FORMATTABLE));
+
+        assert_eq!(from_chrono(&utc(-1, 999_999_999)), Err(OutOfRange)); // Still before the epoch
+        assert_eq!(from_chrono(&utc(MAX_FORMATTABLE as i64 + 1, 0)), Err(OutOfRange));
+
+        // chrono represents a leap second as a second with more than a billion nanoseconds
+        let leap = NaiveDate::from_ymd_opt(2016, 12, 31).unwrap().and_hms_nano_opt(23, 59, 59, 1_500_000_000).unwrap();
+        assert_eq!(from_chrono(&Utc.from_utc_datetime(&leap)), Ok(1483228799));
+    }
+
+
+    #[test]
+    fn test_to_chrono() {
+        assert_eq!(to_chrono(0), Ok(utc(0, 0)));
+        assert_eq!(to_chrono(784111777), Ok(utc(784111777, 0)));
+        assert_eq!(to_chrono(MAX_FORMATTABLE), Ok(utc(MAX_FORMATTABLE as i64, 0)));
+        assert_eq!(to_chrono(MAX_FORMATTABLE + 1), Err(TooFuturistic));
+        assert_eq!(to_chrono(u64::MAX), Err(TooFuturistic));
+    }
+
+
+    #[test]
+    fn test_format_chrono() {
+        let mut buffer = [0; FORMATTED_LEN];
+        assert_eq!(format_chrono(&utc(784111777, 500_000_000), &mut buffer), Ok(()));
+        assert_eq!(&buffer, b"Sun, 06 Nov 1994 08:49:37 GMT");
+
+        assert_eq!(format_chrono(&utc(-1, 0), &mut buffer), Err(OutOfRange));
+        assert_eq!(format_chrono(&utc(MAX_FORMATTABLE as i64 + 1, 0), &mut buffer), Err(OutOfRange));
+    }
+
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Timestamp::try_from(utc(784111777, 500_000_000)), Ok(Timestamp::new(784111777).unwrap()));
+        assert_eq!(Timestamp::try_from(utc(-1, 0)), Err(OutOfRange));
+        assert_eq!(DateTime::<Utc>::from(Timestamp::MAX), utc(MAX_FORMATTABLE as i64, 0));
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{invalid, parse_components, write_imf_fixdate, InvalidDate, FORMATTED_LEN};
+
+
+
+
+/// Format a 32-bit unix timestamp as an IMF-fixdate, as [format](crate::format) does.
+///
+/// Every `u32` timestamp is before the year 2106, so this cannot fail. The conversion never
+/// divides a 64-bit value, which is much cheaper on microcontrollers without 64-bit division.
+///
+/// ```rust
+/// let mut header = [0u8; 29];
+/// date_header::format_u32(1431704061, &mut header);
+/// assert_eq!(&header, b"Fri, 15 May 2015 15:34:21 GMT");
+/// ```
+pub fn format_u32(secs_since_epoch: u32, buffer: &mut [u8; FORMATTED_LEN]) {
+    write_imf_fixdate(secs_since_epoch / 86400, secs_since_epoch % 86400, buffer);
+}
+
+
+/// Parse an HTTP date header into a 32-bit unix timestamp, as [parse](crate::parse) does.
+///
+/// Dates after `Sun, 07 Feb 2106 06:28:15 GMT` don't fit in a `u32` and are rejected.
+/// The timestamp is computed in 32 bits, without the 64-bit multiplication [parse](crate::parse) needs.
+///
+/// ```rust
+/// assert_eq!(Ok(4294967295), date_header::parse_u32(b"Sun, 07 Feb 2106 06:28:15 GMT"));
+/// assert!(date_header::parse_u32(b"Sun, 07 Feb 2106 06:28:16 GMT").is_err());
+/// ```
+pub fn parse_u32(header: &[u8]) -> Result<u32, InvalidDate> {
+    let (date, days) = parse_components(header)?;
+    let secs_of_day = u32::from(date.hour) * 3600 + u32::from(date.min) * 60 + u32::from(date.sec);
+
+    days.checked_mul(86400)
+        .and_then(|secs| secs.checked_add(secs_of_day))
+        .ok_or_else(invalid)
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+    use crate::*;
+
+
+
+    #[test]
+    fn test_u32_bounds() {
+        let mut buffer = [0u8; FORMATTED_LEN];
+
+        format_u32(0, &mut buffer);
+        assert_eq!(&buffer, b"Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(parse_u32(&buffer), Ok(0));
+
+        format_u32(u32::MAX, &mut buffer);
+        assert_eq!(&buffer, b"Sun, 07 Feb 2106 06:28:15 GMT");
+        assert_eq!(parse_u32(&buffer), Ok(u32::MAX));
+
+        assert_eq!(parse_u32(b"Sun, 07 Feb 2106 06:28:16 GMT"), Err(InvalidDate)); // Overflows adding the time of day
+        assert_eq!(parse_u32(b"Mon, 08 Feb 2106 00:00:00 GMT"), Err(InvalidDate)); // Overflows multiplying the days
+        assert_eq!(parse_u32(b"Sun, 07 Feb 2106 06:28:15 GMX"), Err(InvalidDate));
+        assert_eq!(parse_u32(b"Fri, 31 Dec 9999 23:59:59 GMT"), Err(InvalidDate));
+        assert_eq!(parse_u32(b"Sun Nov  6 08:49:37 1994"), Ok(784111777));
+    }
+
+
+
+    proptest! {
+        #[test]
+        fn test_u32_matches_u64(timestamp: u32) {
+            let mut expected = [0u8; FORMATTED_LEN];
+            format(u64::from(timestamp), &mut expected).unwrap();
+
+            let mut buffer = [0u8; FORMATTED_LEN];
+            format_u32(timestamp, &mut buffer);
+            assert_eq!(buffer, expected);
+            assert_eq!(parse_u32(&buffer), Ok(timestamp));
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use core::cmp::Ordering;
+use crate::{parse, HttpDate, InvalidDate};
+
+
+
+
+/// Parse two HTTP date headers and return the number of seconds from `a` to `b`.
+///
+/// The result is positive when `b` is later than `a`. The headers may be in different formats.
+///
+/// ```rust
+/// let date = b"Fri, 15 May 2015 15:34:21 GMT";
+/// let expires = b"Fri, 15 May 2015 16:34:21 GMT";
+/// assert_eq!(Ok(3600), date_header::between(date, expires));
+/// assert_eq!(Ok(-3600), date_header::between(expires, date));
+/// ```
+pub fn between(a: &[u8], b: &[u8]) -> Result<i64, InvalidDate> {
+    let a = parse(a)?;
+    let b = parse(b)?;
+
+    // Parsed dates are always before the year 10000, so they fit in an i64
+    Ok(b as i64 - a as i64)
+}
+
+
+
+/// Compare the instants represented by two HTTP date headers, which may be in different formats.
+///
+/// Both dates are validated as [parse] does, but are then compared by their calendar components
+/// from most to least significant, without computing either timestamp.
+///
+/// ```rust
+/// use std::cmp::Ordering;
+///
+/// let a = b"Fri, 15 May 2015 15:34:21 GMT";
+/// let b = b"Friday, 15-May-15 15:34:21 GMT";
+/// assert_eq!(Ok(Ordering::Equal), date_header::compare(a, b));
+/// assert_eq!(Ok(Ordering::Less), date_header::compare(b"Thu, 01 Jan 1970 00:00:00 GMT", a));
+/// ```
+pub fn compare(a: &[u8], b: &[u8]) -> Result<Ordering, InvalidDate> {
+    Ok(HttpDate::parse(a)?.cmp(&HttpDate::parse(b)?))
+}
+
+
+/// Parse two HTTP date headers and return whichever represents the later instant.
+///
+/// The original header is returned, so it can be forwarded without reformatting.
+/// If both represent the same instant, `a` is returned. If either header is invalid, an error is returned.
+///
+/// ```rust
+/// let a = b"Fri, 15 May 2015 15:34:21 GMT";
+/// let b = b"Friday, 15-May-15 16:34:21 GMT";
+/// assert_eq!(Ok(&b[..]), date_header::later_of(a, b));
+/// ```
+pub fn later_of<'a>(a: &'a [u8], b: &'a [u8]) -> Result<&'a [u8], InvalidDate> {
+    if parse(b)? > parse(a)? {
+        Ok(b)
+    } else {
+        Ok(a)
+    }
+}
+
+
+/// Parse two HTTP date headers and return whichever represents the earlier instant.
+///
+/// The original header is returned, so it can be forwarded without reformatting.
+/// If both represent the same instant, `a` is returned. If either header is invalid, an error is returned.
+///
+/// ```rust
+/// let a = b"Fri, 15 May 2015 15:34:21 GMT";
+/// let b = b"Friday, 15-May-15 16:34:21 GMT";
+/// assert_eq!(Ok(&a[..]), date_header::earlier_of(a, b));
+/// ```
+pub fn earlier_of<'a>(a: &'a [u8], b: &'a [u8]) -> Result<&'a [u8], InvalidDate> {
+    if parse(b)? < parse(a)? {
+        Ok(b)
+    } else {
+        Ok(a)
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+
+
+    #[test]
+    fn test_between() {
+        let imf = b"Sun, 06 Nov 1994 08:49:37 GMT";
+        let rfc850 = b"Sunday, 06-Nov-94 08:49:37 GMT";
+        let asctime = b"Sun Nov  6 08:49:37 1994";
+
+        assert_eq!(between(imf, rfc850), Ok(0));
+        assert_eq!(between(rfc850, asctime), Ok(0));
+        assert_eq!(between(b"Thu, 01 Jan 1970 00:00:00 GMT", imf), Ok(784111777));
+        assert_eq!(between(imf, b"Thu, 01 Jan 1970 00:00:00 GMT"), Ok(-784111777));
+        assert_eq!(between(b"Thu, 01 Jan 1970 00:00:00 GMT", b"Fri, 31 Dec 9999 23:59:59 GMT"), Ok(253402300799));
+
+        assert_eq!(between(b"0", imf), Err(InvalidDate));
+        assert_eq!(between(imf, b"0"), Err(InvalidDate));
+    }
+
+
+
+    #[test]
+    fn test_later_and_earlier_of() {
+        let early = &b"Sun, 06 Nov 1994 08:49:37 GMT"[..];
+        let late = &b"Sun Nov  6 08:49:38 1994"[..];
+        let same = &b"Sunday, 06-Nov-94 08:49:37 GMT"[..];
+
+        assert_eq!(later_of(early, late), Ok(late));
+        assert_eq!(later_of(late, early), Ok(late));
+        assert_eq!(earlier_of(early, late), Ok(early));
+        assert_eq!(earlier_of(late, early), Ok(early));
+
+        // Ties return the first argument
+        assert_eq!(later_of(early, same), Ok(early));
+        assert_eq!(later_of(same, early), Ok(same));
+        assert_eq!(earlier_of(early, same), Ok(early));
+        assert_eq!(earlier_of(same, early), Ok(same));
+
+        assert_eq!(later_of(early, b"0"), Err(InvalidDate));
+        assert_eq!(later_of(b"0", early), Err(InvalidDate));
+        assert_eq!(earlier_of(early, b"0"), Err(InvalidDate));
+        assert_eq!(earlier_of(b"0", early), Err(InvalidDate));
+    }
+
+
+
+    #[test]
+    fn test_compare() {
+        use core::cmp::Ordering;
+
+        let imf = b"Sun, 06 Nov 1994 08:49:37 GMT";
+        let rfc850 = b"Sunday, 06-Nov-94 08:49:37 GMT";
+        let asctime = b"Sun Nov  6 08:49:37 1994";
+
+        assert_eq!(compare(imf, rfc850), Ok(Ordering::Equal));
+        assert_eq!(compare(asctime, imf), Ok(Ordering::Equal));
+
+        // Each component is more significant than the ones after it
+        let cases = [
+            (&b"Sun, 06 Nov 1994 08:49:37 GMT"[..], &b"Sun, 06 Nov 1994 08:49:38 GMT"[..]),
+            (b"Sun, 06 Nov 1994 08:49:59 GMT", b"Sun, 06 Nov 1994 08:50:00 GMT"),
+            (b"Sun, 06 Nov 1994 08:59:59 GMT", b"Sun, 06 Nov 1994 09:00:00 GMT"),
+            (b"Sun, 06 Nov 1994 23:59:59 GMT", b"Mon, 07 Nov 1994 00:00:00 GMT"),
+            (b"Mon, 31 Oct 1994 23:59:59 GMT", b"Tue, 01 Nov 1994 00:00:00 GMT"),
+            (b"Sat, 31 Dec 1994 23:59:59 GMT", b"Sun, 01 Jan 1995 00:00:00 GMT"),
+        ];
+
+        for (earlier, later) in cases {
+            assert_eq!(compare(earlier, later), Ok(Ordering::Less));
+            assert_eq!(compare(later, earlier), Ok(Ordering::Greater));
+            assert_eq!(compare(earlier, later), between(earlier, later).map(|difference| 0.cmp(&difference)));
+        }
+
+        assert_eq!(compare(imf, b"0"), Err(InvalidDate));
+        assert_eq!(compare(b"0", imf), Err(InvalidDate));
+    }
+}
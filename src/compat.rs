@@ -0,0 +1,127 @@
+//! Drop-in replacement for the API of the [httpdate](https://docs.rs/httpdate) crate.
+//!
+//! Migrating is usually a matter of replacing `httpdate::` with `date_header::compat::`.
+//! Unlike httpdate, nothing in this module panics: times before the epoch are clamped to the epoch,
+//! and times after the year 9999 are clamped to the last second of 9999.
+//!
+//! ```rust
+//! use std::time::{Duration, UNIX_EPOCH};
+//! use date_header::compat::{fmt_http_date, parse_http_date};
+//!
+//! let time = UNIX_EPOCH + Duration::from_secs(1431704061);
+//! assert_eq!("Fri, 15 May 2015 15:34:21 GMT", fmt_http_date(time));
+//! assert_eq!(Ok(time), parse_http_date("Fri, 15 May 2015 15:34:21 GMT"));
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+use std::string::String;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::{clamp_to_range, format, parse};
+
+
+
+
+/// Format a [SystemTime] as an IMF-fixdate, discarding any fractional seconds.
+pub fn fmt_http_date(time: SystemTime) -> String {
+    HttpDate::from(time).to_string()
+}
+
+
+/// Parse an HTTP date in any of the formats accepted by [parse] into a [SystemTime].
+pub fn parse_http_date(text: &str) -> Result<SystemTime, Error> {
+    text.parse::<HttpDate>().map(SystemTime::from)
+}
+
+
+/// An HTTP date with one second precision, equivalent to httpdate's `HttpDate`.
+///
+/// ```rust
+/// use date_header::compat::HttpDate;
+///
+/// let date: HttpDate = "Sun Nov  6 08:49:37 1994".parse().unwrap();
+/// assert_eq!("Sun, 06 Nov 1994 08:49:37 GMT", date.to_string());
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct HttpDate(u64);
+
+impl From<SystemTime> for HttpDate {
+    fn from(time: SystemTime) -> HttpDate {
+        let secs_since_epoch = time
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+
+        HttpDate(clamp_to_range(secs_since_epoch))
+    }
+}
+
+impl From<HttpDate> for SystemTime {
+    fn from(date: HttpDate) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(date.0)
+    }
+}
+
+impl FromStr for HttpDate {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<HttpDate, Error> {
+        parse(text.as_bytes()).map(HttpDate).map_err(|_| Error(()))
+    }
+}
+
+impl fmt::Display for HttpDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buffer = [0u8; 29];
+        format(self.0, &mut buffer).expect("HttpDate is always in the formattable range");
+        f.write_str(std::str::from_utf8(&buffer).expect("IMF-fixdate is ASCII"))
+    }
+}
+
+
+/// Error returned when parsing an [HttpDate] fails, equivalent to httpdate's `Error`.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Error(());
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("string contains no or an invalid date")
+    }
+}
+
+impl std::error::Error for Error {}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, UNIX_EPOCH};
+    use super::*;
+
+
+
+    #[test]
+    fn test_compat() {
+        let time = UNIX_EPOCH + Duration::from_secs(784111777);
+        assert_eq!(fmt_http_date(time), "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(fmt_http_date(time + Duration::from_millis(999)), "Sun, 06 Nov 1994 08:49:37 GMT"); // Truncated
+
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"), Ok(time));
+        assert_eq!(parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT"), Ok(time));
+        assert_eq!(parse_http_date("Sun Nov  6 08:49:37 1994"), Ok(time));
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994"), Err(Error(())));
+
+        // Out of range times are clamped rather than panicking
+        assert_eq!(fmt_http_date(UNIX_EPOCH - Duration::from_secs(1)), "Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(fmt_http_date(UNIX_EPOCH + Duration::from_secs(crate::MAX_FORMATTABLE + 1)), "Fri, 31 Dec 9999 23:59:59 GMT");
+
+        let earlier: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+        let later = HttpDate::from(time + Duration::from_secs(1));
+        assert!(earlier < later);
+        assert_eq!(later.to_string(), "Sun, 06 Nov 1994 08:49:38 GMT");
+        assert_eq!(SystemTime::from(earlier), time);
+
+        assert_eq!(Error(()).to_string(), "string contains no or an invalid date");
+    }
+}
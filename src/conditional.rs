@@ -0,0 +1,207 @@
+//! Evaluation of the date-based conditional request header fields.
+//!
+//! See [RFC9110 Section 13](https://datatracker.ietf.org/doc/html/rfc9110#section-13) for more information.
+
+use crate::parse;
+
+
+
+
+/// Evaluate an `If-Modified-Since` header against the selected representation's last modification time.
+///
+/// Returns `true` when the representation has not been modified since the date in the header,
+/// meaning the server should respond with `304 Not Modified`.
+///
+/// Per RFC9110, a recipient must ignore an `If-Modified-Since` field that is not a valid HTTP-date.
+/// An unparseable header is therefore treated as if no condition were present, and this function
+/// returns `false`.
+///
+/// ```rust
+/// let last_modified = 1431704061; // Fri, 15 May 2015 15:34:21 GMT
+/// assert!(date_header::not_modified(last_modified, b"Fri, 15 May 2015 15:34:21 GMT"));
+/// assert!(!date_header::not_modified(last_modified, b"Fri, 15 May 2015 15:34:20 GMT"));
+/// assert!(!date_header::not_modified(last_modified, b"not a date"));
+/// ```
+pub fn not_modified(last_modified: u64, if_modified_since: &[u8]) -> bool {
+    match parse(if_modified_since) {
+        Ok(if_modified_since) => last_modified <= if_modified_since,
+        Err(_) => false,
+    }
+}
+
+
+
+/// Evaluate an `If-Unmodified-Since` header against the selected representation's last modification time.
+///
+/// Returns `true` when the representation has been modified since the date in the header,
+/// meaning the server should respond with `412 Precondition Failed`.
+///
+/// Per RFC9110, a recipient must ignore an `If-Unmodified-Since` field that is not a valid HTTP-date.
+/// An unparseable header is therefore treated as if no condition were present, and this function
+/// returns `false`.
+///
+/// ```rust
+/// let last_modified = 1431704061; // Fri, 15 May 2015 15:34:21 GMT
+/// assert!(!date_header::precondition_failed(last_modified, b"Fri, 15 May 2015 15:34:21 GMT"));
+/// assert!(date_header::precondition_failed(last_modified, b"Fri, 15 May 2015 15:34:20 GMT"));
+/// assert!(!date_header::precondition_failed(last_modified, b"not a date"));
+/// ```
+pub fn precondition_failed(last_modified: u64, if_unmodified_since: &[u8]) -> bool {
+    match parse(if_unmodified_since) {
+        Ok(if_unmodified_since) => last_modified > if_unmodified_since,
+        Err(_) => false,
+    }
+}
+
+
+
+/// Evaluate an `If-Range` header holding an HTTP date against the selected representation's last modification time.
+///
+/// Returns `true` when the server should honor the `Range` header, and `false` when it should
+/// ignore the `Range` header and send the full representation instead.
+///
+/// The condition only holds if the date exactly matches `last_modified` and is a strong validator.
+/// Since `now` is the origin's current time, `last_modified` is only strong if it is at least
+/// one second in the past, as described in [validator_strength].
+/// Invalid dates, including entity-tags, never match.
+/// See [RFC9110 Section 13.1.5](https://datatracker.ietf.org/doc/html/rfc9110#section-13.1.5) for more information.
+///
+/// ```rust
+/// let last_modified = 1431704061; // Fri, 15 May 2015 15:34:21 GMT
+/// let now = last_modified + 60;
+/// assert!(date_header::honor_range(last_modified, b"Fri, 15 May 2015 15:34:21 GMT", now));
+/// assert!(!date_header::honor_range(last_modified, b"Fri, 15 May 2015 15:34:20 GMT", now));
+/// assert!(!date_header::honor_range(last_modified, b"Fri, 15 May 2015 15:34:21 GMT", last_modified));
+/// ```
+pub fn honor_range(last_modified: u64, if_range: &[u8], now: u64) -> bool {
+    match parse(if_range) {
+        Ok(if_range) => if_range == last_modified && validator_strength(last_modified, Some(now)) == ValidatorStrength::Strong,
+        Err(_) => false,
+    }
+}
+
+
+/// Classify whether a `Last-Modified` value may be used as a strong validator.
+///
+/// A `Last-Modified` time is implicitly weak, since a representation may change more than once
+/// within the second it names. RFC9110 allows a client or cache to treat it as strong when the
+/// stored response also carries a `Date` value at least one second after the `Last-Modified` value,
+/// since the representation then could not have changed again within that second.
+///
+/// Pass the `Date` of the stored response, or `None` if the cache entry did not retain one.
+/// The caller is responsible for believing both values were generated by the same clock.
+/// See [RFC9110 Section 8.8.2.2](https://datatracker.ietf.org/doc/html/rfc9110#section-8.8.2.2) for more information.
+///
+/// ```rust
+/// use date_header::ValidatorStrength;
+///
+/// let last_modified = 1431704061;
+/// assert_eq!(ValidatorStrength::Strong, date_header::validator_strength(last_modified, Some(last_modified + 1)));
+/// assert_eq!(ValidatorStrength::Weak, date_header::validator_strength(last_modified, Some(last_modified)));
+/// assert_eq!(ValidatorStrength::Weak, date_header::validator_strength(last_modified, None));
+/// ```
+pub fn validator_strength(last_modified: u64, date: Option<u64>) -> ValidatorStrength {
+    match date {
+        Some(date) if date > last_modified => ValidatorStrength::Strong,
+        _ => ValidatorStrength::Weak,
+    }
+}
+
+
+/// Strength of a `Last-Modified` validator, as returned from [validator_strength].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ValidatorStrength {
+    /// The validator changes whenever the representation changes and may be used for range requests.
+    Strong,
+    /// The validator may be unchanged across changes to the representation.
+    Weak,
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+
+
+    #[test]
+    fn test_not_modified() {
+        let last_modified = 784111777; // Sun, 06 Nov 1994 08:49:37 GMT
+
+        // Same instant in each of the accepted formats
+        assert!(not_modified(last_modified, b"Sun, 06 Nov 1994 08:49:37 GMT"));
+        assert!(not_modified(last_modified, b"Sunday, 06-Nov-94 08:49:37 GMT"));
+        assert!(not_modified(last_modified, b"Sun Nov  6 08:49:37 1994"));
+
+        assert!(not_modified(last_modified, b"Sun, 06 Nov 1994 08:49:38 GMT")); // Header is later
+        assert!(!not_modified(last_modified, b"Sun, 06 Nov 1994 08:49:36 GMT")); // Modified one second after the header
+
+        // Invalid dates are ignored rather than treated as a match
+        assert!(!not_modified(last_modified, b""));
+        assert!(!not_modified(last_modified, b"-1"));
+        assert!(!not_modified(last_modified, b"Mon, 06 Nov 1994 08:49:37 GMT")); // Wrong weekday
+        assert!(!not_modified(0, b"Sun, 06 Nov 1994 08:49:37 GMT, Sun, 06 Nov 1994 08:49:37 GMT")); // Lists are not valid
+    }
+
+
+
+    #[test]
+    fn test_precondition_failed() {
+        let last_modified = 784111777; // Sun, 06 Nov 1994 08:49:37 GMT
+
+        // Same instant in each of the accepted formats
+        assert!(!precondition_failed(last_modified, b"Sun, 06 Nov 1994 08:49:37 GMT"));
+        assert!(!precondition_failed(last_modified, b"Sunday, 06-Nov-94 08:49:37 GMT"));
+        assert!(!precondition_failed(last_modified, b"Sun Nov  6 08:49:37 1994"));
+
+        assert!(!precondition_failed(last_modified, b"Sun, 06 Nov 1994 08:49:38 GMT")); // Header is later
+        assert!(precondition_failed(last_modified, b"Sun, 06 Nov 1994 08:49:36 GMT")); // Modified one second after the header
+
+        // Invalid dates are ignored rather than failing the precondition
+        assert!(!precondition_failed(last_modified, b""));
+        assert!(!precondition_failed(last_modified, b"0"));
+        assert!(!precondition_failed(last_modified, b"Mon, 06 Nov 1994 08:49:37 GMT")); // Wrong weekday
+    }
+
+
+
+    #[test]
+    fn test_validator_strength() {
+        let last_modified = 784111777;
+
+        assert_eq!(validator_strength(last_modified, None), ValidatorStrength::Weak);
+        assert_eq!(validator_strength(last_modified, Some(0)), ValidatorStrength::Weak); // Date before Last-Modified
+        assert_eq!(validator_strength(last_modified, Some(last_modified)), ValidatorStrength::Weak); // Same second
+        assert_eq!(validator_strength(last_modified, Some(last_modified + 1)), ValidatorStrength::Strong); // One second later
+        assert_eq!(validator_strength(last_modified, Some(u64::MAX)), ValidatorStrength::Strong);
+        assert_eq!(validator_strength(u64::MAX, Some(u64::MAX)), ValidatorStrength::Weak);
+    }
+
+
+
+    #[test]
+    fn test_honor_range() {
+        let last_modified = 784111777; // Sun, 06 Nov 1994 08:49:37 GMT
+        let now = last_modified + 1;
+
+        // Same instant in each of the accepted formats
+        assert!(honor_range(last_modified, b"Sun, 06 Nov 1994 08:49:37 GMT", now));
+        assert!(honor_range(last_modified, b"Sunday, 06-Nov-94 08:49:37 GMT", now));
+        assert!(honor_range(last_modified, b"Sun Nov  6 08:49:37 1994", now));
+
+        // Must be an exact match, not just earlier or later
+        assert!(!honor_range(last_modified, b"Sun, 06 Nov 1994 08:49:36 GMT", now));
+        assert!(!honor_range(last_modified, b"Sun, 06 Nov 1994 08:49:38 GMT", now));
+
+        // Weak because the representation may still change within the current second
+        assert!(!honor_range(last_modified, b"Sun, 06 Nov 1994 08:49:37 GMT", last_modified));
+        assert!(!honor_range(last_modified, b"Sun, 06 Nov 1994 08:49:37 GMT", 0));
+
+        // Entity-tags and garbage never match
+        assert!(!honor_range(last_modified, b"\"784111777\"", now));
+        assert!(!honor_range(last_modified, b"W/\"abc\"", now));
+        assert!(!honor_range(last_modified, b"", now));
+    }
+}
@@ -0,0 +1,126 @@
+//! Formatting of the lifetime attributes of a `Set-Cookie` header.
+//!
+//! See [RFC6265](https://datatracker.ietf.org/doc/html/rfc6265#section-4.1.2) for more information.
+//! User agents following [RFC6265bis](https://datatracker.ietf.org/doc/html/draft-ietf-httpbis-rfc6265bis#section-5.6.1)
+//! cap a cookie's lifetime at 400 days.
+
+use crate::{clamp_to_range, delta_seconds, format};
+
+
+
+
+/// Longest lifetime [format_expiry] will give a cookie, the 400 days that user agents allow.
+pub const MAX_LIFETIME: u64 = 400 * 86400;
+
+/// Length of the longest output of [format_expiry]: `Expires=<IMF-fixdate>; Max-Age=<MAX_LIFETIME>`
+pub const MAX_EXPIRY_LEN: usize = 55;
+
+
+
+
+/// Format a cookie's expiry time as `Set-Cookie` attribute bytes, returning the number of bytes written.
+///
+/// `Max-Age` is computed relative to `now`, and is `0` if `expires` has already passed.
+/// Expiry times more than [MAX_LIFETIME] after `now` are shortened to it, and times after the year 9999
+/// are clamped to the last representable second, so that both attributes always name the same time.
+///
+/// ```rust
+/// use date_header::cookie::{format_expiry, Attributes, MAX_EXPIRY_LEN};
+///
+/// let now = 1431704061;
+/// let mut buffer = [0u8; MAX_EXPIRY_LEN];
+///
+/// let len = format_expiry(now + 3600, now, Attributes::Both, &mut buffer);
+/// assert_eq!(&buffer[..len], b"Expires=Fri, 15 May 2015 16:34:21 GMT; Max-Age=3600");
+///
+/// let len = format_expiry(now + 3600, now, Attributes::MaxAge, &mut buffer);
+/// assert_eq!(&buffer[..len], b"Max-Age=3600");
+/// ```
+pub fn format_expiry(expires: u64, now: u64, attributes: Attributes, buffer: &mut [u8; MAX_EXPIRY_LEN]) -> usize {
+    let expires = clamp_to_range(expires.min(now.saturating_add(MAX_LIFETIME)));
+    let mut len = 0;
+
+    if let Attributes::Expires | Attributes::Both = attributes {
+        let mut date = [0u8; 29];
+        format(expires, &mut date).expect("expiry is clamped to the formattable range");
+
+        buffer[..8].copy_from_slice(b"Expires=");
+        buffer[8..37].copy_from_slice(&date);
+        len = 37;
+    }
+
+    if let Attributes::Both = attributes {
+        buffer[len..len + 2].copy_from_slice(b"; ");
+        len += 2;
+    }
+
+    if let Attributes::MaxAge | Attributes::Both = attributes {
+        buffer[len..len + 8].copy_from_slice(b"Max-Age=");
+        len += 8;
+        let mut digits = [0u8; delta_seconds::MAX_LEN];
+        let digits_len = delta_seconds::format(delta_seconds::from_date(expires, now), &mut digits);
+        buffer[len..len + digits_len].copy_from_slice(&digits[..digits_len]);
+        len += digits_len;
+    }
+
+    len
+}
+
+
+/// Which attributes [format_expiry] should emit.
+///
+/// `Max-Age` takes precedence over `Expires` in user agents that support it,
+/// and is immune to clock differences between the server and the client.
+/// Emitting both supports older user agents that only understand `Expires`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Attributes {
+    /// Only `Expires=<IMF-fixdate>`
+    Expires,
+    /// Only `Max-Age=<seconds>`
+    MaxAge,
+    /// `Expires=<IMF-fixdate>; Max-Age=<seconds>`
+    Both,
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::MAX_FORMATTABLE;
+    use super::*;
+
+
+
+    #[test]
+    fn test_format_expiry() {
+        let mut buffer = [0u8; MAX_EXPIRY_LEN];
+        let now = 784111777;
+
+        let cases = [
+            (now, Attributes::Expires, "Expires=Sun, 06 Nov 1994 08:49:37 GMT"),
+            (now, Attributes::MaxAge, "Max-Age=0"),
+            (now + 1, Attributes::Both, "Expires=Sun, 06 Nov 1994 08:49:38 GMT; Max-Age=1"),
+            (now - 1, Attributes::Both, "Expires=Sun, 06 Nov 1994 08:49:36 GMT; Max-Age=0"), // Already expired
+            (now + MAX_LIFETIME, Attributes::Both, "Expires=Mon, 11 Dec 1995 08:49:37 GMT; Max-Age=34560000"),
+            (now + MAX_LIFETIME + 1, Attributes::Both, "Expires=Mon, 11 Dec 1995 08:49:37 GMT; Max-Age=34560000"), // Shortened
+            (u64::MAX, Attributes::Both, "Expires=Mon, 11 Dec 1995 08:49:37 GMT; Max-Age=34560000"),
+        ];
+
+        for (expires, attributes, expected) in cases {
+            let len = format_expiry(expires, now, attributes, &mut buffer);
+            assert_eq!(&buffer[..len], expected.as_bytes(), "{expires} {attributes:?}");
+        }
+
+        // Near the end of the year 9999, the expiry is clamped and Max-Age still agrees with it
+        let len = format_expiry(u64::MAX, MAX_FORMATTABLE - 60, Attributes::Both, &mut buffer);
+        assert_eq!(&buffer[..len], b"Expires=Fri, 31 Dec 9999 23:59:59 GMT; Max-Age=60");
+        let len = format_expiry(u64::MAX, u64::MAX, Attributes::Both, &mut buffer);
+        assert_eq!(&buffer[..len], b"Expires=Fri, 31 Dec 9999 23:59:59 GMT; Max-Age=0");
+
+        // The longest possible output fills the buffer exactly
+        let len = format_expiry(u64::MAX, 0, Attributes::Both, &mut buffer);
+        assert_eq!(len, MAX_EXPIRY_LEN);
+        assert_eq!(&buffer[..], b"Expires=Fri, 05 Feb 1971 00:00:00 GMT; Max-Age=34560000");
+    }
+}
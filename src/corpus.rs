@@ -0,0 +1,86 @@
+//! Regression test over date header values seen in the wild, from `test-data/real_headers.tsv`.
+//!
+//! Strict parsing and the lenient interpretation of `Expires` are checked separately,
+//! so a change to either shows exactly which real-world values it starts or stops accepting.
+
+use crate::*;
+
+
+
+
+// The time the lenient `Expires` results in the corpus are relative to
+const NOW: u64 = 1700000000;
+
+
+struct Entry<'a> {
+    line: usize,
+    field: &'a str,
+    strict: Option<u64>,
+    lenient: Option<Expiry>,
+    value: &'a str,
+}
+
+
+fn entries() -> impl Iterator<Item = Entry<'static>> {
+    include_str!("../test-data/real_headers.tsv")
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.starts_with('#'))
+        .map(|(index, line)| {
+            let mut columns = line.splitn(4, '\t');
+            let mut column = || columns.next().unwrap_or_else(|| panic!("line {} has fewer than 4 columns", index + 1));
+            let (field, strict, lenient, value) = (column(), column(), column(), column());
+
+            Entry {
+                line: index + 1,
+                field,
+                strict: match strict {
+                    "invalid" => None,
+                    timestamp => Some(timestamp.parse().unwrap()),
+                },
+                lenient: match lenient {
+                    "-" => None,
+                    "expired" => Some(Expiry::Expired),
+                    "none" => Some(Expiry::NoInformation),
+                    timestamp => Some(Expiry::At(timestamp.parse().unwrap())),
+                },
+                value,
+            }
+        })
+}
+
+
+
+
+#[test]
+fn test_strict_outcomes() {
+    for entry in entries() {
+        assert_eq!(
+            parse(entry.value.as_bytes()).ok(), entry.strict,
+            "line {}: {}: {:?}", entry.line, entry.field, entry.value,
+        );
+    }
+}
+
+
+#[test]
+fn test_lenient_outcomes() {
+    for entry in entries() {
+        match (entry.field, entry.lenient) {
+            ("Expires", Some(expected)) => assert_eq!(
+                parse_expires(entry.value.as_bytes(), NOW), expected,
+                "line {}: {:?}", entry.line, entry.value,
+            ),
+            ("Expires", None) => panic!("line {}: Expires values need a lenient result", entry.line),
+            (_, lenient) => assert_eq!(lenient, None, "line {}: only Expires has a lenient interpretation", entry.line),
+        }
+    }
+}
+
+
+#[test]
+fn test_corpus_is_not_empty() {
+    assert!(entries().count() > 0);
+    assert!(entries().any(|entry| entry.strict.is_none()));
+    assert!(entries().any(|entry| entry.lenient.is_some()));
+}
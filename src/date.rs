@@ -0,0 +1,464 @@
+use core::fmt;
+use core::str::FromStr;
+use crate::{civil_from_days, civil_to_days, days_in_month, format, in_formattable_range, parse_components, split_days, split_time, InvalidDate, Month, Timestamp, TooFuturistic, Weekday};
+
+
+
+
+/// An HTTP date broken down into its calendar components.
+///
+/// An `HttpDate` always holds a valid date between the unix epoch and the end of the year 9999.
+/// Ordering and hashing are consistent with the instant the date represents.
+///
+/// ```rust
+/// use date_header::{HttpDate, Weekday};
+///
+/// let date = HttpDate::parse(b"Fri, 15 May 2015 15:34:21 GMT").unwrap();
+/// assert_eq!((2015, 5, 15), (date.year(), date.month(), date.day()));
+/// assert_eq!((15, 34, 21), (date.hour(), date.minute(), date.second()));
+/// assert_eq!(Weekday::Friday, date.weekday());
+/// assert_eq!(1431704061, date.timestamp());
+///
+/// // Display always produces an IMF-fixdate, whatever format was parsed
+/// let date: HttpDate = "Friday, 15-May-15 15:34:21 GMT".parse().unwrap();
+/// assert_eq!("Fri, 15 May 2015 15:34:21 GMT", date.to_string());
+/// ```
+// Fields are ordered from most to least significant so the derived ordering is chronological
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct HttpDate {
+    pub(crate) year: u16, // 1970...9999
+    pub(crate) mon: u8, // 1...12
+    pub(crate) day: u8, // 1...31
+    pub(crate) hour: u8, // 0...23
+    pub(crate) min: u8, // 0...59
+    pub(crate) sec: u8, // 0...59
+}
+
+impl HttpDate {
+    /// Start building a date from its components.
+    ///
+    /// ```rust
+    /// use date_header::{HttpDate, Month};
+    ///
+    /// let date = HttpDate::builder()
+    ///     .year(2015)
+    ///     .month(Month::May)
+    ///     .day(15)
+    ///     .hour(15)
+    ///     .minute(34)
+    ///     .second(21)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!("Fri, 15 May 2015 15:34:21 GMT", date.to_string());
+    /// ```
+    pub fn builder() -> HttpDateBuilder {
+        HttpDateBuilder::default()
+    }
+
+    /// Parse an HTTP date header in any of the formats accepted by [parse](crate::parse).
+    pub fn parse(header: &[u8]) -> Result<HttpDate, InvalidDate> {
+        parse_components(header).map(|(date, _)| date)
+    }
+
+    /// Break a unix timestamp down into its calendar components, failing if it is after the year 9999.
+    pub fn from_timestamp(secs_since_epoch: u64) -> Result<HttpDate, TooFuturistic> {
+        if !in_formattable_range(secs_since_epoch) {
+            return Err(TooFuturistic);
+        }
+
+        let (days, secs_of_day) = split_days(secs_since_epoch);
+        let (year, mon, day) = civil_from_days(days);
+        let (hour, min, sec) = split_time(secs_of_day);
+
+        Ok(HttpDate { year, mon, day, hour, min, sec })
+    }
+
+    /// Format this date as an IMF-fixdate, as [format] does.
+    ///
+    /// Unlike [format], this cannot fail.
+    pub fn format(self, buffer: &mut [u8; 29]) {
+        format(self.timestamp(), buffer).expect("HttpDate is always in the formattable range");
+    }
+
+    /// Seconds since the unix epoch.
+    pub fn timestamp(self) -> u64 {
+        let days = civil_to_days(self.year, self.mon, self.day);
+        u64::from(days) * 86400 + u64::from(self.hour) * 3600 + u64::from(self.min) * 60 + u64::from(self.sec)
+    }
+
+    /// Year, from 1970 through 9999.
+    pub fn year(self) -> u16 {
+        self.year
+    }
+
+    /// Month, from 1 for January through 12 for December.
+    pub fn month(self) -> u8 {
+        self.mon
+    }
+
+    /// Day of the month, from 1 through 31.
+    pub fn day(self) -> u8 {
+        self.day
+    }
+
+    /// Hour, from 0 through 23.
+    pub fn hour(self) -> u8 {
+        self.hour
+    }
+
+    /// Minute, from 0 through 59.
+    pub fn minute(self) -> u8 {
+        self.min
+    }
+
+    /// Second, from 0 through 59.
+    pub fn second(self) -> u8 {
+        self.sec
+    }
+
+    /// Day of the week, which is always derived from the date rather than stored.
+    pub fn weekday(self) -> Weekday {
+        Weekday::from_days(civil_to_days(self.year, self.mon, self.day))
+    }
+}
+
+
+/// Builder for an [HttpDate], created by [HttpDate::builder].
+///
+/// Components that aren't set default to midnight on January 1st, 1970.
+/// Every component is validated by [build](HttpDateBuilder::build), which reports the first one out of range.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct HttpDateBuilder {
+    year: u16,
+    month: Month,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+}
+
+impl Default for HttpDateBuilder {
+    fn default() -> HttpDateBuilder {
+        HttpDateBuilder {
+            year: 1970,
+            month: Month::January,
+            day: 1,
+            hour: 0,
+            minute: 0,
+            second: 0,
+        }
+    }
+}
+
+impl HttpDateBuilder {
+    /// Set the year, which must be from 1970 through 9999.
+    pub fn year(mut self, year: u16) -> HttpDateBuilder {
+        self.year = year;
+        self
+    }
+
+    /// Set the month.
+    pub fn month(mut self, month: Month) -> HttpDateBuilder {
+        self.month = month;
+        self
+    }
+
+    /// Set the day of the month, which must exist in the chosen month and year.
+    pub fn day(mut self, day: u8) -> HttpDateBuilder {
+        self.day = day;
+        self
+    }
+
+    /// Set the hour, which must be from 0 through 23.
+    pub fn hour(mut self, hour: u8) -> HttpDateBuilder {
+        self.hour = hour;
+        self
+    }
+
+    /// Set the minute, which must be from 0 through 59.
+    pub fn minute(mut self, minute: u8) -> HttpDateBuilder {
+        self.minute = minute;
+        self
+    }
+
+    /// Set the second, which must be from 0 through 59.
+    pub fn second(mut self, second: u8) -> HttpDateBuilder {
+        self.second = second;
+        self
+    }
+
+    /// Validate the components and build the date.
+    pub fn build(self) -> Result<HttpDate, InvalidComponent> {
+        if !(1970..=9999).contains(&self.year) {
+            return Err(InvalidComponent::Year);
+        }
+
+        let month_len = days_in_month(self.year, self.month.number()).expect("Month is always valid");
+        if self.day < 1 || self.day > month_len {
+            return Err(InvalidComponent::Day);
+        }
+
+        if self.hour > 23 {
+            return Err(InvalidComponent::Hour);
+        }
+
+        if self.minute > 59 {
+            return Err(InvalidComponent::Minute);
+        }
+
+        if self.second > 59 {
+            return Err(InvalidComponent::Second);
+        }
+
+        Ok(HttpDate {
+            year: self.year,
+            mon: self.month.number(),
+            day: self.day,
+            hour: self.hour,
+            min: self.minute,
+            sec: self.second,
+        })
+    }
+}
+
+
+/// Error returned from [HttpDateBuilder::build] naming the component that was out of range.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum InvalidComponent {
+    /// The year was not from 1970 through 9999.
+    Year,
+    /// The day does not exist in the chosen month and year.
+    Day,
+    /// The hour was greater than 23.
+    Hour,
+    /// The minute was greater than 59.
+    Minute,
+    /// The second was greater than 59.
+    Second,
+}
+
+
+
+
+impl TryFrom<u64> for HttpDate {
+    type Error = TooFuturistic;
+
+    fn try_from(secs_since_epoch: u64) -> Result<HttpDate, TooFuturistic> {
+        HttpDate::from_timestamp(secs_since_epoch)
+    }
+}
+
+impl TryFrom<&[u8]> for HttpDate {
+    type Error = InvalidDate;
+
+    fn try_from(header: &[u8]) -> Result<HttpDate, InvalidDate> {
+        HttpDate::parse(header)
+    }
+}
+
+impl TryFrom<&str> for HttpDate {
+    type Error = InvalidDate;
+
+    fn try_from(header: &str) -> Result<HttpDate, InvalidDate> {
+        HttpDate::parse(header.as_bytes())
+    }
+}
+
+impl From<HttpDate> for u64 {
+    fn from(date: HttpDate) -> u64 {
+        date.timestamp()
+    }
+}
+
+impl From<Timestamp> for HttpDate {
+    fn from(timestamp: Timestamp) -> HttpDate {
+        HttpDate::from_timestamp(timestamp.as_secs()).expect("Timestamp is always in the formattable range")
+    }
+}
+
+impl From<HttpDate> for Timestamp {
+    fn from(date: HttpDate) -> Timestamp {
+        Timestamp::new(date.timestamp()).expect("HttpDate is always in the formattable range")
+    }
+}
+
+impl fmt::Display for HttpDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buffer = [0u8; 29];
+        self.format(&mut buffer);
+        f.pad(core::str::from_utf8(&buffer).expect("IMF-fixdate is ASCII"))
+    }
+}
+
+impl FromStr for HttpDate {
+    type Err = InvalidDate;
+
+    fn from_str(text: &str) -> Result<HttpDate, InvalidDate> {
+        HttpDate::parse(text.as_bytes())
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    use crate::*;
+
+
+
+    #[test]
+    fn test_http_date() {
+        let date = HttpDate::parse(b"Sun Nov  6 08:49:37 1994").unwrap();
+        assert_eq!(date, HttpDate::from_timestamp(784111777).unwrap());
+        assert_eq!(date.timestamp(), 784111777);
+        assert_eq!((date.year(), date.month(), date.day()), (1994, 11, 6));
+        assert_eq!((date.hour(), date.minute(), date.second()), (8, 49, 37));
+        assert_eq!(date.weekday(), Weekday::Sunday);
+
+        assert_eq!(HttpDate::parse(b"Mon Nov  6 08:49:37 1994"), Err(InvalidDate));
+        assert_eq!(HttpDate::from_timestamp(253402300800), Err(TooFuturistic));
+
+        for timestamp in [0, 68169600, 951782400, 253402300799] {
+            assert_eq!(HttpDate::from_timestamp(timestamp).unwrap().timestamp(), timestamp);
+        }
+    }
+
+
+    #[test]
+    fn test_ordering() {
+        // Each pair differs in a single component, and the first is earlier
+        let pairs = [
+            (0, 1),
+            (59, 60),
+            (3599, 3600),
+            (86399, 86400),
+            (2678399, 2678400), // Jan 31st to Feb 1st
+            (31535999, 31536000), // Dec 31st to Jan 1st
+            (0, 253402300799),
+        ];
+
+        for (earlier, later) in pairs {
+            let earlier_date = HttpDate::from_timestamp(earlier).unwrap();
+            let later_date = HttpDate::from_timestamp(later).unwrap();
+            assert!(earlier_date < later_date, "{earlier} < {later}");
+
+            let earlier_timestamp = Timestamp::new(earlier).unwrap();
+            let later_timestamp = Timestamp::new(later).unwrap();
+            assert!(earlier_timestamp < later_timestamp, "{earlier} < {later}");
+        }
+
+        let hash = |date: &HttpDate| {
+            let mut hasher = DefaultHasher::new();
+            date.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        // Different formats of the same instant hash the same
+        let imf = HttpDate::parse(b"Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+        let rfc850 = HttpDate::parse(b"Sunday, 06-Nov-94 08:49:37 GMT").unwrap();
+        assert_eq!(hash(&imf), hash(&rfc850));
+
+        let mut dates = std::collections::BTreeMap::new();
+        dates.insert(HttpDate::from_timestamp(2).unwrap(), "b");
+        dates.insert(HttpDate::from_timestamp(1).unwrap(), "a");
+        assert_eq!(dates.values().copied().collect::<Vec<_>>(), ["a", "b"]);
+    }
+
+
+
+    #[test]
+    fn test_display_from_str() {
+        let cases = [
+            "Sun, 06 Nov 1994 08:49:37 GMT",
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "Sun Nov  6 08:49:37 1994",
+        ];
+
+        for text in cases {
+            let date: HttpDate = text.parse().unwrap();
+            assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT", "{text}");
+            assert_eq!(format!("{date:>31}"), "  Sun, 06 Nov 1994 08:49:37 GMT", "{text}");
+            assert_eq!(format!("{date:-<30}"), "Sun, 06 Nov 1994 08:49:37 GMT-", "{text}");
+        }
+
+        assert_eq!("".parse::<HttpDate>(), Err(InvalidDate));
+        assert_eq!("Sun, 06 Nov 1994".parse::<HttpDate>(), Err(InvalidDate));
+        assert_eq!(HttpDate::from_timestamp(0).unwrap().to_string(), "Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(HttpDate::from_timestamp(253402300799).unwrap().to_string(), "Fri, 31 Dec 9999 23:59:59 GMT");
+    }
+
+
+
+    #[test]
+    fn test_conversions() {
+        for secs in [0, 784111777, 253402300799] {
+            let date = HttpDate::try_from(secs).unwrap();
+            let timestamp = Timestamp::try_from(secs).unwrap();
+            assert_eq!(u64::from(date), secs);
+            assert_eq!(u64::from(timestamp), secs);
+            assert_eq!(Timestamp::from(date), timestamp);
+            assert_eq!(HttpDate::from(timestamp), date);
+        }
+
+        assert_eq!(HttpDate::try_from(253402300800), Err(TooFuturistic));
+        assert_eq!(Timestamp::try_from(u64::MAX), Err(TooFuturistic));
+    }
+
+
+
+    #[test]
+    fn test_try_from_text() {
+        let date = HttpDate::from_timestamp(784111777).unwrap();
+        let timestamp = Timestamp::new(784111777).unwrap();
+
+        for text in ["Sun, 06 Nov 1994 08:49:37 GMT", "Sunday, 06-Nov-94 08:49:37 GMT", "Sun Nov  6 08:49:37 1994"] {
+            assert_eq!(HttpDate::try_from(text), Ok(date));
+            assert_eq!(HttpDate::try_from(text.as_bytes()), Ok(date));
+            assert_eq!(Timestamp::try_from(text), Ok(timestamp));
+            assert_eq!(Timestamp::try_from(text.as_bytes()), Ok(timestamp));
+        }
+
+        assert_eq!(HttpDate::try_from("0"), Err(InvalidDate));
+        assert_eq!(HttpDate::try_from(&b"0"[..]), Err(InvalidDate));
+        assert_eq!(Timestamp::try_from("0"), Err(InvalidDate));
+        assert_eq!(Timestamp::try_from(&b"0"[..]), Err(InvalidDate));
+    }
+
+
+
+    #[test]
+    fn test_builder() {
+        assert_eq!(HttpDate::builder().build().unwrap().timestamp(), 0);
+
+        let date = HttpDate::builder().year(1994).month(Month::November).day(6).hour(8).minute(49).second(37).build();
+        assert_eq!(date.map(HttpDate::timestamp), Ok(784111777));
+
+        let date = HttpDate::builder().year(2000).month(Month::February).day(29).build().unwrap();
+        assert_eq!(date.weekday(), Weekday::Tuesday);
+
+        let date = HttpDate::builder().year(9999).month(Month::December).day(31).hour(23).minute(59).second(59).build();
+        assert_eq!(date.map(HttpDate::timestamp), Ok(253402300799));
+
+        let fail = [
+            (HttpDate::builder().year(1969), InvalidComponent::Year),
+            (HttpDate::builder().year(10000), InvalidComponent::Year),
+            (HttpDate::builder().day(0), InvalidComponent::Day),
+            (HttpDate::builder().day(32), InvalidComponent::Day),
+            (HttpDate::builder().month(Month::April).day(31), InvalidComponent::Day),
+            (HttpDate::builder().year(2000).month(Month::February).day(30), InvalidComponent::Day),
+            (HttpDate::builder().year(2100).month(Month::February).day(29), InvalidComponent::Day),
+            (HttpDate::builder().hour(24), InvalidComponent::Hour),
+            (HttpDate::builder().minute(60), InvalidComponent::Minute),
+            (HttpDate::builder().second(60), InvalidComponent::Second),
+            (HttpDate::builder().year(0).second(60), InvalidComponent::Year), // The first invalid component is reported
+        ];
+
+        for (builder, component) in fail {
+            assert_eq!(builder.build(), Err(component), "{builder:?}");
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! [defmt](::defmt) support for the error and date types, for logging over RTT without `core::fmt`.
+//!
+//! [Timestamp] and [HttpDate] are logged as their IMF-fixdate, like their `Display` impls,
+//! and the errors are logged as their names, like their `Debug` impls.
+
+use ::defmt::{write, Format, Formatter};
+use crate::{HttpDate, InvalidComponent, InvalidDate, Month, Timestamp, TooFuturistic, Weekday, FORMATTED_LEN};
+
+
+
+
+impl Format for Timestamp {
+    fn format(&self, f: Formatter<'_>) {
+        let mut buffer = [0u8; FORMATTED_LEN];
+        Timestamp::format(*self, &mut buffer);
+        write!(f, "{=[u8]:a}", buffer)
+    }
+}
+
+impl Format for HttpDate {
+    fn format(&self, f: Formatter<'_>) {
+        let mut buffer = [0u8; FORMATTED_LEN];
+        HttpDate::format(*self, &mut buffer);
+        write!(f, "{=[u8]:a}", buffer)
+    }
+}
+
+impl Format for Weekday {
+    fn format(&self, f: Formatter<'_>) {
+        write!(f, "{=str}", self.short_name())
+    }
+}
+
+impl Format for Month {
+    fn format(&self, f: Formatter<'_>) {
+        write!(f, "{=str}", self.short_name())
+    }
+}
+
+impl Format for InvalidDate {
+    fn format(&self, f: Formatter<'_>) {
+        write!(f, "InvalidDate")
+    }
+}
+
+impl Format for TooFuturistic {
+    fn format(&self, f: Formatter<'_>) {
+        write!(f, "TooFuturistic")
+    }
+}
+
+impl Format for InvalidComponent {
+    fn format(&self, f: Formatter<'_>) {
+        match self {
+            InvalidComponent::Year => write!(f, "Year"),
+            InvalidComponent::Day => write!(f, "Day"),
+            InvalidComponent::Hour => write!(f, "Hour"),
+            InvalidComponent::Minute => write!(f, "Minute"),
+            InvalidComponent::Second => write!(f, "Second"),
+        }
+    }
+}
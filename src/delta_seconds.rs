@@ -0,0 +1,165 @@
+//! Parsing and formatting for the delta-seconds values used by `Age`, `Retry-After`,
+//! `Cache-Control` directives, and `Keep-Alive: timeout`.
+//!
+//! See [RFC9111 Section 1.2.2](https://datatracker.ietf.org/doc/html/rfc9111#section-1.2.2) for more information.
+//!
+//! ```rust
+//! use date_header::delta_seconds;
+//!
+//! assert_eq!(Ok(120), delta_seconds::parse(b"120"));
+//!
+//! let mut buffer = [0u8; delta_seconds::MAX_LEN];
+//! let len = delta_seconds::format(120, &mut buffer);
+//! assert_eq!(&buffer[..len], b"120");
+//! ```
+
+
+
+
+/// Value that parsed delta-seconds are capped at, as required by RFC9111.
+pub const MAX: u64 = 2147483648;
+
+/// Length of the longest output of [format], which is the length of `u64::MAX`
+pub const MAX_LEN: usize = 20;
+
+
+
+
+/// Parse a delta-seconds value.
+///
+/// Only ASCII digits are accepted. Values greater than [MAX] are capped at [MAX],
+/// as RFC9111 requires, rather than overflowing or failing.
+///
+/// ```rust
+/// use date_header::delta_seconds;
+///
+/// assert_eq!(Ok(60), delta_seconds::parse(b"60"));
+/// assert_eq!(Ok(delta_seconds::MAX), delta_seconds::parse(b"99999999999999999999999"));
+/// assert!(delta_seconds::parse(b"-1").is_err());
+/// ```
+pub fn parse(value: &[u8]) -> Result<u64, InvalidDeltaSeconds> {
+    if value.is_empty() || !value.iter().all(u8::is_ascii_digit) {
+        return Err(InvalidDeltaSeconds);
+    }
+
+    Ok(saturating_digits(value).min(MAX))
+}
+
+
+// The value of a run of ASCII digits, saturating at `u64::MAX` rather than overflowing
+pub(crate) fn saturating_digits(digits: &[u8]) -> u64 {
+    digits
+        .iter()
+        .fold(0u64, |seconds, digit| seconds.saturating_mul(10).saturating_add(u64::from(digit - b'0')))
+}
+
+
+/// Format a delta-seconds value into the provided buffer, returning the number of bytes written.
+///
+/// ```rust
+/// use date_header::delta_seconds;
+///
+/// let mut buffer = [0u8; delta_seconds::MAX_LEN];
+/// let len = delta_seconds::format(3600, &mut buffer);
+/// assert_eq!(&buffer[..len], b"3600");
+/// ```
+pub fn format(mut seconds: u64, buffer: &mut [u8; MAX_LEN]) -> usize {
+    let mut digits = [0u8; MAX_LEN];
+    let mut start = digits.len();
+
+    loop {
+        start -= 1;
+        digits[start] = b'0' + (seconds % 10) as u8;
+        seconds /= 10;
+        if seconds == 0 {
+            break;
+        }
+    }
+
+    let len = digits.len() - start;
+    buffer[..len].copy_from_slice(&digits[start..]);
+    len
+}
+
+
+/// Convert a delta-seconds value relative to `reference` into a unix timestamp, saturating on overflow.
+///
+/// ```rust
+/// assert_eq!(1431704121, date_header::delta_seconds::to_date(60, 1431704061));
+/// ```
+pub fn to_date(seconds: u64, reference: u64) -> u64 {
+    reference.saturating_add(seconds)
+}
+
+
+/// Convert a unix timestamp into a delta-seconds value relative to `reference`, or `0` if it is before `reference`.
+///
+/// ```rust
+/// assert_eq!(60, date_header::delta_seconds::from_date(1431704121, 1431704061));
+/// assert_eq!(0, date_header::delta_seconds::from_date(1431704001, 1431704061));
+/// ```
+pub fn from_date(date: u64, reference: u64) -> u64 {
+    date.saturating_sub(reference)
+}
+
+
+/// Error returned from [parse] indicating that the input text was not a valid delta-seconds value.
+#[derive(Debug, Eq, PartialEq)]
+pub struct InvalidDeltaSeconds;
+
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+
+
+    #[test]
+    fn test_parse() {
+        let success = [
+            ("0", 0),
+            ("000", 0),
+            ("1", 1),
+            ("86400", 86400),
+            ("2147483647", 2147483647),
+            ("2147483648", MAX),
+            ("2147483649", MAX), // Capped
+            ("18446744073709551616", MAX), // Would overflow a u64
+        ];
+
+        for (value, expected) in success {
+            assert_eq!(parse(value.as_bytes()), Ok(expected), "{value}");
+        }
+
+        let fail = ["", " 1", "1 ", "-1", "+1", "1.5", "1e3", "0x10", "ten"];
+
+        for value in fail {
+            assert_eq!(parse(value.as_bytes()), Err(InvalidDeltaSeconds), "{value}");
+        }
+    }
+
+
+    #[test]
+    fn test_format() {
+        let mut buffer = [0u8; MAX_LEN];
+
+        for value in [0, 1, 9, 10, 99, 100, 86400, MAX, u64::MAX] {
+            let len = format(value, &mut buffer);
+            assert_eq!(&buffer[..len], value.to_string().as_bytes());
+        }
+    }
+
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(to_date(0, 100), 100);
+        assert_eq!(to_date(60, 100), 160);
+        assert_eq!(to_date(60, u64::MAX), u64::MAX);
+
+        assert_eq!(from_date(160, 100), 60);
+        assert_eq!(from_date(100, 100), 0);
+        assert_eq!(from_date(0, 100), 0);
+    }
+}
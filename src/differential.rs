@@ -0,0 +1,144 @@
+//! Differential tests against httpdate, chrono, and time.
+//!
+//! Each of those crates implements the calendar independently, so agreeing with all three
+//! over random timestamps, the range boundaries, and a corpus of real and broken headers
+//! catches mistakes that tests written from this crate's own understanding would share.
+
+use std::time::{Duration, UNIX_EPOCH};
+use ::chrono::DateTime;
+use proptest::prelude::*;
+use crate::*;
+
+
+
+
+// Timestamps where a calendar implementation is most likely to go wrong
+const BOUNDARY_TIMESTAMPS: &[u64] = &[
+    0,                   // The epoch
+    86399,               // The last second of the first day
+    68169599,            // Feb 28th, 1972, the last second before the first leap day after the epoch
+    94694399,            // Dec 31st, 1972, the last second of the first leap year after the epoch
+    951782400,           // Feb 29th, 2000, a leap day in a century divisible by 400
+    951868799,           // Feb 29th, 2000, the last second
+    978307199,           // Dec 31st, 2000, the last second of a leap year
+    2147483647,          // The last second of a signed 32-bit time_t
+    2147483648,          // The first second after it
+    4107542399,          // Feb 28th, 2100, which isn't followed by a leap day
+    4107542400,          // Mar 1st, 2100
+    4294967295,          // The last second of an unsigned 32-bit time_t
+    MAX_FORMATTABLE,     // Dec 31st, 9999
+];
+
+
+// Headers to parse, both valid and subtly invalid
+const HEADERS: &[&str] = &[
+    "Sun, 06 Nov 1994 08:49:37 GMT",
+    "Sunday, 06-Nov-94 08:49:37 GMT",
+    "Sun Nov  6 08:49:37 1994",
+    "Thu, 01 Jan 1970 00:00:00 GMT",
+    "Fri, 31 Dec 9999 23:59:59 GMT",
+    "Tue, 29 Feb 2000 12:00:00 GMT",
+    "Thursday, 01-Jan-70 00:00:00 GMT",
+    "Tuesday, 31-Dec-69 23:59:59 GMT",   // Two digit years before 70 are in the 2000s
+    "Fri May 15 15:34:21 2015",
+    "Mon Feb 29 00:00:00 2016",
+    "Sat, 29 Feb 2100 00:00:00 GMT",      // 2100 isn't a leap year
+    "Sat, 31 Feb 2015 00:00:00 GMT",      // An impossible calendar date
+    "Fri, 31 Apr 2015 00:00:00 GMT",      // Would roll over to Friday, May 1st
+    "Mon, 06 Nov 1994 08:49:37 GMT",      // The wrong weekday
+    "Sun, 06 Nov 1994 24:00:00 GMT",
+    "Sat, 31 Dec 2016 23:59:60 GMT",      // Leap seconds
+    "Sun, 06 Nov 1994 08:49:37 UTC",
+    "Sun, 06 Nov 1994 08:49:37 gmt",
+    "Sun, 6 Nov 1994 08:49:37 GMT",
+    "Sun,  6 Nov 1994 08:49:37 GMT",
+    "Sun, 06 Nov 94 08:49:37 GMT",
+    "Sun, 06 Nov 1994 08:49 GMT",
+    "Sun, 06 Nov 1969 08:49:37 GMT",      // Before the epoch
+    "Sun Nov 06 08:49:37 1994",
+    "Sunday, 06-Nov-1994 08:49:37 GMT",
+    "Thu, 01 Jan 1970 00:00:00 +0000",
+    "",
+    "0",
+];
+
+
+
+
+fn httpdate_format(timestamp: u64) -> String {
+    httpdate::fmt_http_date(UNIX_EPOCH + Duration::from_secs(timestamp))
+}
+
+
+fn httpdate_parse(header: &str) -> Option<u64> {
+    let time = httpdate::parse_http_date(header).ok()?;
+    time.duration_since(UNIX_EPOCH).ok().map(|duration| duration.as_secs())
+}
+
+
+fn check_timestamp(timestamp: u64) {
+    let mut buffer = [0; FORMATTED_LEN];
+    format(timestamp, &mut buffer).unwrap();
+    let formatted = core::str::from_utf8(&buffer).unwrap();
+
+    assert_eq!(formatted, httpdate_format(timestamp), "httpdate formats {timestamp} differently");
+
+    let chrono = DateTime::from_timestamp(timestamp as i64, 0).unwrap();
+    assert_eq!(formatted, chrono.format("%a, %d %b %Y %H:%M:%S GMT").to_string(), "chrono formats {timestamp} differently");
+
+    let date = HttpDate::from_timestamp(timestamp).unwrap();
+    let time = ::time::OffsetDateTime::from_unix_timestamp(timestamp as i64).unwrap();
+    assert_eq!(
+        (date.year(), date.month(), date.day(), date.hour(), date.minute(), date.second(), date.weekday().number_from_monday()),
+        (time.year() as u16, time.month() as u8, time.day(), time.hour(), time.minute(), time.second(), time.weekday().number_from_monday()),
+        "time splits {timestamp} differently",
+    );
+
+    assert_eq!(parse(&buffer), Ok(timestamp));
+    assert_eq!(httpdate_parse(formatted), Some(timestamp));
+}
+
+
+
+
+#[test]
+fn test_boundary_timestamps() {
+    for &timestamp in BOUNDARY_TIMESTAMPS {
+        check_timestamp(timestamp);
+    }
+}
+
+
+#[test]
+fn test_header_corpus() {
+    for header in HEADERS {
+        let parsed = parse(header.as_bytes()).ok();
+
+        // httpdate accepts exactly the same three formats, so it must agree on every header
+        assert_eq!(parsed, httpdate_parse(header), "httpdate disagrees about {header:?}");
+
+        // chrono and time are more lenient about IMF-fixdate, but must agree with anything accepted
+        if let Some(timestamp) = parsed.filter(|_| header.len() == FORMATTED_LEN) {
+            let chrono = DateTime::parse_from_rfc2822(header).unwrap();
+            assert_eq!(chrono.timestamp(), timestamp as i64, "chrono disagrees about {header:?}");
+
+            let time = ::time::OffsetDateTime::parse(header, &::time::format_description::well_known::Rfc2822).unwrap();
+            assert_eq!(time.unix_timestamp(), timestamp as i64, "time disagrees about {header:?}");
+        }
+    }
+}
+
+
+proptest! {
+    #[test]
+    fn test_random_timestamps(timestamp in 0..=MAX_FORMATTABLE) {
+        check_timestamp(timestamp);
+    }
+
+
+    // Real names and plausible numbers, so impossible dates and wrong weekdays come up often
+    #[test]
+    fn test_random_headers_match_httpdate(header in "(Mon|Tue|Wed|Thu|Fri|Sat|Sun), (0[1-9]|[12][0-9]|3[01]) (Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sep|Oct|Nov|Dec) (19[6-9][0-9]|2[01][0-9]{2}) [0-2][0-9]:[0-5][0-9]:[0-6][0-9] GMT") {
+        assert_eq!(parse(header.as_bytes()).ok(), httpdate_parse(&header));
+    }
+}
@@ -0,0 +1,177 @@
+//! Conversions between the packed date and time of DOS and FAT file systems and unix timestamps,
+//! for `Last-Modified` headers on devices serving files from an SD card or flash drive.
+//!
+//! A FAT timestamp is a 16 bit date and a 16 bit time, covering 1980 through 2107 with two second resolution:
+//!
+//! | Bits  | Date              | Time              |
+//! |-------|-------------------|-------------------|
+//! | 15-9  | Years since 1980  | Hour (15-11)      |
+//! | 8-5   | Month             | Minute (10-5)     |
+//! | 4-0   | Day               | Seconds / 2       |
+//!
+//! FAT has no time zone, so these treat the stored time as UTC. A device that keeps its clock
+//! in local time has to adjust for its offset itself.
+//!
+//! ```rust
+//! use date_header::dos;
+//!
+//! // Fri, 15 May 2015 15:34:20 GMT, as written by a FAT driver
+//! let (date, time) = (0x46AF, 0x7C4A);
+//! assert_eq!(Ok(1431704060), dos::from_dos(date, time));
+//!
+//! let mut header = [0u8; 29];
+//! dos::format_dos(date, time, &mut header).unwrap();
+//! assert_eq!(&header, b"Fri, 15 May 2015 15:34:20 GMT");
+//! ```
+
+use crate::{HttpDate, Month, FORMATTED_LEN};
+
+
+
+
+/// The earliest unix timestamp a FAT timestamp can hold: `Tue, 01 Jan 1980 00:00:00 GMT`
+pub const MIN: u64 = 315532800;
+
+/// The latest unix timestamp a FAT timestamp can hold: `Sat, 31 Dec 2107 23:59:58 GMT`
+pub const MAX: u64 = 4354819198;
+
+
+
+
+/// Convert a FAT date and time into a unix timestamp.
+///
+/// Fails if any field is out of range, such as a zero month or day, a day past the end of its month,
+/// or seconds of 60 or more. Every valid FAT timestamp is an even number of seconds.
+pub fn from_dos(date: u16, time: u16) -> Result<u64, InvalidDos> {
+    let month = Month::from_number((date >> 5 & 0xF) as u8).ok_or(InvalidDos)?;
+
+    HttpDate::builder()
+        .year(1980 + (date >> 9))
+        .month(month)
+        .day((date & 0x1F) as u8)
+        .hour((time >> 11) as u8)
+        .minute((time >> 5 & 0x3F) as u8)
+        .second((time & 0x1F) as u8 * 2)
+        .build()
+        .map(HttpDate::timestamp)
+        .map_err(|_| InvalidDos)
+}
+
+
+/// Convert a unix timestamp into a FAT date and time, as `(date, time)`.
+///
+/// FAT only stores even seconds, so an odd second is truncated to the one before it.
+/// That keeps a file's recorded time from ever being later than when it was actually modified.
+/// Fails for times before 1980 or after 2107.
+pub fn to_dos(secs_since_epoch: u64) -> Result<(u16, u16), OutOfRange> {
+    if !(MIN..=MAX + 1).contains(&secs_since_epoch) {
+        return Err(OutOfRange);
+    }
+
+    let date = HttpDate::from_timestamp(secs_since_epoch).expect("2107 is in the formattable range");
+
+    let packed_date = (date.year() - 1980) << 9 | u16::from(date.month()) << 5 | u16::from(date.day());
+    let packed_time = u16::from(date.hour()) << 11 | u16::from(date.minute()) << 5 | u16::from(date.second() / 2);
+    Ok((packed_date, packed_time))
+}
+
+
+/// Format a FAT date and time as an IMF-fixdate into the provided buffer.
+///
+/// Fails if any field is out of range, as [from_dos] does.
+pub fn format_dos(date: u16, time: u16, buffer: &mut [u8; FORMATTED_LEN]) -> Result<(), InvalidDos> {
+    let secs = from_dos(date, time)?;
+    HttpDate::from_timestamp(secs).expect("2107 is in the formattable range").format(buffer);
+    Ok(())
+}
+
+
+/// Error returned from [from_dos] and [format_dos] indicating that a field of the FAT date or time is out of range.
+#[derive(Debug, Eq, PartialEq)]
+pub struct InvalidDos;
+
+
+/// Error returned from [to_dos] indicating that the time is outside the years FAT can represent.
+#[derive(Debug, Eq, PartialEq)]
+pub struct OutOfRange;
+
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+
+
+    // Pack each field into a FAT date and time
+    fn pack(year: u16, month: u16, day: u16, hour: u16, minute: u16, second: u16) -> (u16, u16) {
+        ((year - 1980) << 9 | month << 5 | day, hour << 11 | minute << 5 | (second / 2))
+    }
+
+
+    #[test]
+    fn test_from_dos() {
+        let (date, time) = pack(1980, 1, 1, 0, 0, 0);
+        assert_eq!(from_dos(date, time), Ok(MIN));
+
+        let (date, time) = pack(2107, 12, 31, 23, 59, 58);
+        assert_eq!(from_dos(date, time), Ok(MAX));
+
+        let (date, time) = pack(2015, 5, 15, 15, 34, 20);
+        assert_eq!((date, time), (0x46AF, 0x7C4A));
+        assert_eq!(from_dos(date, time), Ok(1431704060));
+
+        let (date, time) = pack(2024, 2, 29, 12, 0, 0);
+        assert_eq!(from_dos(date, time), Ok(1709208000));
+    }
+
+
+    #[test]
+    fn test_from_dos_invalid() {
+        let (_, time) = pack(1980, 1, 1, 0, 0, 0);
+
+        // An all zero date, as left by some drivers, has a zero month and day
+        assert_eq!(from_dos(0, 0), Err(InvalidDos));
+        assert_eq!(from_dos(pack(2015, 0, 15, 0, 0, 0).0, time), Err(InvalidDos));
+        assert_eq!(from_dos(pack(2015, 13, 15, 0, 0, 0).0, time), Err(InvalidDos));
+        assert_eq!(from_dos(pack(2015, 5, 0, 0, 0, 0).0, time), Err(InvalidDos));
+        assert_eq!(from_dos(pack(2023, 2, 29, 0, 0, 0).0, time), Err(InvalidDos));
+
+        let (date, _) = pack(2015, 5, 15, 0, 0, 0);
+        assert_eq!(from_dos(date, pack(1980, 1, 1, 24, 0, 0).1), Err(InvalidDos));
+        assert_eq!(from_dos(date, pack(1980, 1, 1, 0, 60, 0).1), Err(InvalidDos));
+        assert_eq!(from_dos(date, pack(1980, 1, 1, 0, 0, 60).1), Err(InvalidDos));
+        assert_eq!(from_dos(date, pack(1980, 1, 1, 0, 0, 58).1), Ok(1431648058));
+    }
+
+
+    #[test]
+    fn test_to_dos() {
+        assert_eq!(to_dos(MIN), Ok(pack(1980, 1, 1, 0, 0, 0)));
+        assert_eq!(to_dos(MAX), Ok(pack(2107, 12, 31, 23, 59, 58)));
+        assert_eq!(to_dos(MAX + 1), Ok(pack(2107, 12, 31, 23, 59, 58))); // The last odd second is truncated
+        assert_eq!(to_dos(1431704061), Ok((0x46AF, 0x7C4A)));
+
+        assert_eq!(to_dos(MIN - 1), Err(OutOfRange));
+        assert_eq!(to_dos(MAX + 2), Err(OutOfRange));
+        assert_eq!(to_dos(u64::MAX), Err(OutOfRange));
+
+        // Round trips through to_dos only lose the odd second
+        for secs in [MIN, 784111777, 1431704061, 1709208000, MAX] {
+            let (date, time) = to_dos(secs).unwrap();
+            assert_eq!(from_dos(date, time), Ok(secs & !1));
+        }
+    }
+
+
+    #[test]
+    fn test_format_dos() {
+        let mut buffer = [0; FORMATTED_LEN];
+        let (date, time) = pack(1994, 11, 6, 8, 49, 36);
+        assert_eq!(format_dos(date, time, &mut buffer), Ok(()));
+        assert_eq!(&buffer, b"Sun, 06 Nov 1994 08:49:36 GMT");
+
+        assert_eq!(format_dos(0, 0, &mut buffer), Err(InvalidDos));
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{delta_seconds, InvalidDate};
+
+
+
+
+/// Format unix epoch seconds as plain decimal digits, as used by `X-Timestamp` and similar fields,
+/// returning the number of bytes written.
+///
+/// A buffer of [delta_seconds::MAX_LEN] bytes is always long enough.
+/// Panics if the buffer is too short to hold every digit.
+///
+/// ```rust
+/// let mut buffer = [0u8; 20];
+/// let len = date_header::format_epoch_decimal(1431704061, &mut buffer);
+/// assert_eq!(&buffer[..len], b"1431704061");
+/// ```
+pub fn format_epoch_decimal(secs_since_epoch: u64, buffer: &mut [u8]) -> usize {
+    let mut digits = [0u8; delta_seconds::MAX_LEN];
+    let len = delta_seconds::format(secs_since_epoch, &mut digits);
+    buffer[..len].copy_from_slice(&digits[..len]);
+    len
+}
+
+
+/// Parse unix epoch seconds written as plain decimal digits.
+///
+/// Only ASCII digits are accepted: signs, whitespace, fractions, and values that overflow a u64 are all rejected.
+///
+/// ```rust
+/// assert_eq!(Ok(1431704061), date_header::parse_epoch_decimal(b"1431704061"));
+/// assert!(date_header::parse_epoch_decimal(b"+1431704061").is_err());
+/// assert!(date_header::parse_epoch_decimal(b"1431704061.5").is_err());
+/// ```
+pub fn parse_epoch_decimal(value: &[u8]) -> Result<u64, InvalidDate> {
+    if value.is_empty() {
+        return Err(InvalidDate);
+    }
+
+    value.iter().try_fold(0u64, |seconds, &digit| {
+        if !digit.is_ascii_digit() {
+            return Err(InvalidDate);
+        }
+
+        seconds
+            .checked_mul(10)
+            .and_then(|seconds| seconds.checked_add(u64::from(digit - b'0')))
+            .ok_or(InvalidDate)
+    })
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+
+
+    #[test]
+    fn test_parse_epoch_decimal() {
+        let success = [
+            ("0", 0),
+            ("00", 0),
+            ("1431704061", 1431704061),
+            ("253402300800", 253402300800), // Not formattable as a date, but still a valid number
+            ("18446744073709551615", u64::MAX),
+        ];
+
+        for (input, expected) in success {
+            assert_eq!(parse_epoch_decimal(input.as_bytes()), Ok(expected), "{input}");
+        }
+
+        let failure = [
+            "",
+            " 1",
+            "1 ",
+            "+1",
+            "-1",
+            "1.0",
+            "1e3",
+            "0x10",
+            "18446744073709551616", // u64::MAX + 1
+            "99999999999999999999999",
+            "Fri, 15 May 2015 15:34:21 GMT",
+        ];
+
+        for input in failure {
+            assert_eq!(parse_epoch_decimal(input.as_bytes()), Err(InvalidDate), "{input}");
+        }
+    }
+
+
+    #[test]
+    fn test_format_epoch_decimal() {
+        let mut buffer = [0u8; 20];
+
+        for secs in [0, 9, 10, 1431704061, u64::MAX] {
+            let len = format_epoch_decimal(secs, &mut buffer);
+            assert_eq!(&buffer[..len], secs.to_string().as_bytes());
+            assert_eq!(parse_epoch_decimal(&buffer[..len]), Ok(secs));
+        }
+
+        let mut exact = [0u8; 10];
+        assert_eq!(format_epoch_decimal(1431704061, &mut exact), 10);
+        assert_eq!(&exact, b"1431704061");
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_format_epoch_decimal_short_buffer() {
+        format_epoch_decimal(1431704061, &mut [0u8; 9]);
+    }
+}
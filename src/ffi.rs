@@ -0,0 +1,143 @@
+//! A C interface to [format] and [parse], for C and C++ servers that would rather not link a libc date stack.
+//!
+//! The `ffi` directory of the repository builds this as a shared and static library,
+//! and generates `date_header.h` from this module with cbindgen.
+//!
+//! ```c
+//! char header[DATE_HEADER_FORMATTED_LEN];
+//! if (date_header_format(1431704061, (uint8_t *)header) == DATE_HEADER_OK) {
+//!     printf("Date: %.29s\r\n", header);
+//! }
+//!
+//! uint64_t timestamp;
+//! const char *value = "Sun, 06 Nov 1994 08:49:37 GMT";
+//! if (date_header_parse((const uint8_t *)value, strlen(value), &timestamp) == DATE_HEADER_OK) {
+//!     printf("%llu\n", (unsigned long long)timestamp);
+//! }
+//! ```
+
+// Raw pointers and exported symbols can't be expressed without unsafe code,
+// so this module is the one place the `ffi` feature allows it
+#![allow(unsafe_code)]
+
+use core::ptr;
+use crate::{format, parse, FORMATTED_LEN};
+
+
+
+
+/// The function succeeded.
+pub const DATE_HEADER_OK: i32 = 0;
+
+/// The timestamp was after the year 9999, or the header was not a valid date.
+pub const DATE_HEADER_INVALID: i32 = -1;
+
+/// A required pointer was null.
+pub const DATE_HEADER_NULL_POINTER: i32 = -2;
+
+/// The number of bytes [date_header_format] writes.
+// A literal so that cbindgen can write it into the header
+pub const DATE_HEADER_FORMATTED_LEN: usize = 29;
+const _: () = assert!(DATE_HEADER_FORMATTED_LEN == FORMATTED_LEN);
+
+
+
+
+/// Format a unix timestamp as an IMF-fixdate into `out`, which is not null terminated.
+///
+/// Returns [DATE_HEADER_OK], [DATE_HEADER_INVALID] if the timestamp is after the year 9999,
+/// or [DATE_HEADER_NULL_POINTER]. Nothing is written unless formatting succeeds.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writing [DATE_HEADER_FORMATTED_LEN] bytes.
+#[no_mangle]
+pub unsafe extern "C" fn date_header_format(secs_since_epoch: u64, out: *mut u8) -> i32 {
+    if out.is_null() {
+        return DATE_HEADER_NULL_POINTER;
+    }
+
+    let mut buffer = [0; FORMATTED_LEN];
+    if format(secs_since_epoch, &mut buffer).is_err() {
+        return DATE_HEADER_INVALID;
+    }
+
+    // SAFETY: The caller guarantees `out` is valid for FORMATTED_LEN bytes, and it can't overlap a local
+    unsafe { ptr::copy_nonoverlapping(buffer.as_ptr(), out, FORMATTED_LEN) };
+    DATE_HEADER_OK
+}
+
+
+/// Parse an HTTP date of `len` bytes in any of the three formats into a unix timestamp in `out`.
+///
+/// Returns [DATE_HEADER_OK], [DATE_HEADER_INVALID] if the header isn't a valid date,
+/// or [DATE_HEADER_NULL_POINTER]. `out` is only written if parsing succeeds.
+///
+/// # Safety
+///
+/// `header` must be null or valid for reading `len` bytes, and `out` must be null or valid for writing a `uint64_t`.
+#[no_mangle]
+pub unsafe extern "C" fn date_header_parse(header: *const u8, len: usize, out: *mut u64) -> i32 {
+    if header.is_null() || out.is_null() {
+        return DATE_HEADER_NULL_POINTER;
+    }
+
+    // SAFETY: The caller guarantees `header` is valid for `len` bytes
+    let header = unsafe { core::slice::from_raw_parts(header, len) };
+    match parse(header) {
+        Ok(timestamp) => {
+            // SAFETY: The caller guarantees `out` is valid for writing, and it may be unaligned coming from C
+            unsafe { out.write_unaligned(timestamp) };
+            DATE_HEADER_OK
+        }
+        Err(_) => DATE_HEADER_INVALID,
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::MAX_FORMATTABLE;
+    use super::*;
+
+
+
+    #[test]
+    fn test_date_header_format() {
+        let mut buffer = [b'x'; 30];
+        assert_eq!(unsafe { date_header_format(784111777, buffer.as_mut_ptr()) }, DATE_HEADER_OK);
+        assert_eq!(&buffer, b"Sun, 06 Nov 1994 08:49:37 GMTx"); // Not null terminated
+
+        let mut buffer = [b'x'; FORMATTED_LEN];
+        assert_eq!(unsafe { date_header_format(MAX_FORMATTABLE + 1, buffer.as_mut_ptr()) }, DATE_HEADER_INVALID);
+        assert_eq!(buffer, [b'x'; FORMATTED_LEN]); // Untouched on failure
+        assert_eq!(unsafe { date_header_format(0, ptr::null_mut()) }, DATE_HEADER_NULL_POINTER);
+    }
+
+
+    #[test]
+    fn test_date_header_parse() {
+        let mut timestamp = 7;
+        for header in [&b"Sun, 06 Nov 1994 08:49:37 GMT"[..], b"Sunday, 06-Nov-94 08:49:37 GMT", b"Sun Nov  6 08:49:37 1994"] {
+            assert_eq!(unsafe { date_header_parse(header.as_ptr(), header.len(), &mut timestamp) }, DATE_HEADER_OK);
+            assert_eq!(timestamp, 784111777);
+        }
+
+        let mut timestamp = 7;
+        let header = b"Sun, 06 Nov 1994 08:49:37 GMT";
+        assert_eq!(unsafe { date_header_parse(header.as_ptr(), header.len() - 1, &mut timestamp) }, DATE_HEADER_INVALID);
+        assert_eq!(unsafe { date_header_parse(header.as_ptr(), 0, &mut timestamp) }, DATE_HEADER_INVALID);
+        assert_eq!(timestamp, 7); // Untouched on failure
+
+        assert_eq!(unsafe { date_header_parse(ptr::null(), 0, &mut timestamp) }, DATE_HEADER_NULL_POINTER);
+        assert_eq!(unsafe { date_header_parse(header.as_ptr(), header.len(), ptr::null_mut()) }, DATE_HEADER_NULL_POINTER);
+
+        // Unaligned output, as a packed C struct might have
+        let mut bytes = [0u8; 9];
+        let out = bytes[1..].as_mut_ptr() as *mut u64;
+        assert_eq!(unsafe { date_header_parse(header.as_ptr(), header.len(), out) }, DATE_HEADER_OK);
+        assert_eq!(u64::from_ne_bytes(bytes[1..].try_into().unwrap()), 784111777);
+    }
+}
@@ -0,0 +1,312 @@
+//! Conformance tests generated from the RFC 9110 grammar for HTTP-date.
+//!
+//! Every field of a few valid dates is swept across its whole ABNF production, like every
+//! `2DIGIT` for `day` including `00` and `99`, and every valid date is mutated by replacing,
+//! deleting, or inserting a single byte. Each input is checked against a reference written
+//! directly from the ABNF below, plus the calendar rules the grammar can't express.
+//!
+//! ```text
+//! IMF-fixdate  = day-name "," SP date1 SP time-of-day SP GMT
+//! rfc850-date  = day-name-l "," SP date2 SP time-of-day SP GMT
+//! asctime-date = day-name SP date3 SP time-of-day SP year
+//!
+//! date1 = day SP month SP year
+//! date2 = day "-" month "-" 2DIGIT
+//! date3 = month SP ( 2DIGIT / ( SP DIGIT ))
+//! day   = 2DIGIT
+//! year  = 4DIGIT
+//! time-of-day = hour ":" minute ":" second
+//! hour = 2DIGIT, minute = 2DIGIT, second = 2DIGIT
+//! ```
+
+use crate::*;
+
+
+
+
+const DAY_NAMES: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const DAY_NAMES_L: [&str; 7] = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+
+// Valid dates to sweep and mutate, covering a leap day and both ends of the range
+const IMF_FIXDATES: &[&str] = &[
+    "Sun, 06 Nov 1994 08:49:37 GMT",
+    "Tue, 29 Feb 2000 23:59:59 GMT",
+    "Thu, 01 Jan 1970 00:00:00 GMT",
+    "Fri, 31 Dec 9999 23:59:59 GMT",
+];
+
+const RFC850_DATES: &[&str] = &[
+    "Sunday, 06-Nov-94 08:49:37 GMT",
+    "Tuesday, 29-Feb-00 23:59:59 GMT",
+    "Wednesday, 09-Nov-94 08:49:37 GMT",
+    "Tuesday, 31-Dec-69 00:00:00 GMT",
+    "Thursday, 01-Jan-70 00:00:00 GMT",
+];
+
+const ASCTIME_DATES: &[&str] = &[
+    "Sun Nov  6 08:49:37 1994",
+    "Tue Feb 29 23:59:59 2000",
+    "Thu Jan 01 00:00:00 1970",
+    "Fri Dec 31 23:59:59 9999",
+];
+
+
+
+
+/// The fields of a date that matched the grammar, before any calendar checks
+struct Fields {
+    weekday: usize,
+    day: u64,
+    month: usize,
+    year: u64,
+    hour: u64,
+    minute: u64,
+    second: u64,
+}
+
+
+/// Just enough of a parser combinator to transcribe the ABNF
+struct Cursor<'a>(&'a [u8]);
+
+impl Cursor<'_> {
+    fn literal(&mut self, literal: &str) -> Option<()> {
+        self.0 = self.0.strip_prefix(literal.as_bytes())?;
+        Some(())
+    }
+
+    fn digits(&mut self, count: usize) -> Option<u64> {
+        let digits = self.0.get(..count)?;
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        self.0 = &self.0[count..];
+        Some(digits.iter().fold(0, |n, digit| n * 10 + u64::from(digit - b'0')))
+    }
+
+    fn one_of(&mut self, names: &[&str]) -> Option<usize> {
+        let index = names.iter().position(|name| self.0.starts_with(name.as_bytes()))?;
+        self.0 = &self.0[names[index].len()..];
+        Some(index)
+    }
+
+    fn time_of_day(&mut self) -> Option<(u64, u64, u64)> {
+        let hour = self.digits(2)?;
+        self.literal(":")?;
+        let minute = self.digits(2)?;
+        self.literal(":")?;
+        let second = self.digits(2)?;
+        Some((hour, minute, second))
+    }
+
+    fn end(&self) -> Option<()> {
+        self.0.is_empty().then_some(())
+    }
+}
+
+
+fn imf_fixdate(s: &[u8]) -> Option<Fields> {
+    let mut s = Cursor(s);
+    let weekday = s.one_of(&DAY_NAMES)?;
+    s.literal(", ")?;
+    let day = s.digits(2)?;
+    s.literal(" ")?;
+    let month = s.one_of(&MONTHS)?;
+    s.literal(" ")?;
+    let year = s.digits(4)?;
+    s.literal(" ")?;
+    let (hour, minute, second) = s.time_of_day()?;
+    s.literal(" GMT")?;
+    s.end()?;
+    Some(Fields { weekday, day, month, year, hour, minute, second })
+}
+
+
+fn rfc850_date(s: &[u8]) -> Option<Fields> {
+    let mut s = Cursor(s);
+    let weekday = s.one_of(&DAY_NAMES_L)?;
+    s.literal(", ")?;
+    let day = s.digits(2)?;
+    s.literal("-")?;
+    let month = s.one_of(&MONTHS)?;
+    s.literal("-")?;
+    let year = s.digits(2)?;
+    s.literal(" ")?;
+    let (hour, minute, second) = s.time_of_day()?;
+    s.literal(" GMT")?;
+    s.end()?;
+
+    // The grammar leaves two digit years open, and this crate puts them in 1970 through 2069
+    let year = if year < 70 { 2000 + year } else { 1900 + year };
+    Some(Fields { weekday, day, month, year, hour, minute, second })
+}
+
+
+fn asctime_date(s: &[u8]) -> Option<Fields> {
+    let mut s = Cursor(s);
+    let weekday = s.one_of(&DAY_NAMES)?;
+    s.literal(" ")?;
+    let month = s.one_of(&MONTHS)?;
+    s.literal(" ")?;
+    let day = match s.literal(" ") {
+        Some(()) => s.digits(1)?,
+        None => s.digits(2)?,
+    };
+    s.literal(" ")?;
+    let (hour, minute, second) = s.time_of_day()?;
+    s.literal(" ")?;
+    let year = s.digits(4)?;
+    s.end()?;
+    Some(Fields { weekday, day, month, year, hour, minute, second })
+}
+
+
+// Howard Hinnant's days_from_civil, independent of the crate's own calendar
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+
+/// What parsing must produce according to the grammar and the calendar
+fn reference(s: &[u8]) -> Option<u64> {
+    let fields = imf_fixdate(s).or_else(|| rfc850_date(s)).or_else(|| asctime_date(s))?;
+    let month = fields.month as u64 + 1;
+
+    let leap = fields.year % 4 == 0 && (fields.year % 100 != 0 || fields.year % 400 == 0);
+    let month_length = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+
+    // Unix time has no leap seconds, so second 60 is out along with everything before the epoch
+    let in_range = fields.year >= 1970
+        && (1..=month_length).contains(&fields.day)
+        && fields.hour < 24
+        && fields.minute < 60
+        && fields.second < 60;
+    if !in_range {
+        return None;
+    }
+
+    // The epoch was a Thursday, which is index 3 from Monday
+    let days = days_from_civil(fields.year, month, fields.day);
+    if (days + 3) % 7 != fields.weekday as u64 {
+        return None;
+    }
+
+    Some(days * 86400 + fields.hour * 3600 + fields.minute * 60 + fields.second)
+}
+
+
+fn check(header: &[u8]) {
+    assert_eq!(parse(header).ok(), reference(header), "{:?}", String::from_utf8_lossy(header));
+}
+
+
+/// Every alternative of every field's production, as (byte range in the date, alternatives)
+fn fields_of(date: &str) -> Vec<(core::ops::Range<usize>, Vec<String>)> {
+    let names = |names: &[&str]| names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+    let digits = |count: usize| (0..10u32.pow(count as u32)).map(|n| format!("{n:0count$}")).collect::<Vec<_>>();
+
+    if IMF_FIXDATES.contains(&date) {
+        vec![(0..3, names(&DAY_NAMES)), (5..7, digits(2)), (8..11, names(&MONTHS)), (12..16, digits(4)), (17..19, digits(2)), (20..22, digits(2)), (23..25, digits(2))]
+    } else if RFC850_DATES.contains(&date) {
+        let o = date.find(',').unwrap();
+        vec![(0..o, names(&DAY_NAMES_L)), (o + 2..o + 4, digits(2)), (o + 5..o + 8, names(&MONTHS)), (o + 9..o + 11, digits(2)), (o + 12..o + 14, digits(2)), (o + 15..o + 17, digits(2)), (o + 18..o + 20, digits(2))]
+    } else {
+        let mut days = digits(2);
+        days.extend((0..10).map(|n| format!(" {n}")));
+        vec![(0..3, names(&DAY_NAMES)), (4..7, names(&MONTHS)), (8..10, days), (11..13, digits(2)), (14..16, digits(2)), (17..19, digits(2)), (20..24, digits(4))]
+    }
+}
+
+
+fn all_dates() -> impl Iterator<Item = &'static str> {
+    IMF_FIXDATES.iter().chain(RFC850_DATES).chain(ASCTIME_DATES).copied()
+}
+
+
+
+
+#[test]
+fn test_reference_accepts_every_base_date() {
+    for date in all_dates() {
+        assert!(reference(date.as_bytes()).is_some(), "{date:?}");
+        check(date.as_bytes());
+    }
+}
+
+
+#[test]
+fn test_every_production_of_each_field() {
+    for date in all_dates() {
+        for (range, alternatives) in fields_of(date) {
+            for alternative in alternatives {
+                let mut header = date.as_bytes().to_vec();
+                header.splice(range.clone(), alternative.bytes());
+                check(&header);
+            }
+        }
+    }
+}
+
+
+#[test]
+fn test_single_byte_replacements() {
+    for date in all_dates() {
+        for index in 0..date.len() {
+            for byte in 0..=u8::MAX {
+                let mut header = date.as_bytes().to_vec();
+                header[index] = byte;
+                check(&header);
+            }
+        }
+    }
+}
+
+
+#[test]
+fn test_single_byte_deletions_and_insertions() {
+    for date in all_dates() {
+        for index in 0..date.len() {
+            let mut header = date.as_bytes().to_vec();
+            header.remove(index);
+            check(&header);
+        }
+
+        for index in 0..=date.len() {
+            for byte in 0..=u8::MAX {
+                let mut header = date.as_bytes().to_vec();
+                header.insert(index, byte);
+                check(&header);
+            }
+        }
+    }
+}
+
+
+// The edge cases the semantic checks could otherwise hide, pinned down explicitly
+#[test]
+fn test_field_edges() {
+    for header in [
+        "Sun, 00 Nov 1994 08:49:37 GMT",
+        "Sun, 06 Nov 1994 24:00:00 GMT",
+        "Sun, 06 Nov 1994 08:60:37 GMT",
+        "Sun, 06 Nov 1994 08:49:60 GMT",
+        "Sun, 06 Nov 0000 08:49:37 GMT",
+        "Sunday, 00-Nov-94 08:49:37 GMT",
+        "Sun Nov  0 08:49:37 1994",
+        "Sun Nov 00 08:49:37 1994",
+    ] {
+        assert_eq!(reference(header.as_bytes()), None, "{header:?}");
+        check(header.as_bytes());
+    }
+}
@@ -0,0 +1,235 @@
+//! Helpers for reading and writing date headers on an [http::HeaderMap](::http::HeaderMap).
+//!
+//! Getters return `None` if the header is missing or is not a valid HTTP date.
+//!
+//! ```rust
+//! let mut headers = http::HeaderMap::new();
+//! date_header::http::set_date(&mut headers, 1431704061).unwrap();
+//! assert_eq!(headers["date"], "Fri, 15 May 2015 15:34:21 GMT");
+//! assert_eq!(Some(1431704061), date_header::http::get_date(&headers));
+//! ```
+
+use std::cell::RefCell;
+use ::http::header::{HeaderMap, HeaderName, HeaderValue, DATE, EXPIRES, LAST_MODIFIED};
+use crate::{cached::unix_now, format, parse, InvalidDate, Timestamp, TooFuturistic};
+
+
+
+
+thread_local! {
+    static CACHE: RefCell<(u64, HeaderValue)> = const { RefCell::new((u64::MAX, HeaderValue::from_static(""))) };
+}
+
+
+
+
+/// Read the `Date` header as a unix timestamp.
+pub fn get_date(headers: &HeaderMap) -> Option<u64> {
+    get(headers, DATE)
+}
+
+
+/// Read the `Expires` header as a unix timestamp.
+pub fn get_expires(headers: &HeaderMap) -> Option<u64> {
+    get(headers, EXPIRES)
+}
+
+
+/// Read the `Last-Modified` header as a unix timestamp.
+pub fn get_last_modified(headers: &HeaderMap) -> Option<u64> {
+    get(headers, LAST_MODIFIED)
+}
+
+
+/// Parse a [HeaderValue] holding an HTTP date into a unix timestamp.
+///
+/// ```rust
+/// let value = http::HeaderValue::from_static("Fri, 15 May 2015 15:34:21 GMT");
+/// assert_eq!(Ok(1431704061), date_header::http::from_header_value(&value));
+/// ```
+pub fn from_header_value(value: &HeaderValue) -> Result<u64, InvalidDate> {
+    parse(value.as_bytes())
+}
+
+
+/// Format a unix timestamp as an IMF-fixdate [HeaderValue].
+///
+/// The value is built directly from the fixed-width buffer, without an intermediate `String`.
+///
+/// ```rust
+/// let value = date_header::http::to_header_value(1431704061).unwrap();
+/// assert_eq!(value, "Fri, 15 May 2015 15:34:21 GMT");
+/// ```
+pub fn to_header_value(secs_since_epoch: u64) -> Result<HeaderValue, TooFuturistic> {
+    let mut buffer = [0u8; 29];
+    format(secs_since_epoch, &mut buffer)?;
+
+    // IMF-fixdate output is always visible ASCII, which is a valid header value
+    Ok(HeaderValue::from_maybe_shared(buffer).expect("IMF-fixdate is a valid header value"))
+}
+
+
+impl TryFrom<&HeaderValue> for Timestamp {
+    type Error = InvalidDate;
+
+    fn try_from(value: &HeaderValue) -> Result<Timestamp, InvalidDate> {
+        Timestamp::parse(value.as_bytes())
+    }
+}
+
+impl From<Timestamp> for HeaderValue {
+    fn from(timestamp: Timestamp) -> HeaderValue {
+        to_header_value(timestamp.as_secs()).expect("Timestamp is always in the formattable range")
+    }
+}
+
+
+/// The current time as an IMF-fixdate [HeaderValue], for use as a response's `Date` header.
+///
+/// The value is cached per thread and only reformatted when the second changes.
+/// Cloning a cached [HeaderValue] does not copy its bytes, so this is cheap enough to call for every response.
+///
+/// ```rust
+/// let value = date_header::http::date_now();
+/// assert!(date_header::http::from_header_value(&value).is_ok());
+/// ```
+pub fn date_now() -> HeaderValue {
+    let now = unix_now();
+
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if cache.0 != now {
+            *cache = (now, to_header_value(now).expect("unix_now is clamped to the formattable range"));
+        }
+        cache.1.clone()
+    })
+}
+
+
+/// A `Date` header provider in the shape expected by hyper and tower middleware.
+///
+/// The returned function is [date_now], which is `Copy`, `Send`, and `Sync`,
+/// so it can be shared freely between services and threads.
+///
+/// ```rust
+/// let provider = date_header::http::date_provider();
+///
+/// let mut response = http::Response::new(());
+/// response.headers_mut().insert(http::header::DATE, provider());
+/// assert!(date_header::http::get_date(response.headers()).is_some());
+/// ```
+pub fn date_provider() -> fn() -> HeaderValue {
+    date_now
+}
+
+
+/// Set the `Date` header to the IMF-fixdate form of a unix timestamp, replacing any existing value.
+pub fn set_date(headers: &mut HeaderMap, secs_since_epoch: u64) -> Result<(), TooFuturistic> {
+    set(headers, DATE, secs_since_epoch)
+}
+
+
+/// Set the `Expires` header to the IMF-fixdate form of a unix timestamp, replacing any existing value.
+pub fn set_expires(headers: &mut HeaderMap, secs_since_epoch: u64) -> Result<(), TooFuturistic> {
+    set(headers, EXPIRES, secs_since_epoch)
+}
+
+
+/// Set the `Last-Modified` header to the IMF-fixdate form of a unix timestamp, replacing any existing value.
+pub fn set_last_modified(headers: &mut HeaderMap, secs_since_epoch: u64) -> Result<(), TooFuturistic> {
+    set(headers, LAST_MODIFIED, secs_since_epoch)
+}
+
+
+
+
+fn get(headers: &HeaderMap, name: HeaderName) -> Option<u64> {
+    from_header_value(headers.get(name)?).ok()
+}
+
+
+fn set(headers: &mut HeaderMap, name: HeaderName, secs_since_epoch: u64) -> Result<(), TooFuturistic> {
+    headers.insert(name, to_header_value(secs_since_epoch)?);
+    Ok(())
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use ::http::header::{HeaderMap, HeaderValue, DATE, EXPIRES, LAST_MODIFIED};
+    use crate::{InvalidDate, TooFuturistic};
+    use super::*;
+
+
+
+    #[test]
+    fn test_header_map() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(get_date(&headers), None);
+        assert_eq!(get_expires(&headers), None);
+        assert_eq!(get_last_modified(&headers), None);
+
+        assert_eq!(set_date(&mut headers, 784111777), Ok(()));
+        assert_eq!(set_expires(&mut headers, 784111778), Ok(()));
+        assert_eq!(set_last_modified(&mut headers, 784111776), Ok(()));
+        assert_eq!(headers[DATE], "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(headers[EXPIRES], "Sun, 06 Nov 1994 08:49:38 GMT");
+        assert_eq!(headers[LAST_MODIFIED], "Sun, 06 Nov 1994 08:49:36 GMT");
+        assert_eq!(get_date(&headers), Some(784111777));
+        assert_eq!(get_expires(&headers), Some(784111778));
+        assert_eq!(get_last_modified(&headers), Some(784111776));
+
+        // Setting replaces rather than appends
+        assert_eq!(set_date(&mut headers, 0), Ok(()));
+        assert_eq!(headers.get_all(DATE).iter().count(), 1);
+        assert_eq!(get_date(&headers), Some(0));
+
+        // Unrepresentable timestamps leave the existing value alone
+        assert_eq!(set_date(&mut headers, u64::MAX), Err(TooFuturistic));
+        assert_eq!(get_date(&headers), Some(0));
+
+        headers.insert(EXPIRES, HeaderValue::from_static("0"));
+        assert_eq!(get_expires(&headers), None);
+
+        headers.insert(LAST_MODIFIED, HeaderValue::from_static("Sunday, 06-Nov-94 08:49:37 GMT"));
+        assert_eq!(get_last_modified(&headers), Some(784111777));
+    }
+
+
+
+    #[test]
+    fn test_header_value() {
+        assert_eq!(from_header_value(&HeaderValue::from_static("Sun, 06 Nov 1994 08:49:37 GMT")), Ok(784111777));
+        assert_eq!(from_header_value(&HeaderValue::from_static("Sun Nov  6 08:49:37 1994")), Ok(784111777));
+        assert_eq!(from_header_value(&HeaderValue::from_static("Sun, 06 Nov 1994")), Err(InvalidDate));
+
+        assert_eq!(to_header_value(0).unwrap(), "Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(to_header_value(253402300799).unwrap(), "Fri, 31 Dec 9999 23:59:59 GMT");
+        assert_eq!(to_header_value(253402300800), Err(TooFuturistic));
+    }
+
+
+
+    #[test]
+    fn test_date_now() {
+        let before = unix_now();
+        let first = from_header_value(&date_now()).unwrap();
+        let second = from_header_value(&date_provider()()).unwrap();
+        let after = unix_now();
+
+        assert!(before <= first && first <= second && second <= after);
+    }
+
+
+
+    #[test]
+    fn test_timestamp_conversions() {
+        let timestamp = Timestamp::try_from(&HeaderValue::from_static("Sun, 06 Nov 1994 08:49:37 GMT")).unwrap();
+        assert_eq!(timestamp.as_secs(), 784111777);
+        assert_eq!(Timestamp::try_from(&HeaderValue::from_static("0")), Err(InvalidDate));
+        assert_eq!(HeaderValue::from(timestamp), "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(HeaderValue::from(Timestamp::MAX), "Fri, 31 Dec 9999 23:59:59 GMT");
+    }
+}
@@ -0,0 +1,153 @@
+//! Extraction of date headers from parsed [httparse::Header](::httparse::Header) slices.
+//!
+//! ```rust
+//! let headers = [
+//!     httparse::Header { name: "Date", value: b"Fri, 15 May 2015 15:34:21 GMT" },
+//!     httparse::Header { name: "retry-after", value: b"120" },
+//! ];
+//!
+//! let dates = date_header::httparse::extract(&headers);
+//! assert_eq!(Some(1431704061), dates.date);
+//! assert_eq!(Some(date_header::httparse::RetryAfter::Delay(120)), dates.retry_after);
+//! assert_eq!(None, dates.expires);
+//! ```
+
+use ::httparse::Header;
+use crate::{delta_seconds, parse};
+
+
+
+
+/// Parse the `Date`, `Last-Modified`, `Expires`, and `Retry-After` headers in a single pass.
+///
+/// Header names are matched case-insensitively. If a header appears more than once,
+/// only the first occurrence is used, even if it is invalid and a later one isn't.
+/// Missing and invalid headers are left as `None`.
+pub fn extract(headers: &[Header]) -> DateHeaders {
+    const NAMES: [&[u8]; 4] = [b"date", b"last-modified", b"expires", b"retry-after"];
+
+    let mut dates = DateHeaders::default();
+    let mut seen = [false; NAMES.len()];
+
+    for header in headers {
+        let name = header.name.as_bytes();
+        let Some(index) = NAMES.iter().position(|known| name.eq_ignore_ascii_case(known)) else {
+            continue;
+        };
+
+        if core::mem::replace(&mut seen[index], true) {
+            continue;
+        }
+
+        match index {
+            0 => dates.date = parse(header.value).ok(),
+            1 => dates.last_modified = parse(header.value).ok(),
+            2 => dates.expires = parse(header.value).ok(),
+            _ => dates.retry_after = parse_retry_after(header.value),
+        }
+    }
+
+    dates
+}
+
+
+/// Date headers returned from [extract], as unix timestamps.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct DateHeaders {
+    /// The `Date` header.
+    pub date: Option<u64>,
+    /// The `Last-Modified` header.
+    pub last_modified: Option<u64>,
+    /// The `Expires` header.
+    pub expires: Option<u64>,
+    /// The `Retry-After` header.
+    pub retry_after: Option<RetryAfter>,
+}
+
+
+/// Value of a `Retry-After` header, which may be either an HTTP date or a number of seconds.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RetryAfter {
+    /// Unix timestamp after which the request may be retried.
+    Date(u64),
+    /// Number of seconds to wait before retrying the request.
+    Delay(u64),
+}
+
+
+
+
+fn parse_retry_after(value: &[u8]) -> Option<RetryAfter> {
+    if let Ok(date) = parse(value) {
+        return Some(RetryAfter::Date(date));
+    }
+
+    delta_seconds::parse(value).ok().map(RetryAfter::Delay)
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use ::httparse::Header;
+    use super::*;
+
+
+
+    #[test]
+    fn test_extract() {
+        assert_eq!(extract(&[]), DateHeaders::default());
+
+        let headers = [
+            Header { name: "Content-Type", value: b"text/plain" },
+            Header { name: "DATE", value: b"Sun, 06 Nov 1994 08:49:37 GMT" },
+            Header { name: "Date", value: b"Thu, 01 Jan 1970 00:00:00 GMT" }, // Later duplicates are ignored
+            Header { name: "last-modified", value: b"Sunday, 06-Nov-94 08:49:36 GMT" },
+            Header { name: "Expires", value: b"0" }, // Invalid
+            Header { name: "Retry-After", value: b"Sun Nov  6 08:49:38 1994" },
+        ];
+
+        assert_eq!(extract(&headers), DateHeaders {
+            date: Some(784111777),
+            last_modified: Some(784111776),
+            expires: None,
+            retry_after: Some(RetryAfter::Date(784111778)),
+        });
+    }
+
+
+    #[test]
+    fn test_duplicates() {
+        let headers = [
+            Header { name: "Date", value: b"yesterday" },
+            Header { name: "date", value: b"Sun, 06 Nov 1994 08:49:37 GMT" },
+            Header { name: "Retry-After", value: b"-1" },
+            Header { name: "Retry-After", value: b"120" },
+            Header { name: "Expires", value: b"Sun, 06 Nov 1994 08:49:37 GMT" },
+            Header { name: "expires", value: b"0" },
+        ];
+
+        // The first occurrence is final, whether or not it's valid
+        assert_eq!(extract(&headers), DateHeaders {
+            date: None,
+            last_modified: None,
+            expires: Some(784111777),
+            retry_after: None,
+        });
+    }
+
+
+    #[test]
+    fn test_retry_after() {
+        assert_eq!(parse_retry_after(b"0"), Some(RetryAfter::Delay(0)));
+        assert_eq!(parse_retry_after(b"120"), Some(RetryAfter::Delay(120)));
+        assert_eq!(parse_retry_after(b"99999999999999999999999"), Some(RetryAfter::Delay(delta_seconds::MAX))); // Capped
+        assert_eq!(parse_retry_after(b"Sun, 06 Nov 1994 08:49:37 GMT"), Some(RetryAfter::Date(784111777)));
+
+        assert_eq!(parse_retry_after(b""), None);
+        assert_eq!(parse_retry_after(b"-1"), None);
+        assert_eq!(parse_retry_after(b"1.5"), None);
+        assert_eq!(parse_retry_after(b" 120"), None);
+    }
+}
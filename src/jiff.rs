@@ -0,0 +1,194 @@
+//! Conversions between [jiff](::jiff)'s `Timestamp` and `Zoned` and unix timestamps,
+//! for codebases using jiff that want to round-trip header dates through this crate's formatter.
+//!
+//! HTTP dates only have whole seconds, so converting from jiff truncates toward the past,
+//! which is the right direction for `Last-Modified` and other validators.
+//! A `Zoned` in any time zone is converted to the same instant in GMT.
+//!
+//! jiff's range ends a couple of hours before the last day of 9999 is over in UTC,
+//! so the last few formattable timestamps can't be converted to jiff.
+//!
+//! ```rust
+//! let modified = jiff::Timestamp::new(1431704061, 700_000_000).unwrap();
+//! assert_eq!(Ok(1431704061), date_header::jiff::from_jiff(&modified));
+//!
+//! let mut header = [0u8; 29];
+//! date_header::jiff::format_jiff(&modified, &mut header).unwrap();
+//! assert_eq!(&header, b"Fri, 15 May 2015 15:34:21 GMT");
+//! ```
+
+use ::jiff::tz::TimeZone;
+use ::jiff::{Timestamp as JiffTimestamp, Zoned};
+use crate::{format, in_formattable_range, Timestamp, TooFuturistic, FORMATTED_LEN};
+
+
+
+
+/// Convert a jiff timestamp into a unix timestamp, discarding any fractional second.
+///
+/// Fails for times before the unix epoch or after the year 9999.
+pub fn from_jiff(timestamp: &JiffTimestamp) -> Result<u64, OutOfRange> {
+    // jiff truncates toward zero, which would put the second before the epoch at the epoch
+    if timestamp.signum() < 0 {
+        return Err(OutOfRange);
+    }
+
+    match u64::try_from(timestamp.as_second()) {
+        Ok(secs) if in_formattable_range(secs) => Ok(secs),
+        _ => Err(OutOfRange),
+    }
+}
+
+
+/// Convert a zoned date time in any time zone into a unix timestamp, discarding any fractional second.
+///
+/// Fails for times before the unix epoch or after the year 9999.
+pub fn from_zoned(zoned: &Zoned) -> Result<u64, OutOfRange> {
+    from_jiff(&zoned.timestamp())
+}
+
+
+/// Convert a unix timestamp into a jiff timestamp.
+///
+/// Fails for times after jiff's maximum, late on December 30th, 9999.
+pub fn to_jiff(secs_since_epoch: u64) -> Result<JiffTimestamp, TooFuturistic> {
+    i64::try_from(secs_since_epoch).ok()
+        .and_then(|secs| JiffTimestamp::from_second(secs).ok())
+        .ok_or(TooFuturistic)
+}
+
+
+/// Convert a unix timestamp into a zoned date time in UTC.
+///
+/// Fails for times after jiff's maximum, late on December 30th, 9999.
+pub fn to_zoned(secs_since_epoch: u64) -> Result<Zoned, TooFuturistic> {
+    to_jiff(secs_since_epoch).map(|timestamp| timestamp.to_zoned(TimeZone::UTC))
+}
+
+
+/// Format a jiff timestamp as an IMF-fixdate into the provided buffer, as [format] does.
+///
+/// Fails for times before the unix epoch or after the year 9999.
+pub fn format_jiff(timestamp: &JiffTimestamp, buffer: &mut [u8; FORMATTED_LEN]) -> Result<(), OutOfRange> {
+    let secs = from_jiff(timestamp)?;
+    format(secs, buffer).map_err(|_| OutOfRange)
+}
+
+
+/// Format a zoned date time as an IMF-fixdate into the provided buffer, as [format] does.
+///
+/// Fails for times before the unix epoch or after the year 9999.
+pub fn format_zoned(zoned: &Zoned, buffer: &mut [u8; FORMATTED_LEN]) -> Result<(), OutOfRange> {
+    format_jiff(&zoned.timestamp(), buffer)
+}
+
+
+/// Error returned when a jiff value can't be an HTTP date.
+#[derive(Debug, Eq, PartialEq)]
+pub struct OutOfRange;
+
+
+
+
+impl TryFrom<JiffTimestamp> for Timestamp {
+    type Error = OutOfRange;
+
+    fn try_from(timestamp: JiffTimestamp) -> Result<Timestamp, OutOfRange> {
+        from_jiff(&timestamp).map(|secs| Timestamp::new(secs).expect("from_jiff is always in the formattable range"))
+    }
+}
+
+impl TryFrom<&Zoned> for Timestamp {
+    type Error = OutOfRange;
+
+    fn try_from(zoned: &Zoned) -> Result<Timestamp, OutOfRange> {
+        Timestamp::try_from(zoned.timestamp())
+    }
+}
+
+impl TryFrom<Timestamp> for JiffTimestamp {
+    type Error = TooFuturistic;
+
+    fn try_from(timestamp: Timestamp) -> Result<JiffTimestamp, TooFuturistic> {
+        to_jiff(timestamp.as_secs())
+    }
+}
+
+impl TryFrom<Timestamp> for Zoned {
+    type Error = TooFuturistic;
+
+    fn try_from(timestamp: Timestamp) -> Result<Zoned, TooFuturistic> {
+        to_zoned(timestamp.as_secs())
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use ::jiff::tz::Offset;
+    use crate::MAX_FORMATTABLE;
+    use super::*;
+
+
+
+    fn jiff(seconds: i64, nanos: i32) -> JiffTimestamp {
+        JiffTimestamp::new(seconds, nanos).unwrap()
+    }
+
+
+    #[test]
+    fn test_from_jiff() {
+        assert_eq!(from_jiff(&jiff(0, 0)), Ok(0));
+        assert_eq!(from_jiff(&jiff(784111777, 999_999_999)), Ok(784111777)); // Truncated, not rounded
+        assert_eq!(from_jiff(&JiffTimestamp::MAX), Ok(JiffTimestamp::MAX.as_second() as u64));
+
+        assert_eq!(from_jiff(&jiff(0, -1)), Err(OutOfRange)); // Would truncate to the epoch
+        assert_eq!(from_jiff(&jiff(-1, 0)), Err(OutOfRange));
+
+        // The same instant in any time zone
+        let zoned = jiff(784111777, 0).to_zoned(TimeZone::fixed(Offset::constant(-5)));
+        assert_eq!(from_zoned(&zoned), Ok(784111777));
+    }
+
+
+    #[test]
+    fn test_to_jiff() {
+        assert_eq!(to_jiff(0), Ok(JiffTimestamp::UNIX_EPOCH));
+        assert_eq!(to_jiff(JiffTimestamp::MAX.as_second() as u64), Ok(jiff(JiffTimestamp::MAX.as_second(), 0)));
+        assert_eq!(to_jiff(JiffTimestamp::MAX.as_second() as u64 + 1), Err(TooFuturistic));
+        assert_eq!(to_jiff(MAX_FORMATTABLE), Err(TooFuturistic)); // Past the end of jiff's range
+        assert_eq!(to_jiff(u64::MAX), Err(TooFuturistic));
+
+        let zoned = to_zoned(784111777).unwrap();
+        assert_eq!((zoned.timestamp(), zoned.time_zone()), (jiff(784111777, 0), &TimeZone::UTC));
+        assert_eq!(to_zoned(u64::MAX), Err(TooFuturistic));
+    }
+
+
+    #[test]
+    fn test_format_jiff() {
+        let mut buffer = [0; FORMATTED_LEN];
+        assert_eq!(format_jiff(&jiff(784111777, 500_000_000), &mut buffer), Ok(()));
+        assert_eq!(&buffer, b"Sun, 06 Nov 1994 08:49:37 GMT");
+
+        let zoned = jiff(784111777, 0).to_zoned(TimeZone::fixed(Offset::constant(9)));
+        assert_eq!(format_zoned(&zoned, &mut buffer), Ok(()));
+        assert_eq!(&buffer, b"Sun, 06 Nov 1994 08:49:37 GMT");
+
+        assert_eq!(format_jiff(&jiff(-1, 0), &mut buffer), Err(OutOfRange));
+        assert_eq!(format_jiff(&JiffTimestamp::MAX, &mut buffer), Ok(()));
+        assert_eq!(&buffer, b"Thu, 30 Dec 9999 22:00:00 GMT");
+    }
+
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Timestamp::try_from(jiff(784111777, 500_000_000)), Ok(Timestamp::new(784111777).unwrap()));
+        assert_eq!(Timestamp::try_from(jiff(-1, 0)), Err(OutOfRange));
+        assert_eq!(Timestamp::try_from(&Zoned::try_from(Timestamp::MIN).unwrap()), Ok(Timestamp::MIN));
+        assert_eq!(JiffTimestamp::try_from(Timestamp::MIN), Ok(JiffTimestamp::UNIX_EPOCH));
+        assert_eq!(JiffTimestamp::try_from(Timestamp::MAX), Err(TooFuturistic));
+    }
+}
@@ -0,0 +1,229 @@
+//! Best-effort parsing for date values that aren't valid HTTP dates, but turn up in them anyway.
+//!
+//! Crawled pages and misbehaving servers put all sorts of things in date-valued fields.
+//! The strict [parse](crate::parse) rejects them, as the RFCs require; these functions recover
+//! what they can, for when a guess is more useful than nothing.
+
+use crate::log::apply_zone;
+use crate::{invalid, parse_epoch_decimal, DateRange, InvalidDate, MAX_FORMATTABLE, YEAR_1971};
+
+
+
+
+/// The epoch seconds accepted by [parse]: anything after 1970 that can be formatted.
+///
+/// A year, whether written with two digits or four, is far earlier than 1971 when read as seconds,
+/// so it is never mistaken for a timestamp.
+pub const DEFAULT_EPOCH_WINDOW: DateRange = DateRange { not_before: YEAR_1971, not_after: MAX_FORMATTABLE };
+
+
+/// Length of a JavaScript date without its parenthesized zone name: `Fri May 15 2015 15:34:21 GMT+0000`
+const JS_DATE_LEN: usize = 33;
+
+/// Longest value that can have `Sept` shortened, which is plenty for any format here with a real zone name
+const SEPT_BUFFER_LEN: usize = 128;
+
+
+
+
+/// Parse a date value, accepting anything that can be recognized with reasonable confidence.
+///
+/// Surrounding whitespace is ignored. Then, in order:
+///
+/// * An HTTP date in any of the formats accepted by [parse](crate::parse)
+/// * A JavaScript date, as accepted by [parse_js_date]
+/// * Unix epoch seconds written as plain digits, within [DEFAULT_EPOCH_WINDOW]
+///
+/// In the dates, September may also be abbreviated `Sept`, as in `Tue, 06 Sept 1994 08:49:37 GMT`.
+///
+/// ```rust
+/// use date_header::lenient;
+///
+/// assert_eq!(Ok(1431704061), lenient::parse(b" Fri, 15 May 2015 15:34:21 GMT "));
+/// assert_eq!(Ok(1431704061), lenient::parse(b"Fri May 15 2015 17:34:21 GMT+0200"));
+/// assert_eq!(Ok(1431704061), lenient::parse(b"1431704061"));
+/// assert_eq!(Ok(778841377), lenient::parse(b"Tue, 06 Sept 1994 08:49:37 GMT"));
+/// assert!(lenient::parse(b"15").is_err());
+/// ```
+pub fn parse(value: &[u8]) -> Result<u64, InvalidDate> {
+    parse_with(value, DEFAULT_EPOCH_WINDOW)
+}
+
+
+/// Parse a date value as [parse] does, accepting epoch seconds only within `epoch_window`.
+///
+/// Narrow the window when the plausible dates are known, such as to recent years for a `Last-Modified`
+/// header, so that stray numbers are rejected rather than read as dates.
+///
+/// ```rust
+/// use date_header::{lenient, DateRange};
+///
+/// let recent = DateRange { not_before: 1420070400, not_after: 1451606399 }; // 2015
+/// assert_eq!(Ok(1431704061), lenient::parse_with(b"1431704061", recent));
+/// assert!(lenient::parse_with(b"1999", recent).is_err());
+/// assert!(lenient::parse_with(b"784111777", recent).is_err());
+/// ```
+pub fn parse_with(value: &[u8], epoch_window: DateRange) -> Result<u64, InvalidDate> {
+    let mut buffer = [0u8; SEPT_BUFFER_LEN];
+    let value = value.trim_ascii();
+    let value = shorten_sept(value, &mut buffer).unwrap_or(value);
+
+    crate::parse(value)
+        .or_else(|_| parse_js_date(value))
+        .or_else(|_| match parse_epoch_decimal(value) {
+            Ok(secs) if epoch_window.contains(secs) => Ok(secs),
+            _ => Err(invalid()),
+        })
+}
+
+
+/// Parse the output of JavaScript's `Date.prototype.toString()` into a unix timestamp.
+///
+/// The format is `Fri May 15 2015 15:34:21 GMT+0000 (Coordinated Universal Time)`: a date and time
+/// in the browser's local zone, its offset from UTC, and optionally the zone's name in parentheses.
+/// The name is ignored, since the offset already says everything needed.
+/// The weekday must match the local date, which must be from 1970 through 9999, and the result is converted to UTC.
+///
+/// ```rust
+/// let value = b"Fri May 15 2015 08:34:21 GMT-0700 (Pacific Daylight Time)";
+/// assert_eq!(Ok(1431704061), date_header::lenient::parse_js_date(value));
+/// ```
+pub fn parse_js_date(value: &[u8]) -> Result<u64, InvalidDate> {
+    if value.len() < JS_DATE_LEN {
+        return Err(invalid());
+    }
+
+    let (date, name) = value.split_at(JS_DATE_LEN);
+    if !matches!(name, [] | [b' ', b'(', .., b')']) {
+        return Err(invalid());
+    }
+
+    // `Fri May 15 2015 15:34:21` is an asctime date with the year moved after the day
+    let (date, offset) = date.split_at(28);
+    let (date, gmt) = date.split_at(24);
+    if date[15] != b' ' || gmt != b" GMT" {
+        return Err(invalid());
+    }
+
+    let mut asctime = [0u8; 24];
+    asctime[..11].copy_from_slice(&date[..11]);
+    asctime[11..19].copy_from_slice(&date[16..]);
+    asctime[19] = b' ';
+    asctime[20..].copy_from_slice(&date[11..15]);
+
+    let local = crate::parse(&asctime)?;
+    apply_zone(local, offset)
+}
+
+
+
+
+// Copy a date using the four letter `Sept` into the buffer, with the usual `Sep` in its place
+fn shorten_sept<'a>(value: &[u8], buffer: &'a mut [u8; SEPT_BUFFER_LEN]) -> Option<&'a [u8]> {
+    let t = value.windows(6).position(|word| matches!(word, [b' ' | b'-', b'S', b'e', b'p', b't', b' ' | b'-']))? + 4;
+
+    let shortened = buffer.get_mut(..value.len() - 1)?;
+    shortened[..t].copy_from_slice(&value[..t]);
+    shortened[t..].copy_from_slice(&value[t + 1..]);
+    Some(shortened)
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(b"Fri, 15 May 2015 15:34:21 GMT"), Ok(1431704061));
+        assert_eq!(parse(b"Friday, 15-May-15 15:34:21 GMT"), Ok(1431704061));
+        assert_eq!(parse(b"Fri May 15 15:34:21 2015"), Ok(1431704061));
+        assert_eq!(parse(b"\t Fri, 15 May 2015 15:34:21 GMT\r\n"), Ok(1431704061));
+        assert_eq!(parse(b"Fri May 15 2015 15:34:21 GMT+0000 (Coordinated Universal Time)"), Ok(1431704061));
+        assert_eq!(parse(b" Fri May 15 2015 15:34:21 GMT+0000 "), Ok(1431704061));
+
+        assert_eq!(parse(b""), Err(InvalidDate));
+        assert_eq!(parse(b"yesterday"), Err(InvalidDate));
+        assert_eq!(parse(b"-1431704061"), Err(InvalidDate));
+        assert_eq!(parse(b"Sat, 15 May 2015 15:34:21 GMT"), Err(InvalidDate));
+    }
+
+
+    #[test]
+    fn test_parse_epoch_seconds() {
+        assert_eq!(parse(b"1431704061"), Ok(1431704061));
+        assert_eq!(parse(b" 1431704061\n"), Ok(1431704061));
+        assert_eq!(parse(b"31536000"), Ok(31536000));
+        assert_eq!(parse(b"253402300799"), Ok(253402300799));
+
+        // Years and other small numbers aren't plausible timestamps
+        assert_eq!(parse(b"94"), Err(InvalidDate));
+        assert_eq!(parse(b"1994"), Err(InvalidDate));
+        assert_eq!(parse(b"0"), Err(InvalidDate));
+        assert_eq!(parse(b"31535999"), Err(InvalidDate));
+        assert_eq!(parse(b"253402300800"), Err(InvalidDate));
+        assert_eq!(parse(b"99999999999999999999999"), Err(InvalidDate));
+        assert_eq!(parse(b"1431704061.5"), Err(InvalidDate));
+        assert_eq!(parse(b"1431 704061"), Err(InvalidDate));
+
+        let window = DateRange { not_before: 0, not_after: 100 };
+        assert_eq!(parse_with(b"0", window), Ok(0));
+        assert_eq!(parse_with(b"94", window), Ok(94));
+        assert_eq!(parse_with(b"101", window), Err(InvalidDate));
+        assert_eq!(parse_with(b"1431704061", window), Err(InvalidDate));
+        assert_eq!(parse_with(b"Fri, 15 May 2015 15:34:21 GMT", window), Ok(1431704061)); // Only epoch seconds are limited
+    }
+
+
+    #[test]
+    fn test_parse_sept() {
+        assert_eq!(parse(b"Tue, 06 Sept 1994 08:49:37 GMT"), Ok(778841377));
+        assert_eq!(parse(b"Tuesday, 06-Sept-94 08:49:37 GMT"), Ok(778841377));
+        assert_eq!(parse(b"Tue Sept  6 08:49:37 1994"), Ok(778841377));
+        assert_eq!(parse(b"Tue Sept 06 1994 10:49:37 GMT+0200 (Central European Summer Time)"), Ok(778841377));
+        assert_eq!(parse(b"Tue, 06 Sep 1994 08:49:37 GMT"), Ok(778841377));
+
+        assert_eq!(parse(b"Tue, 06 sept 1994 08:49:37 GMT"), Err(InvalidDate));
+        assert_eq!(parse(b"Tue, 06 Septe 1994 08:49:37 GMT"), Err(InvalidDate));
+
+        // Only the lenient paths accept it
+        assert_eq!(crate::parse(b"Tue, 06 Sept 1994 08:49:37 GMT"), Err(InvalidDate));
+    }
+
+
+    #[test]
+    fn test_parse_js_date() {
+        assert_eq!(parse_js_date(b"Fri May 15 2015 15:34:21 GMT+0000 (Coordinated Universal Time)"), Ok(1431704061));
+        assert_eq!(parse_js_date(b"Fri May 15 2015 15:34:21 GMT+0000"), Ok(1431704061));
+        assert_eq!(parse_js_date(b"Fri May 15 2015 21:04:21 GMT+0530 (India Standard Time)"), Ok(1431704061));
+        assert_eq!(parse_js_date(b"Fri May 15 2015 08:34:21 GMT-0700 (PDT)"), Ok(1431704061));
+        assert_eq!(parse_js_date(b"Thu Jan 01 1970 00:00:00 GMT+0000 ()"), Ok(0));
+
+        // The weekday is of the local date, which can differ from the UTC one
+        assert_eq!(parse_js_date(b"Sat May 16 2015 01:34:21 GMT+1000 (Australian Eastern Standard Time)"), Ok(1431704061));
+        assert_eq!(parse_js_date(b"Fri May 16 2015 01:34:21 GMT+1000 (Australian Eastern Standard Time)"), Err(InvalidDate));
+    }
+
+
+    #[test]
+    fn test_parse_js_date_invalid() {
+        assert_eq!(parse_js_date(b"Wed Dec 31 1969 16:00:00 GMT-0800 (Pacific Standard Time)"), Err(InvalidDate)); // Local date before 1970
+        assert_eq!(parse_js_date(b"Thu Jan 01 1970 00:59:59 GMT+0100"), Err(InvalidDate)); // Before the epoch in UTC
+        assert_eq!(parse_js_date(b"Fri May 15 2015 15:34:21 GMT+0000 Coordinated Universal Time"), Err(InvalidDate));
+        assert_eq!(parse_js_date(b"Fri May 15 2015 15:34:21 GMT+0000 (Coordinated Universal Time"), Err(InvalidDate));
+        assert_eq!(parse_js_date(b"Fri May 15 2015 15:34:21 GMT+0000(UTC)"), Err(InvalidDate));
+        assert_eq!(parse_js_date(b"Fri May 15 2015 15:34:21 UTC+0000"), Err(InvalidDate));
+        assert_eq!(parse_js_date(b"Fri May 15 2015 15:34:21 GMT 0000"), Err(InvalidDate));
+        assert_eq!(parse_js_date(b"Fri May 15 2015 15:34:21 GMT+00"), Err(InvalidDate));
+        assert_eq!(parse_js_date(b"Fri May 15 2015 15:34:21 GMT+2400"), Err(InvalidDate));
+        assert_eq!(parse_js_date(b"Fri May 15 2015 15:34:21 GMT"), Err(InvalidDate));
+        assert_eq!(parse_js_date(b"Fri May 15 2015-15:34:21 GMT+0000"), Err(InvalidDate));
+        assert_eq!(parse_js_date(b"Fri May 32 2015 15:34:21 GMT+0000"), Err(InvalidDate));
+        assert_eq!(parse_js_date(b"Fri May 15 15:34:21 2015"), Err(InvalidDate));
+        assert_eq!(parse_js_date(b""), Err(InvalidDate));
+    }
+}
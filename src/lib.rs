@@ -1,12 +1,131 @@
 #![doc = include_str!("../README.md")]
-#![forbid(unsafe_code)]
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(feature = "unsafe-fast", feature = "ffi", feature = "no-panic", feature = "simd")), forbid(unsafe_code))]
+#![cfg_attr(any(feature = "unsafe-fast", feature = "ffi", feature = "no-panic", feature = "simd"), deny(unsafe_code))]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+
+
+#[cfg(feature = "axum")]
+pub mod axum;
+#[cfg(feature = "borsh")]
+mod borsh;
+mod cache;
+#[cfg(feature = "std")]
+mod cached;
+mod calendar;
+mod capabilities;
+#[cfg(feature = "chrono")]
+pub mod chrono;
+#[cfg(feature = "u32")]
+mod compact;
+#[cfg(feature = "std")]
+pub mod compat;
+mod compare;
+#[cfg(all(test, feature = "corpus-tests"))]
+mod corpus;
+#[cfg(feature = "defmt")]
+mod defmt;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod conditional;
+pub mod cookie;
+mod date;
+pub mod dos;
+#[cfg(test)]
+mod differential;
+pub mod delta_seconds;
+mod epoch;
+#[cfg(test)]
+mod grammar;
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(feature = "httparse")]
+pub mod httparse;
+#[cfg(feature = "jiff")]
+pub mod jiff;
+pub mod lenient;
+pub mod log;
+mod no_panic;
+pub mod ntp;
+#[cfg(any(feature = "proptest", feature = "rand"))]
+mod obsolete;
+#[cfg(feature = "prost")]
+pub mod prost;
+#[cfg(feature = "rand")]
+pub mod random;
+mod range;
+#[cfg(feature = "rayon")]
+pub mod rayon;
+mod ratelimit;
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(feature = "schemars")]
+pub mod schemars;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+#[cfg(feature = "quickcheck")]
+mod arbitrary;
+#[cfg(all(feature = "simd", target_feature = "sse2"))]
+mod simd;
+mod sunset;
+#[cfg(any(test, feature = "test-vectors"))]
+pub mod test_vectors;
+#[cfg(feature = "std")]
+mod system_time;
+#[cfg(feature = "time")]
+pub mod time;
+mod timestamp;
+#[cfg(feature = "tracing")]
+pub mod tracing;
+#[cfg(feature = "ufmt-write")]
+pub mod ufmt_write;
+#[cfg(feature = "uuid")]
+pub mod uuid;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "winnow")]
+pub mod winnow;
+#[cfg(kani)]
+mod verification;
+
+use calendar::{civil_from_days, civil_to_days, split_days, split_time, MONTH_NAMES, WEEKDAY_NAMES};
+
+pub use cache::{age_of, check_date, check_date_header, clock_skew, freshness_lifetime, is_expired, parse_expires, seconds_until, CacheKind, Correction, DateCheck, Expiry, FreshnessSources};
+#[cfg(feature = "std")]
+pub use cached::format_now;
+pub use calendar::{days_in_month, days_in_year, from_jdn, is_leap_year, iso_week, ordinal_day, start_of_day, start_of_hour, start_of_minute, to_jdn, weekday_of, Month, Weekday};
+pub use capabilities::{capabilities, Capabilities, Format};
+#[cfg(feature = "u32")]
+pub use compact::{format_u32, parse_u32};
+pub use compare::{between, compare, earlier_of, later_of};
+pub use conditional::{honor_range, not_modified, precondition_failed, validator_strength, ValidatorStrength};
+pub use date::{HttpDate, HttpDateBuilder, InvalidComponent};
+pub use epoch::{format_epoch_decimal, parse_epoch_decimal};
+pub use range::DateRange;
+pub use ratelimit::parse_rate_limit_reset;
+pub use sunset::Sunset;
+#[cfg(feature = "std")]
+pub use system_time::{format_system_time, from_system_time, parse_system_time, to_system_time, OutOfRange, Rounding};
+pub use timestamp::Timestamp;
+
+
+
+
+// Unix timestamp for Jan 1st, 1971
+const YEAR_1971: u64 = 31536000;
 
+// Unix timestamp for Jan 1st, 10000
+const YEAR_10000: u64 = 253402300800;
 
+/// The earliest unix timestamp that can be formatted: `Thu, 01 Jan 1970 00:00:00 GMT`
+pub const MIN: u64 = 0;
 
+/// The latest unix timestamp that can be formatted: `Fri, 31 Dec 9999 23:59:59 GMT`
+pub const MAX_FORMATTABLE: u64 = YEAR_10000 - 1;
 
-// Unix timestamp for Jan 1st, 10000
-const YEAR_10000: u64 = 253402300800;
+/// Length of an IMF-fixdate, and therefore of the buffer passed to [format].
+pub const FORMATTED_LEN: usize = 29;
 
 
 
@@ -24,124 +143,127 @@ const YEAR_10000: u64 = 253402300800;
 /// assert_eq!(Ok(()), date_header::format(1431704061, &mut header));
 /// assert_eq!(&header, b"Fri, 15 May 2015 15:34:21 GMT");
 /// ```
-pub fn format(secs_since_epoch: u64, buffer: &mut [u8; 29]) -> Result<(), TooFuturistic> {
-    if secs_since_epoch >= YEAR_10000 {
-        return Err(TooFuturistic);
-    }
-
-    /* 2000-03-01 (mod 400 year, immediately after feb29 */
-    const LEAPOCH: i64 = 11017;
-    const DAYS_PER_400Y: i64 = 365 * 400 + 97;
-    const DAYS_PER_100Y: i64 = 365 * 100 + 24;
-    const DAYS_PER_4Y: i64 = 365 * 4 + 1;
+#[inline]
+pub fn format(secs_since_epoch: u64, buffer: &mut [u8; FORMATTED_LEN]) -> Result<(), TooFuturistic> {
+    no_panic::checked(|| {
+        if !in_formattable_range(secs_since_epoch) {
+            return Err(TooFuturistic);
+        }
 
-    let days = (secs_since_epoch / 86400) as i64 - LEAPOCH;
-    let secs_of_day = secs_since_epoch % 86400;
+        let (days, secs_of_day) = split_days(secs_since_epoch);
+        write_imf_fixdate(days, secs_of_day, buffer);
 
-    let sec = (secs_of_day % 60) as u8;
-    let min = ((secs_of_day % 3600) / 60) as u8;
-    let hour = (secs_of_day / 3600) as u8;
+        Ok(())
+    })
+}
 
-    let mut qc_cycles = days / DAYS_PER_400Y;
-    let mut remdays = days % DAYS_PER_400Y;
 
-    if remdays < 0 {
-        remdays += DAYS_PER_400Y;
-        qc_cycles -= 1;
-    }
+// Write the IMF-fixdate for a day since the epoch and a second of that day
+#[inline]
+pub(crate) fn write_imf_fixdate(days: u32, secs_of_day: u32, buffer: &mut [u8; FORMATTED_LEN]) {
+    let (year, mon, mday) = civil_from_days(days);
+
+    let (hour, min, sec) = split_time(secs_of_day);
+
+    // The month is always 1 through 12, but the optimizer can't see that through
+    // civil_from_days, so the remainder is what removes the bounds check
+    let wday = &WEEKDAY_NAMES[Weekday::from_days(days) as usize];
+    let month = &MONTH_NAMES[usize::from(mon - 1) % 12];
+
+    // Assemble the output as four little-endian words holding the fixed punctuation,
+    // then write each with a single store instead of one store per byte. The last two
+    // words overlap by three bytes that both of them hold identically, so no byte is
+    // written on its own and nothing is copied from a template first.
+    let head = u64::from_le_bytes(*b"\0\0\0, \0\0 ")
+        | u64::from(wday[0])
+        | u64::from(wday[1]) << 8
+        | u64::from(wday[2]) << 16
+        | two_digits(mday) << 40;
+
+    let middle = u64::from_le_bytes(*b"\0\0\0 \0\0\0\0")
+        | u64::from(month[0])
+        | u64::from(month[1]) << 8
+        | u64::from(month[2]) << 16
+        | two_digits((year / 100) as u8) << 32
+        | two_digits((year % 100) as u8) << 48;
+
+    // ` HH:MM:S`, where the shift drops the last digit of the seconds
+    let time = u64::from_le_bytes(*b" \0\0:\0\0:\0")
+        | two_digits(hour) << 8
+        | two_digits(min) << 32
+        | two_digits(sec) << 56;
+
+    // `M:SS GMT`, starting from the last digit of the minutes
+    let tail = u64::from_le_bytes(*b"\0:\0\0 GMT")
+        | two_digits(min) >> 8
+        | two_digits(sec) << 16;
+
+    buffer[..8].copy_from_slice(&head.to_le_bytes());
+    buffer[8..16].copy_from_slice(&middle.to_le_bytes());
+    buffer[16..24].copy_from_slice(&time.to_le_bytes());
+    buffer[21..].copy_from_slice(&tail.to_le_bytes());
+}
 
-    let mut c_cycles = remdays / DAYS_PER_100Y;
-    if c_cycles == 4 {
-        c_cycles -= 1;
-    }
-    remdays -= c_cycles * DAYS_PER_100Y;
 
-    let mut q_cycles = remdays / DAYS_PER_4Y;
-    if q_cycles == 25 {
-        q_cycles -= 1;
+// ASCII digits of every number from 0 through 99, so formatting needs no division by ten.
+// The table covers every u8 so that indexing it has no bounds check, and the rest are never used.
+#[cfg(feature = "fast-tables")]
+static DIGIT_PAIRS: [[u8; 2]; 256] = {
+    let mut pairs = [[0; 2]; 256];
+    let mut i = 0;
+    while i < 100 {
+        pairs[i] = [b'0' + (i / 10) as u8, b'0' + (i % 10) as u8];
+        i += 1;
     }
-    remdays -= q_cycles * DAYS_PER_4Y;
+    pairs
+};
 
-    let mut remyears = remdays / 365;
-    if remyears == 4 {
-        remyears -= 1;
-    }
-    remdays -= remyears * 365;
 
-    let mut year = 2000 + remyears + 4 * q_cycles + 100 * c_cycles + 400 * qc_cycles;
+// The two ASCII digits of a number below 100, in the low 16 bits of a little-endian word
+#[cfg(feature = "fast-tables")]
+#[inline]
+fn two_digits(n: u8) -> u64 {
+    u64::from(u16::from_le_bytes(DIGIT_PAIRS[usize::from(n)]))
+}
 
-    let months = [31, 30, 31, 30, 31, 31, 30, 31, 30, 31, 31, 29];
-    let mut mon = 0;
-    for mon_len in months.iter() {
-        mon += 1;
-        if remdays < *mon_len {
-            break;
-        }
-        remdays -= *mon_len;
-    }
-    let mday = remdays + 1;
-    let mon = if mon + 2 > 12 {
-        year += 1;
-        mon - 10
-    } else {
-        mon + 2
-    };
 
-    let mut wday = (3 + days) % 7;
-    if wday <= 0 {
-        wday += 7
-    };
+// The same digits without a table. n * 103 >> 10 is n / 10 for every n below 100.
+#[cfg(not(feature = "fast-tables"))]
+#[inline]
+fn two_digits(n: u8) -> u64 {
+    debug_assert!(n < 100);
 
-    let wday = match wday {
-        1 => b"Mon",
-        2 => b"Tue",
-        3 => b"Wed",
-        4 => b"Thu",
-        5 => b"Fri",
-        6 => b"Sat",
-        7 => b"Sun",
-        _ => unreachable!(),
-    };
+    let tens = (u64::from(n) * 103) >> 10;
+    let ones = u64::from(n) - tens * 10;
+    0x3030 | tens | ones << 8
+}
 
-    let month = match mon {
-        1 => b"Jan",
-        2 => b"Feb",
-        3 => b"Mar",
-        4 => b"Apr",
-        5 => b"May",
-        6 => b"Jun",
-        7 => b"Jul",
-        8 => b"Aug",
-        9 => b"Sep",
-        10 => b"Oct",
-        11 => b"Nov",
-        12 => b"Dec",
-        _ => unreachable!(),
-    };
+/// Check whether a unix timestamp can be formatted, meaning it is not after [MAX_FORMATTABLE].
+///
+/// ```rust
+/// assert!(date_header::in_formattable_range(253402300799));
+/// assert!(!date_header::in_formattable_range(253402300800));
+/// ```
+pub const fn in_formattable_range(secs_since_epoch: u64) -> bool {
+    secs_since_epoch <= MAX_FORMATTABLE
+}
 
-    *buffer = *b"   , 00     0000 00:00:00 GMT";
-    buffer[0] = wday[0];
-    buffer[1] = wday[1];
-    buffer[2] = wday[2];
-    buffer[5] = b'0' + (mday / 10) as u8;
-    buffer[6] = b'0' + (mday % 10) as u8;
-    buffer[8] = month[0];
-    buffer[9] = month[1];
-    buffer[10] = month[2];
-    buffer[12] = b'0' + (year / 1000) as u8;
-    buffer[13] = b'0' + (year / 100 % 10) as u8;
-    buffer[14] = b'0' + (year / 10 % 10) as u8;
-    buffer[15] = b'0' + (year % 10) as u8;
-    buffer[17] = b'0' + (hour / 10);
-    buffer[18] = b'0' + (hour % 10);
-    buffer[20] = b'0' + (min / 10);
-    buffer[21] = b'0' + (min % 10);
-    buffer[23] = b'0' + (sec / 10);
-    buffer[24] = b'0' + (sec % 10);
 
-    Ok(())
+/// Clamp a unix timestamp to [MAX_FORMATTABLE], so that formatting it cannot fail.
+///
+/// ```rust
+/// assert_eq!(1431704061, date_header::clamp_to_range(1431704061));
+/// assert_eq!(date_header::MAX_FORMATTABLE, date_header::clamp_to_range(u64::MAX));
+/// ```
+pub const fn clamp_to_range(secs_since_epoch: u64) -> u64 {
+    if secs_since_epoch > MAX_FORMATTABLE {
+        MAX_FORMATTABLE
+    } else {
+        secs_since_epoch
+    }
 }
 
+
 /// Error returned from [format] indicating that the timestamp is too far into the future.
 ///
 /// IMF-fixdate only supports days prior to the year 10000
@@ -160,11 +282,137 @@ pub struct TooFuturistic;
 /// let header = b"Fri, 15 May 2015 15:34:21 GMT";
 /// assert_eq!(Ok(1431704061), date_header::parse(header));
 /// ```
+#[inline]
 pub fn parse(header: &[u8]) -> Result<u64, InvalidDate> {
-    let date = parse_imf_fixdate(header)
-        .or_else(|_| parse_rfc850_date(header))
-        .or_else(|_| parse_asctime(header))?;
+    no_panic::checked(|| parse_date(header).map(|(_, timestamp)| timestamp))
+}
+
+
+/// Parse an optional HTTP date header, as returned by header map lookups.
+///
+/// A missing header is `Ok(None)`, while a present but invalid one is still an error.
+///
+/// ```rust
+/// assert_eq!(Ok(Some(1431704061)), date_header::parse_opt(Some(b"Fri, 15 May 2015 15:34:21 GMT")));
+/// assert_eq!(Ok(None), date_header::parse_opt(None));
+/// assert!(date_header::parse_opt(Some(b"yesterday")).is_err());
+/// ```
+#[inline]
+pub fn parse_opt(header: Option<&[u8]>) -> Result<Option<u64>, InvalidDate> {
+    header.map(parse).transpose()
+}
+
+
+/// Parse an optional HTTP date header, treating an invalid date as if the header were missing.
+///
+/// This is what [RFC9110](https://datatracker.ietf.org/doc/html/rfc9110#section-13.1.3) asks of recipients of
+/// `If-Modified-Since` and `If-Unmodified-Since`, which must ignore the header if it is not a valid HTTP date.
+///
+/// ```rust
+/// assert_eq!(Some(1431704061), date_header::parse_opt_or_ignore(Some(b"Fri, 15 May 2015 15:34:21 GMT")));
+/// assert_eq!(None, date_header::parse_opt_or_ignore(Some(b"yesterday")));
+/// assert_eq!(None, date_header::parse_opt_or_ignore(None));
+/// ```
+#[inline]
+pub fn parse_opt_or_ignore(header: Option<&[u8]>) -> Option<u64> {
+    header.and_then(|header| parse(header).ok())
+}
+
+
+/// Parse an HTTP date from the start of the input, returning the rest of the input along with the timestamp.
+///
+/// This has the same shape as a nom parser, for embedding the date grammar in a larger parser
+/// of header fields. The date must be followed by nothing at all or by whatever the caller parses next.
+///
+/// ```rust
+/// let input = b"Sun Nov  6 08:49:37 1994\r\nServer: example";
+/// assert_eq!(Ok((&b"\r\nServer: example"[..], 784111777)), date_header::parse_prefix(input));
+/// ```
+pub fn parse_prefix(input: &[u8]) -> Result<(&[u8], u64), InvalidDate> {
+    // The fourth byte tells the formats apart, and an RFC850 date is as long as its weekday plus 24
+    let len = match input.get(3) {
+        Some(b',') => FORMATTED_LEN,
+        Some(b' ') => 24,
+        _ => input.iter().take(10).position(|&byte| byte == b',').map_or(0, |comma| comma + 24),
+    };
+
+    if input.len() < len {
+        return Err(invalid());
+    }
+
+    let (date, rest) = input.split_at(len);
+    parse(date).map(|timestamp| (rest, timestamp))
+}
+
+
+/// Rewrite an HTTP date header in any of the formats accepted by [parse] into a canonical IMF-fixdate.
+///
+/// Proxies must forward dates as IMF-fixdate. Input that is already an IMF-fixdate is validated
+/// and copied directly, without being reformatted.
+///
+/// ```rust
+/// let mut header = [0u8; 29];
+/// assert_eq!(Ok(()), date_header::normalize(b"Friday, 15-May-15 15:34:21 GMT", &mut header));
+/// assert_eq!(&header, b"Fri, 15 May 2015 15:34:21 GMT");
+/// ```
+pub fn normalize(input: &[u8], out: &mut [u8; FORMATTED_LEN]) -> Result<(), InvalidDate> {
+    let timestamp = parse(input)?;
+
+    // The other formats are never exactly as long as an IMF-fixdate
+    if input.len() == FORMATTED_LEN {
+        out.copy_from_slice(input);
+    } else {
+        format(timestamp, out).expect("parsed dates are always in the formattable range");
+    }
+
+    Ok(())
+}
+
+
+/// Error returned from [parse] indicating that the input text was not valid.
+#[derive(Debug, Eq, PartialEq)]
+pub struct InvalidDate;
+
 
+// Every parse failure goes through here, so the compiler keeps the error paths out of the way of valid input
+#[cold]
+fn invalid() -> InvalidDate {
+    InvalidDate
+}
+
+
+
+
+// Parse and validate a header, returning both its components and its timestamp
+#[inline]
+fn parse_date(header: &[u8]) -> Result<(HttpDate, u64), InvalidDate> {
+    let (date, days) = parse_components(header)?;
+    Ok((date, date.sec as u64 + date.min as u64 * 60 + date.hour as u64 * 3600 + days as u64 * 86400))
+}
+
+
+// Parse and validate a header's components, returning them with the days since the epoch of its date
+#[inline]
+fn parse_components(header: &[u8]) -> Result<(HttpDate, u32), InvalidDate> {
+    // Each format has its own lengths, so only one grammar ever needs to be tried.
+    // RFC850 spells out the weekday, from `Sunday` (30 bytes) through `Wednesday` (33 bytes).
+    let (date, weekday) = if let Ok(s) = <&[u8; 29]>::try_from(header) {
+        parse_imf_fixdate(s)
+    } else if let Ok(s) = <&[u8; 24]>::try_from(header) {
+        parse_asctime(s)
+    } else if (30..=33).contains(&header.len()) {
+        parse_rfc850_date(header)
+    } else {
+        Err(invalid())
+    }?;
+
+    validate(date, weekday).map(|days| (date, days))
+}
+
+
+// Range-check the parsed fields and the weekday, returning the date's days since the epoch
+#[inline]
+fn validate(date: HttpDate, weekday: u8) -> Result<u32, InvalidDate> {
     let is_valid =
         date.sec < 60
         && date.min < 60
@@ -176,120 +424,76 @@ pub fn parse(header: &[u8]) -> Result<u64, InvalidDate> {
         && date.year >= 1970
         && date.year <= 9999;
 
-    if !is_valid {
-        return Err(InvalidDate);
-    }
-
-    let leap_years = ((date.year - 1) - 1968) / 4 - ((date.year - 1) - 1900) / 100 + ((date.year - 1) - 1600) / 400;
-
-    let mut ydays = match date.mon {
-        1 => 0,
-        2 => 31,
-        3 => 59,
-        4 => 90,
-        5 => 120,
-        6 => 151,
-        7 => 181,
-        8 => 212,
-        9 => 243,
-        10 => 273,
-        11 => 304,
-        12 => 334,
-        _ => unreachable!(),
-    };
-    ydays += date.day as u64;
-    ydays -= 1;
-
-    let is_leap_year = date.year % 4 == 0 && (date.year % 100 != 0 || date.year % 400 == 0);
-    if is_leap_year && date.mon > 2 {
-        ydays += 1;
+    // A day past the end of its month would otherwise roll over into the next month
+    if !is_valid || date.day > days_in_month(date.year, date.mon).unwrap_or(0) {
+        return Err(invalid());
     }
 
-    let days = (date.year as u64 - 1970) * 365 + leap_years as u64 + ydays;
-
-    let timestamp = date.sec as u64 + date.min as u64 * 60 + date.hour as u64 * 3600 + days * 86400;
+    let days = civil_to_days(date.year, date.mon, date.day);
 
-    let expected_weekday = ((timestamp / 86400 + 4) % 7) as u8;
-
-    if expected_weekday != date.weekday {
-        Err(InvalidDate)
-    } else {
-        Ok(timestamp)
+    if Weekday::from_days(days).number_from_sunday() != weekday {
+        return Err(invalid());
     }
+
+    Ok(days)
 }
 
 
-/// Error returned from [parse] indicating that the input text was not valid.
-#[derive(Debug, Eq, PartialEq)]
-pub struct InvalidDate;
 
 
+#[cfg(all(feature = "simd", target_feature = "sse2"))]
+use simd::parse_imf_fixdate;
 
 
 // Example: `Sun, 06 Nov 1994 08:49:37 GMT`
-fn parse_imf_fixdate(s: &[u8]) -> Result<HttpDate, InvalidDate> {
-    if s.len() != 29 || &s[25..] != b" GMT" || s[16] != b' ' || s[19] != b':' || s[22] != b':' {
-        return Err(InvalidDate);
+#[cfg(not(all(feature = "simd", target_feature = "sse2")))]
+#[inline]
+fn parse_imf_fixdate(s: &[u8; 29]) -> Result<(HttpDate, u8), InvalidDate> {
+    const SHAPE: Shape<29, 4> = Shape::new(b"___, 00 ___ 0000 00:00:00 GMT");
+    if !SHAPE.matches(s) {
+        return Err(invalid());
     }
 
+    let (hour, min, sec) = toint_time(field(s, 17))?;
+
+    let weekday = weekday_from_word(field(s, 0), b',')?;
+
     let date = HttpDate {
-        sec: toint_2(&s[23..25])?,
-        min: toint_2(&s[20..22])?,
-        hour: toint_2(&s[17..19])?,
-        day: toint_2(&s[5..7])?,
-        mon: match &s[7..12] {
-            b" Jan " => 1,
-            b" Feb " => 2,
-            b" Mar " => 3,
-            b" Apr " => 4,
-            b" May " => 5,
-            b" Jun " => 6,
-            b" Jul " => 7,
-            b" Aug " => 8,
-            b" Sep " => 9,
-            b" Oct " => 10,
-            b" Nov " => 11,
-            b" Dec " => 12,
-            _ => return Err(InvalidDate),
-        },
-        weekday: match &s[..5] {
-            b"Sun, " => 0,
-            b"Mon, " => 1,
-            b"Tue, " => 2,
-            b"Wed, " => 3,
-            b"Thu, " => 4,
-            b"Fri, " => 5,
-            b"Sat, " => 6,
-            _ => return Err(InvalidDate),
-        },
-        year: toint_4(&s[12..16])?,
+        sec,
+        min,
+        hour,
+        day: toint_2(field(s, 5))?,
+        mon: month_from_word(field(s, 8), b' ')?,
+        year: toint_4(field(s, 12))?,
     };
 
-    Ok(date)
+    Ok((date, weekday))
 }
 
 
 // Example: `Sunday, 06-Nov-94 08:49:37 GMT`
-fn parse_rfc850_date(s: &[u8]) -> Result<HttpDate, InvalidDate> {
-    if s.len() < 23 {
-        return Err(InvalidDate);
-    }
-
+#[inline]
+fn parse_rfc850_date(s: &[u8]) -> Result<(HttpDate, u8), InvalidDate> {
     let (s, weekday) =
-        if s.starts_with(b"Sunday, ") { (&s[8..], 0) }
-        else if s.starts_with(b"Monday, ") { (&s[8..], 1) }
-        else if s.starts_with(b"Tuesday, ") { (&s[9..], 2) }
-        else if s.starts_with(b"Wednesday, ") { (&s[11..], 3) }
-        else if s.starts_with(b"Thursday, ") { (&s[10..], 4) }
-        else if s.starts_with(b"Friday, ") { (&s[8..], 5) }
-        else if s.starts_with(b"Saturday, ") { (&s[10..], 6) }
-        else { return Err(InvalidDate); };
+        if let Some(s) = s.strip_prefix(b"Sunday, ") { (s, 0) }
+        else if let Some(s) = s.strip_prefix(b"Monday, ") { (s, 1) }
+        else if let Some(s) = s.strip_prefix(b"Tuesday, ") { (s, 2) }
+        else if let Some(s) = s.strip_prefix(b"Wednesday, ") { (s, 3) }
+        else if let Some(s) = s.strip_prefix(b"Thursday, ") { (s, 4) }
+        else if let Some(s) = s.strip_prefix(b"Friday, ") { (s, 5) }
+        else if let Some(s) = s.strip_prefix(b"Saturday, ") { (s, 6) }
+        else { return Err(invalid()); };
+
+    let Ok(s) = <&[u8; 22]>::try_from(s) else {
+        return Err(invalid());
+    };
 
-    if s.len() != 22 || s[12] != b':' || s[15] != b':' || &s[18..22] != b" GMT" {
-        return Err(InvalidDate);
+    const SHAPE: Shape<22, 3> = Shape::new(b"00-___-00 00:00:00 GMT");
+    if !SHAPE.matches(s) {
+        return Err(invalid());
     }
 
-    let mut year = u16::from(toint_2(&s[7..9])?);
+    let mut year = u16::from(toint_2(field(s, 7))?);
     if year < 70 {
         year += 2000;
     } else {
@@ -297,45 +501,35 @@ fn parse_rfc850_date(s: &[u8]) -> Result<HttpDate, InvalidDate> {
     }
 
     let date = HttpDate {
-        sec: toint_2(&s[16..18])?,
-        min: toint_2(&s[13..15])?,
-        hour: toint_2(&s[10..12])?,
-        day: toint_2(&s[0..2])?,
-        mon: match &s[2..7] {
-            b"-Jan-" => 1,
-            b"-Feb-" => 2,
-            b"-Mar-" => 3,
-            b"-Apr-" => 4,
-            b"-May-" => 5,
-            b"-Jun-" => 6,
-            b"-Jul-" => 7,
-            b"-Aug-" => 8,
-            b"-Sep-" => 9,
-            b"-Oct-" => 10,
-            b"-Nov-" => 11,
-            b"-Dec-" => 12,
-            _ => return Err(InvalidDate),
-        },
+        sec: toint_2(field(s, 16))?,
+        min: toint_2(field(s, 13))?,
+        hour: toint_2(field(s, 10))?,
+        day: toint_2(field(s, 0))?,
+        mon: month_from_word(field(s, 3), b'-')?,
         year,
-        weekday,
     };
 
-    Ok(date)
+    Ok((date, weekday))
 }
 
 
 // Example: `Sun Nov  6 08:49:37 1994`
-fn parse_asctime(s: &[u8]) -> Result<HttpDate, InvalidDate> {
-    if s.len() != 24 || s[10] != b' ' || s[13] != b':' || s[16] != b':' || s[19] != b' ' {
-        return Err(InvalidDate);
+#[inline]
+fn parse_asctime(s: &[u8; 24]) -> Result<(HttpDate, u8), InvalidDate> {
+    // The day may be padded with a space rather than a zero
+    const SHAPE: Shape<24, 3> = Shape::new(b"___ ___ _0 00:00:00 0000");
+    if !SHAPE.matches(s) {
+        return Err(invalid());
     }
 
+    let weekday = weekday_from_word(field(s, 0), b' ')?;
+
     let date = HttpDate {
-        sec: toint_2(&s[17..19])?,
-        min: toint_2(&s[14..16])?,
-        hour: toint_2(&s[11..13])?,
+        sec: toint_2(field(s, 17))?,
+        min: toint_2(field(s, 14))?,
+        hour: toint_2(field(s, 11))?,
         day: {
-            let x = &s[8..10];
+            let x: &[u8; 2] = field(s, 8);
             {
                 if x[0] == b' ' {
                     toint_1(x[1])
@@ -344,83 +538,238 @@ fn parse_asctime(s: &[u8]) -> Result<HttpDate, InvalidDate> {
                 }
             }?
         },
-        mon: match &s[4..8] {
-            b"Jan " => 1,
-            b"Feb " => 2,
-            b"Mar " => 3,
-            b"Apr " => 4,
-            b"May " => 5,
-            b"Jun " => 6,
-            b"Jul " => 7,
-            b"Aug " => 8,
-            b"Sep " => 9,
-            b"Oct " => 10,
-            b"Nov " => 11,
-            b"Dec " => 12,
-            _ => return Err(InvalidDate),
-        },
-        year: toint_4(&s[20..24])?,
-        weekday: match &s[0..4] {
-            b"Sun " => 0,
-            b"Mon " => 1,
-            b"Tue " => 2,
-            b"Wed " => 3,
-            b"Thu " => 4,
-            b"Fri " => 5,
-            b"Sat " => 6,
-            _ => return Err(InvalidDate),
-        },
+        mon: month_from_word(field(s, 4), b' ')?,
+        year: toint_4(field(s, 20))?,
     };
 
-    Ok(date)
+    Ok((date, weekday))
+}
+
+
+// A fixed-width field of a fixed-width date. Both lengths are known at compile time,
+// so the range check folds away and the parsers have no panicking paths at all.
+#[cfg(not(feature = "unsafe-fast"))]
+#[inline]
+fn field<const LEN: usize, const N: usize>(s: &[u8; LEN], start: usize) -> &[u8; N] {
+    s[start..start + N].try_into().unwrap()
+}
+
+
+// The same field without the range check, for builds where the optimizer can't be trusted to remove it.
+//
+// `start + N <= LEN` holds for every call no matter what the input bytes are. The parsers pass constant
+// starts for fields that end within their format's fixed length, and `Shape` passes offsets computed
+// when the shape is built, which end within its pattern, and so within the array of the pattern's length.
+// The input's length was already checked when it was converted to an array. Debug builds still assert the invariant.
+#[cfg(feature = "unsafe-fast")]
+#[allow(unsafe_code)]
+#[inline]
+fn field<const LEN: usize, const N: usize>(s: &[u8; LEN], start: usize) -> &[u8; N] {
+    debug_assert!(start + N <= LEN);
+
+    // SAFETY: `start..start + N` is within the array, as described above,
+    // and `[u8; N]` has the same layout and alignment as N bytes
+    unsafe { &*(s.get_unchecked(start..start + N).as_ptr() as *const [u8; N]) }
+}
+
+
+// Month and weekday names are matched as one little-endian word holding the three letter
+// name and the separator after it. XORing the expected separator into the top byte clears it,
+// so a single integer compare checks both the name and the separator. The words are built at
+// compile time from the same name tables the formatter uses.
+
+const fn name_words<const N: usize>(names: &[[u8; 3]; N]) -> [u32; N] {
+    let mut words = [0; N];
+    let mut i = 0;
+    while i < N {
+        words[i] = u32::from_le_bytes([names[i][0], names[i][1], names[i][2], 0]);
+        i += 1;
+    }
+    words
 }
 
 
-#[derive(Debug, Copy, Clone)]
-struct HttpDate {
-    sec: u8, // 0...59
-    min: u8, // 0...59
-    hour: u8, // 0...23
-    day: u8, // 1...31
-    mon: u8, // 1...12
-    year: u16, // 1970...9999
-    weekday: u8, // 0...6
+#[inline]
+fn name_from_word(s: &[u8; 4], sep: u8) -> u32 {
+    u32::from_le_bytes(*s) ^ (u32::from(sep) << 24)
 }
 
 
+// Number of a three letter month name, from 1 for January
+#[inline]
+fn month_from_word(s: &[u8; 4], sep: u8) -> Result<u8, InvalidDate> {
+    const MONTH_WORDS: [u32; 12] = name_words(&MONTH_NAMES);
+
+    let word = name_from_word(s, sep);
+    match MONTH_WORDS.iter().position(|&name| name == word) {
+        Some(index) => Ok(index as u8 + 1),
+        None => Err(invalid()),
+    }
+}
+
+
+// Number of a three letter weekday name, from 0 for Sunday
+#[inline]
+fn weekday_from_word(s: &[u8; 4], sep: u8) -> Result<u8, InvalidDate> {
+    const WEEKDAY_WORDS: [u32; 7] = name_words(&WEEKDAY_NAMES);
+
+    // The table starts from Monday
+    let word = name_from_word(s, sep);
+    match WEEKDAY_WORDS.iter().position(|&name| name == word) {
+        Some(index) => Ok((index as u8 + 1) % 7),
+        None => Err(invalid()),
+    }
+}
+
+
+#[inline]
 fn toint_1(x: u8) -> Result<u8, InvalidDate> {
     let result = x.wrapping_sub(b'0');
     if result < 10 {
         Ok(result)
     } else {
-        Err(InvalidDate)
+        Err(invalid())
     }
 }
 
 
-fn toint_2(s: &[u8]) -> Result<u8, InvalidDate> {
-    let high = s[0].wrapping_sub(b'0');
-    let low = s[1].wrapping_sub(b'0');
+// The punctuation and digit positions of a fixed-width date, checked eight bytes at a time.
+//
+// This rejects most garbage in a handful of instructions, before any field is parsed.
+// In a pattern, `0` is any digit, `_` is anything (names are checked by the parser),
+// and every other byte must match exactly. A shape only matches arrays the length of its pattern.
+struct Shape<const LEN: usize, const WORDS: usize> {
+    offsets: [usize; WORDS],
+    literal_mask: [u64; WORDS],
+    literals: [u64; WORDS],
+    digit_mask: [u64; WORDS],
+}
 
-    if high < 10 && low < 10 {
-        Ok(high * 10 + low)
-    } else {
-        Err(InvalidDate)
+impl<const LEN: usize, const WORDS: usize> Shape<LEN, WORDS> {
+    // Words cover the pattern from the start, with the last one ending exactly at its end
+    const fn new(pattern: &[u8; LEN]) -> Shape<LEN, WORDS> {
+        assert!(LEN >= 8 && WORDS == LEN.div_ceil(8));
+
+        let mut shape = Shape {
+            offsets: [0; WORDS],
+            literal_mask: [0; WORDS],
+            literals: [0; WORDS],
+            digit_mask: [0; WORDS],
+        };
+
+        let mut word = 0;
+        while word < WORDS {
+            let offset = if word == WORDS - 1 { LEN - 8 } else { word * 8 };
+            shape.offsets[word] = offset;
+
+            let mut byte = 0;
+            while byte < 8 {
+                let shift = byte * 8;
+                match pattern[offset + byte] {
+                    b'_' => (),
+                    b'0' => shape.digit_mask[word] |= 0xFF << shift,
+                    literal => {
+                        shape.literal_mask[word] |= 0xFF << shift;
+                        shape.literals[word] |= (literal as u64) << shift;
+                    }
+                }
+                byte += 1;
+            }
+
+            word += 1;
+        }
+
+        shape
+    }
+
+    #[inline]
+    fn matches(&self, s: &[u8; LEN]) -> bool {
+        (0..WORDS).all(|word| {
+            let bytes = u64::from_le_bytes(*field(s, self.offsets[word]));
+
+            // Bytes that aren't digit positions are replaced with zeros before the digit check
+            let digits = (bytes & self.digit_mask[word]) | (0x3030303030303030 & !self.digit_mask[word]);
+            (bytes & self.literal_mask[word] == self.literals[word]) & all_digits_8(digits)
+        })
     }
 }
 
 
-fn toint_4(s: &[u8]) -> Result<u16, InvalidDate> {
-    let a = u16::from(s[0].wrapping_sub(b'0'));
-    let b = u16::from(s[1].wrapping_sub(b'0'));
-    let c = u16::from(s[2].wrapping_sub(b'0'));
-    let d = u16::from(s[3].wrapping_sub(b'0'));
+// The fixed-width digit fields are parsed a word at a time rather than a byte at a time.
+// Every byte is checked and converted with a few integer operations on the whole word,
+// which works on every target without unsafe code or platform-specific SIMD intrinsics.
+// The `simd` feature replaces this for IMF-fixdates on x86 targets with SSE2.
 
-    if a < 10 && b < 10 && c < 10 && d < 10 {
-        Ok(a * 1000 + b * 100 + c * 10 + d)
-    } else {
-        Err(InvalidDate)
+// True if every byte of the word is an ASCII digit
+#[inline]
+const fn all_digits_8(word: u64) -> bool {
+    // A digit has a high nibble of 3, and adding 6 to it keeps the high nibble at 3
+    (word & 0xF0F0F0F0F0F0F0F0) | ((word.wrapping_add(0x0606060606060606) & 0xF0F0F0F0F0F0F0F0) >> 4) == 0x3333333333333333
+}
+
+
+#[inline]
+const fn all_digits_4(word: u32) -> bool {
+    (word & 0xF0F0F0F0) | ((word.wrapping_add(0x06060606) & 0xF0F0F0F0) >> 4) == 0x33333333
+}
+
+
+#[inline]
+const fn all_digits_2(word: u16) -> bool {
+    (word & 0xF0F0) | ((word.wrapping_add(0x0606) & 0xF0F0) >> 4) == 0x3333
+}
+
+
+// Parse `HH:MM:SS` into `(hour, min, sec)`
+#[inline]
+fn toint_time(s: &[u8; 8]) -> Result<(u8, u8, u8), InvalidDate> {
+    const COLON_MASK: u64 = u64::from_le_bytes([0, 0, 0xFF, 0, 0, 0xFF, 0, 0]);
+    const COLONS: u64 = u64::from_le_bytes([0, 0, b':', 0, 0, b':', 0, 0]);
+
+    let word = u64::from_le_bytes(*s);
+    if word & COLON_MASK != COLONS {
+        return Err(invalid());
+    }
+
+    // Swap the colons for zeros so every byte can be checked as a digit
+    let word = (word & !COLON_MASK) | (0x3030303030303030 & COLON_MASK);
+    if !all_digits_8(word) {
+        return Err(invalid());
+    }
+
+    // Each byte becomes ten times itself plus the following digit, which never carries
+    let digits = word & 0x0F0F0F0F0F0F0F0F;
+    let pairs = (digits * 10 + (digits >> 8)).to_le_bytes();
+
+    Ok((pairs[0], pairs[3], pairs[6]))
+}
+
+
+// Parse a four digit number
+#[inline]
+fn toint_4(s: &[u8; 4]) -> Result<u16, InvalidDate> {
+    let word = u32::from_le_bytes(*s);
+    if !all_digits_4(word) {
+        return Err(invalid());
+    }
+
+    let digits = word & 0x0F0F0F0F;
+    let pairs = (digits * 10 + (digits >> 8)).to_le_bytes();
+
+    Ok(u16::from(pairs[0]) * 100 + u16::from(pairs[2]))
+}
+
+
+// Parse a two digit number
+#[inline]
+fn toint_2(s: &[u8; 2]) -> Result<u8, InvalidDate> {
+    let word = u16::from_le_bytes(*s);
+    if !all_digits_2(word) {
+        return Err(invalid());
     }
+
+    let digits = (word & 0x0F0F).to_le_bytes();
+    Ok(digits[0] * 10 + digits[1])
 }
 
 
@@ -439,6 +788,7 @@ mod test {
             // Same day, different formats to parse
             (784111777, "Sunday, 06-Nov-94 08:49:37 GMT"),
             (784111777, "Sun Nov  6 08:49:37 1994"),
+            (784370977, "Wednesday, 09-Nov-94 08:49:37 GMT"), // Longest RFC850 weekday
             (784111777, "Sun, 06 Nov 1994 08:49:37 GMT"),
 
             // Random additional day to test
@@ -477,8 +827,8 @@ mod test {
         for (index, (timestamp, formatted)) in success.into_iter().enumerate() {
             assert_eq!(parse(formatted.as_bytes()), Ok(timestamp), "{formatted} parses as {timestamp}");
 
-            // Format always fromats to IMF, but the first two test cases are a different format
-            if index >= 2 {
+            // Format always fromats to IMF, but the first three test cases are a different format
+            if index >= 3 {
                 assert!(format(timestamp, &mut buffer).is_ok(), "{timestamp} formats successfully");
                 assert_eq!(&buffer, formatted.as_bytes(), "{timestamp} formats as {formatted}");
             }
@@ -486,8 +836,14 @@ mod test {
 
 
         let fail = [
+            "", // Empty
+            "Sun, 06 Nov 1994 08:49:37", // Missing the GMT suffix
+            "Wednesday, 09-Nov-94 08:49:37 GMT ", // One byte longer than any format
             "Sat, 01 Jan 10000 00:00:00", // First second that can't be represented in true IMF format
             "Wed, 31 Dec 1969 00:00:00 GMT", // day before the epoch
+            "Fri, 31 Apr 2015 00:00:00 GMT", // April 31st, whose weekday matches May 1st
+            "Thursday, 29-Feb-01 00:00:00 GMT", // Not a leap year, so this would be March 1st
+            "Fri Feb 30 00:00:00 2024", // Would be March 1st
             "Wed, 31 Dec 1969 23:59:59 GMT", // one second before the epoch
             "Sun Nov 10 08:00:00 1000", // Far too long before the epoch. Time probably didn't exist back then.
             "Sun Nov 10 08*00:00 2000", // Invalid character
@@ -528,6 +884,208 @@ mod test {
 
 
 
+    #[test]
+    fn test_digit_pairs() {
+        assert_eq!(two_digits(0).to_le_bytes(), *b"00\0\0\0\0\0\0");
+        assert_eq!(two_digits(7).to_le_bytes(), *b"07\0\0\0\0\0\0");
+        assert_eq!(two_digits(19).to_le_bytes(), *b"19\0\0\0\0\0\0");
+        assert_eq!(two_digits(42).to_le_bytes(), *b"42\0\0\0\0\0\0");
+        assert_eq!(two_digits(99).to_le_bytes(), *b"99\0\0\0\0\0\0");
+
+        for n in 0..100 {
+            let digits = two_digits(n).to_le_bytes();
+            assert_eq!(&digits[..2], format!("{:0>2}", n).as_bytes());
+        }
+    }
+
+
+    #[test]
+    fn test_shape() {
+        const SHAPE: Shape<9, 2> = Shape::new(b"0_-0000:_");
+        assert_eq!(SHAPE.offsets, [0, 1]);
+
+        assert!(SHAPE.matches(b"1x-2345:y"));
+        assert!(SHAPE.matches(b"9--0000::"));
+        assert!(!SHAPE.matches(b"ax-2345:y")); // Not a digit
+        assert!(!SHAPE.matches(b"1x-234::y"));
+        assert!(!SHAPE.matches(b"1x 2345:y")); // Wrong punctuation
+        assert!(!SHAPE.matches(b"1x-2345;y"));
+    }
+
+
+    #[test]
+    fn test_name_words() {
+        assert_eq!(month_from_word(b"Jan ", b' '), Ok(1));
+        assert_eq!(month_from_word(b"Dec-", b'-'), Ok(12));
+        assert_eq!(month_from_word(b"Dec ", b'-'), Err(InvalidDate));
+        assert_eq!(month_from_word(b"dec ", b' '), Err(InvalidDate));
+        assert_eq!(month_from_word(b"Jan\0", 0), Ok(1));
+        assert_eq!(month_from_word(b"Ja\0 ", b' '), Err(InvalidDate));
+
+        assert_eq!(weekday_from_word(b"Sun,", b','), Ok(0));
+        assert_eq!(weekday_from_word(b"Sat ", b' '), Ok(6));
+        assert_eq!(weekday_from_word(b"Sat,", b' '), Err(InvalidDate));
+        assert_eq!(weekday_from_word(b"Jan ", b' '), Err(InvalidDate));
+    }
+
+
+    #[test]
+    fn test_word_parsers() {
+        assert_eq!(toint_time(b"00:00:00"), Ok((0, 0, 0)));
+        assert_eq!(toint_time(b"08:49:37"), Ok((8, 49, 37)));
+        assert_eq!(toint_time(b"99:99:99"), Ok((99, 99, 99))); // Range checks happen later
+        assert_eq!(toint_time(b"08-49-37"), Err(InvalidDate));
+        assert_eq!(toint_time(b"08:4::37"), Err(InvalidDate));
+        assert_eq!(toint_time(b"08:49:3/"), Err(InvalidDate));
+        assert_eq!(toint_time(b"\xff8:49:37"), Err(InvalidDate));
+
+        assert_eq!(toint_4(b"1994"), Ok(1994));
+        assert_eq!(toint_4(b"9999"), Ok(9999));
+        assert_eq!(toint_4(b"0000"), Ok(0));
+        assert_eq!(toint_4(b"199:"), Err(InvalidDate));
+        assert_eq!(toint_4(b" 994"), Err(InvalidDate));
+
+        assert_eq!(toint_2(b"00"), Ok(0));
+        assert_eq!(toint_2(b"94"), Ok(94));
+        assert_eq!(toint_2(b" 6"), Err(InvalidDate));
+        assert_eq!(toint_2(b"6:"), Err(InvalidDate));
+        assert_eq!(toint_2(b"\xb6\xb6"), Err(InvalidDate));
+    }
+
+
+    #[test]
+    fn test_normalize() {
+        let mut buffer = [0u8; FORMATTED_LEN];
+
+        for input in ["Sun, 06 Nov 1994 08:49:37 GMT", "Sunday, 06-Nov-94 08:49:37 GMT", "Sun Nov  6 08:49:37 1994"] {
+            assert_eq!(normalize(input.as_bytes(), &mut buffer), Ok(()), "{input}");
+            assert_eq!(&buffer, b"Sun, 06 Nov 1994 08:49:37 GMT", "{input}");
+        }
+
+        // Invalid input leaves the buffer untouched
+        let mut buffer = [b'x'; FORMATTED_LEN];
+        assert_eq!(normalize(b"Mon, 06 Nov 1994 08:49:37 GMT", &mut buffer), Err(InvalidDate));
+        assert_eq!(normalize(b"", &mut buffer), Err(InvalidDate));
+        assert_eq!(buffer, [b'x'; FORMATTED_LEN]);
+    }
+
+
+    #[test]
+    fn test_parse_opt() {
+        let valid: &[u8] = b"Sun, 06 Nov 1994 08:49:37 GMT";
+        let invalid: &[u8] = b"Mon, 06 Nov 1994 08:49:37 GMT";
+
+        assert_eq!(parse_opt(Some(valid)), Ok(Some(784111777)));
+        assert_eq!(parse_opt(Some(invalid)), Err(InvalidDate));
+        assert_eq!(parse_opt(Some(b"")), Err(InvalidDate));
+        assert_eq!(parse_opt(None), Ok(None));
+
+        assert_eq!(parse_opt_or_ignore(Some(valid)), Some(784111777));
+        assert_eq!(parse_opt_or_ignore(Some(invalid)), None);
+        assert_eq!(parse_opt_or_ignore(Some(b"")), None);
+        assert_eq!(parse_opt_or_ignore(None), None);
+    }
+
+
+    #[test]
+    fn test_parse_prefix() {
+        let cases: [&[u8]; 3] = [b"Sun, 06 Nov 1994 08:49:37 GMT", b"Sunday, 06-Nov-94 08:49:37 GMT", b"Sun Nov  6 08:49:37 1994"];
+        for date in cases {
+            assert_eq!(parse_prefix(date), Ok((&b""[..], 784111777)));
+            assert_eq!(parse_prefix(&[date, b"\r\n"].concat()), Ok((&b"\r\n"[..], 784111777)));
+        }
+
+        // Every length of RFC850 weekday
+        let weekdays = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"];
+        for (day, weekday) in (7..).zip(weekdays) {
+            let date = format!("{weekday}, {day:02}-Nov-94 08:49:37 GMT;");
+            assert_eq!(parse_prefix(date.as_bytes()), Ok((&b";"[..], 784111777 + (day - 6) * 86400)), "{date}");
+        }
+
+        assert_eq!(parse_prefix(b"Sun, 06 Nov 1994 08:49:37"), Err(InvalidDate));
+        assert_eq!(parse_prefix(b"Sun, 06 Nov 1994 08:49:37 UTC"), Err(InvalidDate));
+        assert_eq!(parse_prefix(b"Sunday 06-Nov-94 08:49:37 GMT"), Err(InvalidDate));
+        assert_eq!(parse_prefix(b""), Err(InvalidDate));
+    }
+
+
+    #[test]
+    fn test_range_helpers() {
+        let mut buffer = [0u8; FORMATTED_LEN];
+
+        assert!(in_formattable_range(MIN));
+        assert!(in_formattable_range(MAX_FORMATTABLE));
+        assert!(!in_formattable_range(MAX_FORMATTABLE + 1));
+        assert!(!in_formattable_range(u64::MAX));
+
+        assert_eq!(clamp_to_range(MIN), MIN);
+        assert_eq!(clamp_to_range(MAX_FORMATTABLE), MAX_FORMATTABLE);
+        assert_eq!(clamp_to_range(MAX_FORMATTABLE + 1), MAX_FORMATTABLE);
+
+        assert_eq!(format(MIN, &mut buffer), Ok(()));
+        assert_eq!(format(MAX_FORMATTABLE, &mut buffer), Ok(()));
+        assert_eq!(format(MAX_FORMATTABLE + 1, &mut buffer), Err(TooFuturistic));
+        assert_eq!(format(clamp_to_range(u64::MAX), &mut buffer), Ok(()));
+    }
+
+
+
+    // Every day the crate can represent, walked with a calendar kept independently of the crate's own
+    #[test]
+    #[ignore = "Slow in debug builds. Run with `cargo test --release -- --ignored`"]
+    fn test_every_day() {
+        let (mut year, mut month, mut day) = (1970u16, 1u8, 1u8);
+        let mut buffer = [0; FORMATTED_LEN];
+
+        for days in 0..YEAR_10000 / 86400 {
+            let noon = days * 86400 + 43200;
+            format(noon, &mut buffer).unwrap();
+            assert_eq!(parse(&buffer), Ok(noon));
+
+            // Sakamoto's method, with 0 for Sunday
+            const OFFSETS: [u16; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+            let y = if month < 3 { year - 1 } else { year };
+            let weekday = (y + y / 4 - y / 100 + y / 400 + OFFSETS[usize::from(month - 1)] + u16::from(day)) % 7;
+
+            let expected = format!(
+                "{}, {:0>2} {} {} 12:00:00 GMT",
+                ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"][usize::from(weekday)],
+                day,
+                ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"][usize::from(month - 1)],
+                year,
+            );
+            assert_eq!(core::str::from_utf8(&buffer).unwrap(), expected);
+
+            let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+            let month_len = [31, if leap { 29 } else { 28 }, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31][usize::from(month - 1)];
+            day += 1;
+            if day > month_len {
+                day = 1;
+                month += 1;
+                if month > 12 {
+                    month = 1;
+                    year += 1;
+                }
+            }
+        }
+
+        assert_eq!((year, month, day), (10000, 1, 1));
+    }
+
+
+
+    fn month_number(name: &str) -> u8 {
+        MONTH_NAMES.iter().position(|month| month == name.as_bytes()).unwrap() as u8 + 1
+    }
+
+
+    // The byte-at-a-time conversion that the word-sized parsers must agree with
+    fn scalar_toint(s: &[u8]) -> Result<u16, InvalidDate> {
+        s.iter().try_fold(0, |total, &x| Ok(total * 10 + u16::from(toint_1(x)?)))
+    }
+
+
+
     proptest! {
         #[test]
         fn test_imf_parse(
@@ -546,6 +1104,12 @@ mod test {
                 .map(|text| parse(text.as_bytes()))
                 .collect();
 
+            // A day past the end of the month is never valid, whatever the weekday
+            if day > i32::from(days_in_month(year as u16, month_number(&month)).unwrap()) {
+                assert!(parse_results.iter().all(|x| x.is_err()), "{:?}", parse_results);
+                return Ok(());
+            }
+
             // Exactly one valid weekday parse
             assert_eq!(parse_results.iter().filter(|x| x.is_ok()).count(), 1, "{:?}", parse_results);
 
@@ -571,6 +1135,12 @@ mod test {
                 .map(|text| parse(text.as_bytes()))
                 .collect();
 
+            // A day past the end of the month is never valid, whatever the weekday
+            if day > i32::from(days_in_month(1900 + year as u16, month_number(&month)).unwrap()) {
+                assert!(parse_results.iter().all(|x| x.is_err()), "{:?}", parse_results);
+                return Ok(());
+            }
+
             // Exactly one valid weekday parse
             assert_eq!(parse_results.iter().filter(|x| x.is_ok()).count(), 1, "{:?}", parse_results);
 
@@ -597,6 +1167,12 @@ mod test {
                 .map(|text| parse(text.as_bytes()))
                 .collect();
 
+            // A day past the end of the month is never valid, whatever the weekday
+            if day > i32::from(days_in_month(year as u16, month_number(&month)).unwrap()) {
+                assert!(parse_results.iter().all(|x| x.is_err()), "{:?}", parse_results);
+                return Ok(());
+            }
+
             // Exactly one valid weekday parse
             assert_eq!(parse_results.iter().filter(|x| x.is_ok()).count(), 1, "{:?}", parse_results);
 
@@ -620,11 +1196,57 @@ mod test {
         }
 
 
+        #[test]
+        fn test_normalize_props(timestamp in 0..YEAR_10000) {
+            let mut expected = [0; FORMATTED_LEN];
+            format(timestamp, &mut expected).unwrap();
+
+            let mut buffer = [0; FORMATTED_LEN];
+            assert_eq!(normalize(&expected, &mut buffer), Ok(()));
+            assert_eq!(buffer, expected);
+        }
+
+
         #[test]
         fn test_invalid_bits(bits in prop::array::uniform29(0u8..)) {
             // This test assumes that the chances of actually generating a random
             // but valid bit pattern across 29 bytes is effectively impossible.
             assert!(parse(&bits).is_err());
         }
+
+
+        #[test]
+        fn test_arbitrary_lengths(bytes in prop::collection::vec(prop::sample::select(b"0123456789:, -GMTSunNovday".to_vec()), 0..40)) {
+            // Exercises every fixed-width field read, which matters most with the unsafe-fast feature
+            if let Ok(timestamp) = parse(&bytes) {
+                let mut buffer = [0; FORMATTED_LEN];
+                normalize(&bytes, &mut buffer).unwrap();
+                assert_eq!(parse(&buffer), Ok(timestamp));
+            }
+        }
+
+
+        #[test]
+        fn test_toint_time_matches_scalar(time in prop::array::uniform8(prop::sample::select(b"0123456789:/ ;".to_vec()))) {
+            let expected = if time[2] == b':' && time[5] == b':' {
+                scalar_toint(&time[0..2]).and_then(|hour| Ok((hour as u8, scalar_toint(&time[3..5])? as u8, scalar_toint(&time[6..8])? as u8)))
+            } else {
+                Err(InvalidDate)
+            };
+
+            assert_eq!(toint_time(&time), expected);
+        }
+
+
+        #[test]
+        fn test_toint_4_matches_scalar(digits in prop::array::uniform4(0u8..)) {
+            assert_eq!(toint_4(&digits), scalar_toint(&digits));
+        }
+
+
+        #[test]
+        fn test_toint_2_matches_scalar(digits in prop::array::uniform2(0u8..)) {
+            assert_eq!(toint_2(&digits).map(u16::from), scalar_toint(&digits));
+        }
     }
 }
\ No newline at end of file
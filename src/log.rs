@@ -0,0 +1,371 @@
+//! Parsing and formatting for the timestamps in web server logs.
+//!
+//! These aren't HTTP dates, but log ingestion usually ends up needing both, with the same
+//! validation. Fractions of a second are returned separately from the unix timestamp.
+
+use crate::{civil_from_days, field, in_formattable_range, invalid, month_from_word, parse, split_days, split_time, toint_2, toint_4, toint_time};
+use crate::{HttpDate, InvalidDate, Month, TooFuturistic, MONTH_NAMES};
+
+
+
+
+/// Length of a Common Log Format timestamp with its zone, as written by [format_clf]: `15/May/2015:15:34:21 +0000`
+pub const CLF_LEN: usize = 26;
+
+/// Length of the longest output of [format_clf_millis]: `15/May/2015:15:34:21.123`
+pub const CLF_MILLIS_MAX_LEN: usize = 24;
+
+
+
+
+/// Parse the timestamp of an Apache error log entry into a unix timestamp and microseconds.
+///
+/// The entry's surrounding brackets are optional, and so is the fraction, since Apache 2.2
+/// didn't write one. Otherwise this is an asctime date with six digits of microseconds
+/// after the seconds. Like asctime, the day may be padded with a zero or a space.
+///
+/// ```rust
+/// let entry = b"[Sun Nov 06 08:49:37.123456 1994]";
+/// assert_eq!(Ok((784111777, 123456)), date_header::log::parse_apache_error(entry));
+/// ```
+pub fn parse_apache_error(input: &[u8]) -> Result<(u64, u32), InvalidDate> {
+    let input = match input {
+        [b'[', inner @ .., b']'] => inner,
+        _ => input,
+    };
+
+    match input.len() {
+        24 => parse(input).map(|secs| (secs, 0)),
+        31 => {
+            // `Sun Nov 06 08:49:37.123456 1994` is an asctime date with the fraction spliced in
+            let (date, year) = input.split_at(26);
+            let (date, fraction) = date.split_at(19);
+
+            let mut asctime = [0u8; 24];
+            asctime[..19].copy_from_slice(date);
+            asctime[19..].copy_from_slice(year);
+
+            let secs = parse(&asctime)?;
+            Ok((secs, parse_fraction(fraction)?))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+
+/// Parse a Common Log Format timestamp into a unix timestamp, as written by Apache and nginx access logs.
+///
+/// The surrounding brackets are optional. The zone offset is required, and the result is converted to UTC.
+/// The local date and time must be from 1970 through 9999.
+///
+/// ```rust
+/// let entry = b"[10/Oct/2000:13:55:36 -0700]";
+/// assert_eq!(Ok(971211336), date_header::log::parse_clf(entry));
+/// ```
+pub fn parse_clf(input: &[u8]) -> Result<u64, InvalidDate> {
+    let input = strip_brackets(input);
+    if input.len() != CLF_LEN {
+        return Err(invalid());
+    }
+
+    let (time, zone) = input.split_at(20);
+    let local = parse_clf_time(time.try_into().expect("Split at its length"))?;
+
+    match zone {
+        [b' ', offset @ ..] => apply_zone(local, offset),
+        _ => Err(invalid()),
+    }
+}
+
+
+/// Parse a Common Log Format timestamp with milliseconds, as written by HAProxy and some CDNs,
+/// into a unix timestamp and milliseconds.
+///
+/// The format is `15/May/2015:15:34:21.123`, with exactly three digits of fraction. The surrounding
+/// brackets and a trailing zone offset are both optional. Without an offset the time is taken as UTC,
+/// which is what HAProxy writes when the machine's clock is set to UTC.
+///
+/// ```rust
+/// let entry = b"15/May/2015:15:34:21.123";
+/// assert_eq!(Ok((1431704061, 123)), date_header::log::parse_clf_millis(entry));
+/// ```
+pub fn parse_clf_millis(input: &[u8]) -> Result<(u64, u16), InvalidDate> {
+    let input = strip_brackets(input);
+    if input.len() != CLF_MILLIS_MAX_LEN && input.len() != CLF_MILLIS_MAX_LEN + 6 {
+        return Err(invalid());
+    }
+
+    let (time, rest) = input.split_at(20);
+    let (fraction, zone) = rest.split_at(4);
+
+    let millis = match fraction {
+        [b'.', digits @ ..] => parse_digits(digits)? as u16,
+        _ => return Err(invalid()),
+    };
+
+    let local = parse_clf_time(time.try_into().expect("Split at its length"))?;
+    let secs = match zone {
+        [] => local,
+        [b' ', offset @ ..] => apply_zone(local, offset)?,
+        _ => return Err(invalid()),
+    };
+    Ok((secs, millis))
+}
+
+
+/// Format a unix timestamp as a Common Log Format timestamp in UTC, without brackets.
+///
+/// ```rust
+/// let mut buffer = [0u8; date_header::log::CLF_LEN];
+/// date_header::log::format_clf(1431704061, &mut buffer).unwrap();
+/// assert_eq!(&buffer, b"15/May/2015:15:34:21 +0000");
+/// ```
+pub fn format_clf(secs_since_epoch: u64, buffer: &mut [u8; CLF_LEN]) -> Result<(), TooFuturistic> {
+    write_clf_time(secs_since_epoch, field_mut(buffer))?;
+    buffer[20..].copy_from_slice(b" +0000");
+    Ok(())
+}
+
+
+/// Format a unix timestamp and milliseconds as an HAProxy-style Common Log Format timestamp in UTC,
+/// returning the number of bytes written.
+///
+/// `width` is the number of digits of fraction to write, from 0 for none at all through 3 for milliseconds,
+/// and larger widths are treated as 3. Digits past the width are truncated, as are milliseconds past 999.
+///
+/// ```rust
+/// let mut buffer = [0u8; date_header::log::CLF_MILLIS_MAX_LEN];
+/// let len = date_header::log::format_clf_millis(1431704061, 123, 1, &mut buffer).unwrap();
+/// assert_eq!(&buffer[..len], b"15/May/2015:15:34:21.1");
+/// ```
+pub fn format_clf_millis(secs_since_epoch: u64, millis: u16, width: usize, buffer: &mut [u8; CLF_MILLIS_MAX_LEN]) -> Result<usize, TooFuturistic> {
+    write_clf_time(secs_since_epoch, field_mut(buffer))?;
+
+    let width = width.min(3);
+    if width == 0 {
+        return Ok(20);
+    }
+
+    let millis = millis.min(999);
+    let digits = [b'.', b'0' + (millis / 100) as u8, b'0' + (millis / 10 % 10) as u8, b'0' + (millis % 10) as u8];
+    buffer[20..21 + width].copy_from_slice(&digits[..1 + width]);
+    Ok(21 + width)
+}
+
+
+
+
+fn strip_brackets(input: &[u8]) -> &[u8] {
+    match input {
+        [b'[', inner @ .., b']'] => inner,
+        _ => input,
+    }
+}
+
+
+// Parse `15/May/2015:15:34:21` into a unix timestamp, as though it were in UTC
+fn parse_clf_time(s: &[u8; 20]) -> Result<u64, InvalidDate> {
+    if s[2] != b'/' || s[11] != b':' {
+        return Err(invalid());
+    }
+
+    let month = Month::from_number(month_from_word(field(s, 3), b'/')?).ok_or_else(invalid)?;
+    let (hour, minute, second) = toint_time(field(s, 12))?;
+
+    HttpDate::builder()
+        .year(toint_4(field(s, 7))?)
+        .month(month)
+        .day(toint_2(field(s, 0))?)
+        .hour(hour)
+        .minute(minute)
+        .second(second)
+        .build()
+        .map(HttpDate::timestamp)
+        .map_err(|_| invalid())
+}
+
+
+// Convert a local time to UTC using a zone offset of `+HHMM` or `-HHMM`
+pub(crate) fn apply_zone(local: u64, offset: &[u8]) -> Result<u64, InvalidDate> {
+    let (sign, hours, minutes) = match offset {
+        [sign @ (b'+' | b'-'), h1, h2, m1, m2] => (*sign, parse_digits(&[*h1, *h2])?, parse_digits(&[*m1, *m2])?),
+        _ => return Err(invalid()),
+    };
+
+    if hours > 23 || minutes > 59 {
+        return Err(invalid());
+    }
+
+    let offset = u64::from(hours * 3600 + minutes * 60);
+    let utc = if sign == b'+' { local.checked_sub(offset) } else { Some(local + offset) };
+
+    match utc {
+        Some(utc) if in_formattable_range(utc) => Ok(utc),
+        _ => Err(invalid()),
+    }
+}
+
+
+// Write `15/May/2015:15:34:21`
+fn write_clf_time(secs_since_epoch: u64, buffer: &mut [u8; 20]) -> Result<(), TooFuturistic> {
+    if !in_formattable_range(secs_since_epoch) {
+        return Err(TooFuturistic);
+    }
+
+    let (days, secs_of_day) = split_days(secs_since_epoch);
+    let (year, mon, mday) = civil_from_days(days);
+    let (hour, min, sec) = split_time(secs_of_day);
+
+    let digits = |n: u8| [b'0' + n / 10, b'0' + n % 10];
+    let month = &MONTH_NAMES[usize::from(mon - 1)];
+
+    buffer[0..2].copy_from_slice(&digits(mday));
+    buffer[2] = b'/';
+    buffer[3..6].copy_from_slice(month);
+    buffer[6] = b'/';
+    buffer[7..9].copy_from_slice(&digits((year / 100) as u8));
+    buffer[9..11].copy_from_slice(&digits((year % 100) as u8));
+    buffer[11] = b':';
+    buffer[12..14].copy_from_slice(&digits(hour));
+    buffer[14] = b':';
+    buffer[15..17].copy_from_slice(&digits(min));
+    buffer[17] = b':';
+    buffer[18..20].copy_from_slice(&digits(sec));
+    Ok(())
+}
+
+
+// The first 20 bytes of a buffer, where the date and time go
+fn field_mut<const LEN: usize>(buffer: &mut [u8; LEN]) -> &mut [u8; 20] {
+    (&mut buffer[..20]).try_into().expect("Every buffer holds at least the date and time")
+}
+
+
+// Parse `.uuuuuu` into microseconds
+fn parse_fraction(fraction: &[u8]) -> Result<u32, InvalidDate> {
+    match fraction {
+        [b'.', digits @ ..] if digits.len() == 6 => parse_digits(digits),
+        _ => Err(invalid()),
+    }
+}
+
+
+// Parse a short run of ASCII digits
+fn parse_digits(digits: &[u8]) -> Result<u32, InvalidDate> {
+    if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+        return Err(invalid());
+    }
+
+    Ok(digits.iter().fold(0, |value, digit| value * 10 + u32::from(digit - b'0')))
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+
+
+    #[test]
+    fn test_parse_apache_error() {
+        assert_eq!(parse_apache_error(b"[Sun Nov 06 08:49:37.123456 1994]"), Ok((784111777, 123456)));
+        assert_eq!(parse_apache_error(b"Sun Nov 06 08:49:37.123456 1994"), Ok((784111777, 123456)));
+        assert_eq!(parse_apache_error(b"[Sun Nov  6 08:49:37.000001 1994]"), Ok((784111777, 1)));
+        assert_eq!(parse_apache_error(b"[Fri Dec 31 23:59:59.999999 9999]"), Ok((253402300799, 999999)));
+
+        // Apache 2.2 entries have no fraction
+        assert_eq!(parse_apache_error(b"[Sun Nov 06 08:49:37 1994]"), Ok((784111777, 0)));
+        assert_eq!(parse_apache_error(b"Sun Nov  6 08:49:37 1994"), Ok((784111777, 0)));
+    }
+
+
+    #[test]
+    fn test_parse_apache_error_invalid() {
+        assert_eq!(parse_apache_error(b"[Mon Nov 06 08:49:37.123456 1994]"), Err(InvalidDate)); // Wrong weekday
+        assert_eq!(parse_apache_error(b"[Sun Nov 06 08:49:37,123456 1994]"), Err(InvalidDate));
+        assert_eq!(parse_apache_error(b"[Sun Nov 06 08:49:37.12345a 1994]"), Err(InvalidDate));
+        assert_eq!(parse_apache_error(b"[Sun Nov 06 08:49:37.123 1994]"), Err(InvalidDate));
+        assert_eq!(parse_apache_error(b"[Sun Nov 06 08:49:37.123456789 1994]"), Err(InvalidDate));
+        assert_eq!(parse_apache_error(b"[Sun Nov 06 08:49:37.123456  1994]"), Err(InvalidDate));
+        assert_eq!(parse_apache_error(b"[Sun Nov 06 08:49:37.123456 1994"), Err(InvalidDate));
+        assert_eq!(parse_apache_error(b"[Sun Nov 06 08:49:60.123456 1994]"), Err(InvalidDate));
+        assert_eq!(parse_apache_error(b"[]"), Err(InvalidDate));
+        assert_eq!(parse_apache_error(b""), Err(InvalidDate));
+
+        // Only asctime has the fraction spliced in
+        assert_eq!(parse_apache_error(b"[Sun, 06 Nov 1994 08:49:37 GMT]"), Err(InvalidDate));
+    }
+
+
+    #[test]
+    fn test_parse_clf() {
+        assert_eq!(parse_clf(b"[10/Oct/2000:13:55:36 -0700]"), Ok(971211336));
+        assert_eq!(parse_clf(b"10/Oct/2000:20:55:36 +0000"), Ok(971211336));
+        assert_eq!(parse_clf(b"11/Oct/2000:02:25:36 +0530"), Ok(971211336));
+        assert_eq!(parse_clf(b"01/Jan/1970:00:00:00 +0000"), Ok(0));
+        assert_eq!(parse_clf(b"31/Dec/9999:23:59:59 +0000"), Ok(253402300799));
+
+        assert_eq!(parse_clf(b"01/Jan/1970:00:00:00 +0100"), Err(InvalidDate)); // Before the epoch in UTC
+        assert_eq!(parse_clf(b"31/Dec/9999:23:59:59 -0100"), Err(InvalidDate)); // After the year 9999 in UTC
+        assert_eq!(parse_clf(b"10/Oct/2000:13:55:36"), Err(InvalidDate));
+        assert_eq!(parse_clf(b"10/Oct/2000:13:55:36 0700"), Err(InvalidDate));
+        assert_eq!(parse_clf(b"10/Oct/2000:13:55:36 -2400"), Err(InvalidDate));
+        assert_eq!(parse_clf(b"10/Oct/2000:13:55:36 -0760"), Err(InvalidDate));
+        assert_eq!(parse_clf(b"10/Oct/2000 13:55:36 -0700"), Err(InvalidDate));
+        assert_eq!(parse_clf(b"10-Oct-2000:13:55:36 -0700"), Err(InvalidDate));
+        assert_eq!(parse_clf(b"31/Sep/2000:13:55:36 -0700"), Err(InvalidDate));
+        assert_eq!(parse_clf(b"10/oct/2000:13:55:36 -0700"), Err(InvalidDate));
+        assert_eq!(parse_clf(b""), Err(InvalidDate));
+    }
+
+
+    #[test]
+    fn test_parse_clf_millis() {
+        assert_eq!(parse_clf_millis(b"15/May/2015:15:34:21.123"), Ok((1431704061, 123)));
+        assert_eq!(parse_clf_millis(b"[15/May/2015:15:34:21.000]"), Ok((1431704061, 0)));
+        assert_eq!(parse_clf_millis(b"15/May/2015:17:34:21.999 +0200"), Ok((1431704061, 999)));
+
+        assert_eq!(parse_clf_millis(b"15/May/2015:15:34:21"), Err(InvalidDate));
+        assert_eq!(parse_clf_millis(b"15/May/2015:15:34:21.12"), Err(InvalidDate));
+        assert_eq!(parse_clf_millis(b"15/May/2015:15:34:21.1234"), Err(InvalidDate));
+        assert_eq!(parse_clf_millis(b"15/May/2015:15:34:21,123"), Err(InvalidDate));
+        assert_eq!(parse_clf_millis(b"15/May/2015:15:34:21.12x"), Err(InvalidDate));
+        assert_eq!(parse_clf_millis(b"15/May/2015:15:34:21.123 +02"), Err(InvalidDate));
+    }
+
+
+    #[test]
+    fn test_format_clf() {
+        let mut buffer = [0; CLF_LEN];
+        assert_eq!(format_clf(971211336, &mut buffer), Ok(()));
+        assert_eq!(&buffer, b"10/Oct/2000:20:55:36 +0000");
+        assert_eq!(parse_clf(&buffer), Ok(971211336));
+
+        assert_eq!(format_clf(253402300799, &mut buffer), Ok(()));
+        assert_eq!(&buffer, b"31/Dec/9999:23:59:59 +0000");
+        assert_eq!(format_clf(253402300800, &mut buffer), Err(TooFuturistic));
+    }
+
+
+    #[test]
+    fn test_format_clf_millis() {
+        let mut buffer = [0; CLF_MILLIS_MAX_LEN];
+        let mut format = |millis, width| {
+            let len = format_clf_millis(1431704061, millis, width, &mut buffer).unwrap();
+            String::from_utf8(buffer[..len].to_vec()).unwrap()
+        };
+
+        assert_eq!(format(123, 3), "15/May/2015:15:34:21.123");
+        assert_eq!(format(7, 3), "15/May/2015:15:34:21.007");
+        assert_eq!(format(987, 2), "15/May/2015:15:34:21.98"); // Truncated, not rounded
+        assert_eq!(format(987, 1), "15/May/2015:15:34:21.9");
+        assert_eq!(format(987, 0), "15/May/2015:15:34:21");
+        assert_eq!(format(987, 9), "15/May/2015:15:34:21.987");
+        assert_eq!(format(4000, 3), "15/May/2015:15:34:21.999");
+
+        assert_eq!(parse_clf_millis(format(123, 3).as_bytes()), Ok((1431704061, 123)));
+        assert_eq!(format_clf_millis(253402300800, 0, 3, &mut buffer), Err(TooFuturistic));
+    }
+}
@@ -0,0 +1,51 @@
+//! Link-time proof that [format](crate::format) and [parse](crate::parse) can't panic, for the `no-panic` feature.
+//!
+//! The checked function's body runs with a guard whose destructor calls a function that doesn't exist.
+//! The guard is forgotten once the body returns, so the destructor is only reachable while unwinding
+//! from a panic. If the optimizer can prove the body never panics it deletes that path, and otherwise
+//! the build fails to link with the missing function's name as the error. Unoptimized builds
+//! keep every path, so the check is only applied when debug assertions are off.
+//!
+//! Everything `format` and `parse` call is `#[inline]` so the optimizer can see all of it from the caller.
+//! The check is only as good as the caller's optimization, so it belongs in CI rather than application builds:
+//! `cargo test --release --features no-panic --test no_panic`. The crate's own unit tests are too large for
+//! the optimizer to inline everything and don't link with it.
+
+
+
+
+#[cfg(all(feature = "no-panic", not(debug_assertions)))]
+struct Guard;
+
+#[cfg(all(feature = "no-panic", not(debug_assertions)))]
+#[allow(unsafe_code)]
+impl Drop for Guard {
+    #[inline]
+    fn drop(&mut self) {
+        extern "C" {
+            #[link_name = "\n\nERROR[date_header]: format or parse may panic, so the no-panic feature can't link\n\n"]
+            fn panicked() -> !;
+        }
+
+        // SAFETY: Never called, since this is only reachable while unwinding and the link fails if it is
+        unsafe { panicked() }
+    }
+}
+
+
+// Run the body of a function that must not panic
+#[cfg(all(feature = "no-panic", not(debug_assertions)))]
+#[inline(always)]
+pub(crate) fn checked<T>(body: impl FnOnce() -> T) -> T {
+    let guard = Guard;
+    let result = body();
+    core::mem::forget(guard);
+    result
+}
+
+
+#[cfg(not(all(feature = "no-panic", not(debug_assertions))))]
+#[inline(always)]
+pub(crate) fn checked<T>(body: impl FnOnce() -> T) -> T {
+    body()
+}
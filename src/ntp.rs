@@ -0,0 +1,167 @@
+//! Conversions between 64-bit NTP timestamps and unix timestamps.
+//!
+//! An NTP timestamp holds 32 bits of seconds since January 1st, 1900 and 32 bits of fraction.
+//! The seconds wrap every 136 years, starting era 1 on February 7th, 2036, so the era has to
+//! come from somewhere else. [from_ntp] uses the rule from
+//! [RFC4330](https://datatracker.ietf.org/doc/html/rfc4330#section-3), covering 1968 through 2104,
+//! and [from_ntp_near] picks the era closest to a known time, as
+//! [RFC5905](https://datatracker.ietf.org/doc/html/rfc5905#section-6) describes.
+//!
+//! HTTP dates only have whole seconds, so the fraction is truncated toward the past.
+//!
+//! ```rust
+//! use date_header::ntp;
+//!
+//! let timestamp = ntp::to_ntp(1431704061).unwrap();
+//! assert_eq!(timestamp, 3640692861 << 32);
+//! assert_eq!(Ok(1431704061), ntp::from_ntp(timestamp | 0x8000_0000)); // Half a second later
+//! ```
+
+use crate::{in_formattable_range, TooFuturistic, MAX_FORMATTABLE};
+
+
+
+
+/// Seconds from the start of NTP era 0, January 1st, 1900, to the unix epoch.
+pub const UNIX_EPOCH: u64 = 2208988800;
+
+/// Seconds in one NTP era.
+pub const ERA_LEN: u64 = 1 << 32;
+
+
+
+
+/// Convert an NTP timestamp into a unix timestamp, discarding the fraction.
+///
+/// Following RFC4330, seconds with the top bit set are in era 0 and the rest are in era 1,
+/// which covers January 20th, 1968 through February 26th, 2104.
+/// Fails for times before the unix epoch.
+pub fn from_ntp(timestamp: u64) -> Result<u64, OutOfRange> {
+    let secs = timestamp >> 32;
+    let era = u64::from(secs & 0x8000_0000 == 0);
+    (era * ERA_LEN + secs).checked_sub(UNIX_EPOCH).ok_or(OutOfRange)
+}
+
+
+/// Convert an NTP timestamp into the unix timestamp closest to a known unix time, discarding the fraction.
+///
+/// The result is within 68 years of `near`, so any reasonable clock reading resolves the era.
+/// Fails for times before the unix epoch or after the year 9999.
+pub fn from_ntp_near(timestamp: u64, near: u64) -> Result<u64, OutOfRange> {
+    let near = near.min(MAX_FORMATTABLE) + UNIX_EPOCH;
+    let secs = timestamp >> 32;
+
+    // Start from the same era as `near`, then move to whichever neighbouring era is closer
+    let mut candidate = (near & !(ERA_LEN - 1)) | secs;
+    if candidate > near && candidate - near > ERA_LEN / 2 {
+        candidate = candidate.checked_sub(ERA_LEN).ok_or(OutOfRange)?;
+    } else if near > candidate && near - candidate > ERA_LEN / 2 {
+        candidate += ERA_LEN;
+    }
+
+    match candidate.checked_sub(UNIX_EPOCH) {
+        Some(secs) if in_formattable_range(secs) => Ok(secs),
+        _ => Err(OutOfRange),
+    }
+}
+
+
+/// Convert a unix timestamp into an NTP timestamp with no fraction.
+///
+/// The era is not part of the timestamp, so times in different eras can give the same value.
+/// Fails for times after the year 9999.
+pub fn to_ntp(secs_since_epoch: u64) -> Result<u64, TooFuturistic> {
+    if !in_formattable_range(secs_since_epoch) {
+        return Err(TooFuturistic);
+    }
+
+    Ok(((secs_since_epoch + UNIX_EPOCH) % ERA_LEN) << 32)
+}
+
+
+/// The NTP era of a unix timestamp, which [to_ntp] leaves out.
+///
+/// ```rust
+/// assert_eq!(0, date_header::ntp::era(2085978495)); // Feb 7th, 2036 06:28:15
+/// assert_eq!(1, date_header::ntp::era(2085978496));
+/// ```
+pub const fn era(secs_since_epoch: u64) -> u64 {
+    (secs_since_epoch + UNIX_EPOCH) / ERA_LEN
+}
+
+
+/// Error returned from [from_ntp] and [from_ntp_near] indicating that the time can't be an HTTP date.
+#[derive(Debug, Eq, PartialEq)]
+pub struct OutOfRange;
+
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+
+
+    // Unix time of the start of NTP era 1
+    const ERA_1: u64 = ERA_LEN - UNIX_EPOCH;
+
+
+    #[test]
+    fn test_from_ntp() {
+        assert_eq!(from_ntp(UNIX_EPOCH << 32), Ok(0));
+        assert_eq!(from_ntp(3640692861 << 32 | 0xFFFF_FFFF), Ok(1431704061)); // Truncated, not rounded
+        assert_eq!(from_ntp(0xFFFF_FFFF << 32), Ok(ERA_1 - 1));
+
+        // Wrapping into era 1, through to the end of the RFC4330 window
+        assert_eq!(from_ntp(0), Ok(ERA_1));
+        assert_eq!(from_ntp(0x7FFF_FFFF << 32), Ok(ERA_1 + 0x7FFF_FFFF));
+
+        // January 1st, 1969
+        assert_eq!(from_ntp((UNIX_EPOCH - 31536000) << 32), Err(OutOfRange));
+        assert_eq!(from_ntp(0x8000_0000 << 32), Err(OutOfRange));
+    }
+
+
+    #[test]
+    fn test_from_ntp_near() {
+        let now = 1431704061;
+        assert_eq!(from_ntp_near(3640692861 << 32, now), Ok(now));
+        assert_eq!(from_ntp_near(0, now), Ok(ERA_1));
+        assert_eq!(from_ntp_near(UNIX_EPOCH << 32, now), Ok(0));
+
+        // Far from the RFC4330 window, near picks the right era
+        let far = 253402300799; // Fri, 31 Dec 9999 23:59:59 GMT
+        assert_eq!(from_ntp_near(to_ntp(far).unwrap(), far), Ok(far));
+        assert_eq!(from_ntp_near(to_ntp(far - 86400).unwrap(), far), Ok(far - 86400));
+        assert_eq!(from_ntp_near(to_ntp(far).unwrap(), far - ERA_LEN / 3), Ok(far));
+        assert_eq!(from_ntp_near(to_ntp(far).unwrap() + (1 << 32), far), Err(OutOfRange)); // One second too late
+
+        // A time just before the epoch doesn't wrap around to a later era, but one 66 years later is closer
+        assert_eq!(from_ntp_near((UNIX_EPOCH - 1) << 32, 0), Err(OutOfRange));
+        assert_eq!(from_ntp_near(0xFFFF_FFFF << 32, 0), Ok(ERA_1 - 1));
+        assert_eq!(from_ntp_near(0xFFFF_FFFF << 32, u64::MAX), Err(OutOfRange));
+    }
+
+
+    #[test]
+    fn test_to_ntp() {
+        assert_eq!(to_ntp(0), Ok(UNIX_EPOCH << 32));
+        assert_eq!(to_ntp(ERA_1 - 1), Ok(0xFFFF_FFFF << 32));
+        assert_eq!(to_ntp(ERA_1), Ok(0));
+        assert_eq!(to_ntp(MAX_FORMATTABLE + 1), Err(TooFuturistic));
+
+        for secs in [0, 1431704061, ERA_1 - 1, ERA_1, ERA_1 + 0x7FFF_FFFF] {
+            assert_eq!(from_ntp(to_ntp(secs).unwrap()), Ok(secs));
+        }
+    }
+
+
+    #[test]
+    fn test_era() {
+        assert_eq!(era(0), 0);
+        assert_eq!(era(ERA_1 - 1), 0);
+        assert_eq!(era(ERA_1), 1);
+        assert_eq!(era(MAX_FORMATTABLE), 59);
+    }
+}
@@ -0,0 +1,62 @@
+//! Formatting into the two obsolete formats, which [parse](crate::parse) accepts but [format](crate::format) never produces.
+//!
+//! Only the generators of test and load data need these, so they allocate rather than writing into a buffer.
+
+use std::string::String;
+use crate::{HttpDate, Month, Weekday};
+
+
+
+
+/// Jan 1st, 2070, where two digit RFC 850 years wrap back around to 1970
+pub(crate) const RFC850_END: u64 = 3155760000;
+
+
+/// Format a date like `Sunday, 06-Nov-94 08:49:37 GMT`, which is only unambiguous before [RFC850_END].
+pub(crate) fn format_rfc850(date: HttpDate) -> String {
+    std::format!(
+        "{}, {:02}-{}-{:02} {:02}:{:02}:{:02} GMT",
+        long_weekday_name(date.weekday()),
+        date.day(),
+        month_name(date),
+        date.year() % 100,
+        date.hour(),
+        date.minute(),
+        date.second(),
+    )
+}
+
+
+/// Format a date like `Sun Nov  6 08:49:37 1994`.
+pub(crate) fn format_asctime(date: HttpDate) -> String {
+    std::format!(
+        "{} {} {:>2} {:02}:{:02}:{:02} {}",
+        date.weekday().short_name(),
+        month_name(date),
+        date.day(),
+        date.hour(),
+        date.minute(),
+        date.second(),
+        date.year(),
+    )
+}
+
+
+
+
+fn long_weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Monday => "Monday",
+        Weekday::Tuesday => "Tuesday",
+        Weekday::Wednesday => "Wednesday",
+        Weekday::Thursday => "Thursday",
+        Weekday::Friday => "Friday",
+        Weekday::Saturday => "Saturday",
+        Weekday::Sunday => "Sunday",
+    }
+}
+
+
+fn month_name(date: HttpDate) -> &'static str {
+    Month::from_number(date.month()).expect("HttpDate always has a valid month").short_name()
+}
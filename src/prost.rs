@@ -0,0 +1,122 @@
+//! Conversions between [prost_types::Timestamp](::prost_types::Timestamp) and unix timestamps,
+//! for gateways translating date headers into protobuf messages.
+//!
+//! HTTP dates only have whole seconds, so converting from protobuf truncates the nanoseconds.
+//! A protobuf timestamp always counts its nanoseconds forward from `seconds`, even before the
+//! epoch, so truncation always rounds toward the past. That's the right direction for
+//! `Last-Modified` and other validators, which must never claim to be newer than they are.
+//!
+//! ```rust
+//! let proto = prost_types::Timestamp { seconds: 1431704061, nanos: 700_000_000 };
+//! assert_eq!(Ok(1431704061), date_header::prost::from_proto(&proto));
+//!
+//! let proto = date_header::prost::to_proto(1431704061).unwrap();
+//! assert_eq!(prost_types::Timestamp { seconds: 1431704061, nanos: 0 }, proto);
+//! ```
+
+use ::prost_types::Timestamp as ProtoTimestamp;
+use crate::{in_formattable_range, Timestamp, TooFuturistic};
+
+
+
+
+/// Convert a protobuf timestamp into a unix timestamp, discarding the nanoseconds.
+///
+/// Fails for times before the unix epoch or after the year 9999,
+/// and for timestamps whose `nanos` are outside `0..1_000_000_000` as protobuf requires.
+pub fn from_proto(timestamp: &ProtoTimestamp) -> Result<u64, OutOfRange> {
+    if !(0..1_000_000_000).contains(&timestamp.nanos) {
+        return Err(OutOfRange);
+    }
+
+    match u64::try_from(timestamp.seconds) {
+        Ok(secs) if in_formattable_range(secs) => Ok(secs),
+        _ => Err(OutOfRange),
+    }
+}
+
+
+/// Convert a unix timestamp into a protobuf timestamp with no nanoseconds.
+///
+/// Fails for times after the year 9999, which is also the end of the protobuf timestamp range.
+pub fn to_proto(secs_since_epoch: u64) -> Result<ProtoTimestamp, TooFuturistic> {
+    if !in_formattable_range(secs_since_epoch) {
+        return Err(TooFuturistic);
+    }
+
+    Ok(ProtoTimestamp {
+        seconds: secs_since_epoch as i64,
+        nanos: 0,
+    })
+}
+
+
+/// Error returned from [from_proto] indicating that the protobuf timestamp can't be an HTTP date.
+#[derive(Debug, Eq, PartialEq)]
+pub struct OutOfRange;
+
+
+
+
+impl TryFrom<ProtoTimestamp> for Timestamp {
+    type Error = OutOfRange;
+
+    fn try_from(timestamp: ProtoTimestamp) -> Result<Timestamp, OutOfRange> {
+        from_proto(&timestamp).map(|secs| Timestamp::new(secs).expect("from_proto is always in the formattable range"))
+    }
+}
+
+impl From<Timestamp> for ProtoTimestamp {
+    fn from(timestamp: Timestamp) -> ProtoTimestamp {
+        to_proto(timestamp.as_secs()).expect("Timestamp is always in the formattable range")
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::MAX_FORMATTABLE;
+    use super::*;
+
+
+
+    fn proto(seconds: i64, nanos: i32) -> ProtoTimestamp {
+        ProtoTimestamp { seconds, nanos }
+    }
+
+
+    #[test]
+    fn test_from_proto() {
+        assert_eq!(from_proto(&proto(0, 0)), Ok(0));
+        assert_eq!(from_proto(&proto(784111777, 0)), Ok(784111777));
+        assert_eq!(from_proto(&proto(784111777, 999_999_999)), Ok(784111777)); // Truncated, not rounded
+        assert_eq!(from_proto(&proto(MAX_FORMATTABLE as i64, 999_999_999)), Ok(MAX_FORMATTABLE));
+
+        assert_eq!(from_proto(&proto(-1, 999_999_999)), Err(OutOfRange)); // Still before the epoch
+        assert_eq!(from_proto(&proto(i64::MIN, 0)), Err(OutOfRange));
+        assert_eq!(from_proto(&proto(MAX_FORMATTABLE as i64 + 1, 0)), Err(OutOfRange));
+        assert_eq!(from_proto(&proto(i64::MAX, 0)), Err(OutOfRange));
+        assert_eq!(from_proto(&proto(784111777, -1)), Err(OutOfRange));
+        assert_eq!(from_proto(&proto(784111777, 1_000_000_000)), Err(OutOfRange));
+    }
+
+
+    #[test]
+    fn test_to_proto() {
+        assert_eq!(to_proto(0), Ok(proto(0, 0)));
+        assert_eq!(to_proto(784111777), Ok(proto(784111777, 0)));
+        assert_eq!(to_proto(MAX_FORMATTABLE), Ok(proto(MAX_FORMATTABLE as i64, 0)));
+        assert_eq!(to_proto(MAX_FORMATTABLE + 1), Err(TooFuturistic));
+        assert_eq!(to_proto(u64::MAX), Err(TooFuturistic));
+    }
+
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Timestamp::try_from(proto(784111777, 500_000_000)), Ok(Timestamp::new(784111777).unwrap()));
+        assert_eq!(Timestamp::try_from(proto(-1, 0)), Err(OutOfRange));
+        assert_eq!(ProtoTimestamp::from(Timestamp::MAX), proto(MAX_FORMATTABLE as i64, 0));
+    }
+}
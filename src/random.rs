@@ -0,0 +1,86 @@
+//! Uniformly random valid dates from [rand](::rand), for load testing with realistic `If-Modified-Since`
+//! and similar values without pulling in a test framework.
+//!
+//! ```rust
+//! use rand::{rngs::SmallRng, SeedableRng};
+//! use date_header::{random::random_header, Format};
+//!
+//! let mut rng = SmallRng::seed_from_u64(1994);
+//! let header = random_header(&mut rng, Format::Asctime);
+//! assert!(date_header::parse(&header).is_ok());
+//! ```
+
+use std::vec::Vec;
+use ::rand::Rng;
+use crate::obsolete::{format_asctime, format_rfc850, RFC850_END};
+use crate::{format, Format, HttpDate, FORMATTED_LEN, MAX_FORMATTABLE};
+
+
+
+
+/// A uniformly random unix timestamp that can be formatted, from the epoch through the end of the year 9999.
+pub fn random_timestamp<R: Rng + ?Sized>(rng: &mut R) -> u64 {
+    rng.random_range(0..=MAX_FORMATTABLE)
+}
+
+
+/// A uniformly random valid header in the given format.
+///
+/// RFC 850 dates only have two digit years, so they are limited to 1970 through 2069.
+pub fn random_header<R: Rng + ?Sized>(rng: &mut R, format_as: Format) -> Vec<u8> {
+    match format_as {
+        Format::ImfFixdate => {
+            let mut buffer = [0; FORMATTED_LEN];
+            format(random_timestamp(rng), &mut buffer).expect("random_timestamp is always formattable");
+            buffer.to_vec()
+        }
+        Format::Rfc850 => {
+            let date = HttpDate::from_timestamp(rng.random_range(0..RFC850_END)).expect("Before the year 9999");
+            format_rfc850(date).into_bytes()
+        }
+        Format::Asctime => {
+            let date = HttpDate::from_timestamp(random_timestamp(rng)).expect("random_timestamp is always formattable");
+            format_asctime(date).into_bytes()
+        }
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use ::rand::{rngs::SmallRng, SeedableRng};
+    use crate::parse;
+    use super::*;
+
+
+
+    #[test]
+    fn test_random_timestamp() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let timestamps: Vec<u64> = (0..1000).map(|_| random_timestamp(&mut rng)).collect();
+        assert!(timestamps.iter().all(|&timestamp| timestamp <= MAX_FORMATTABLE));
+
+        // Uniform over the whole range, so most land after the year 5000
+        assert!(timestamps.iter().filter(|&&timestamp| timestamp > MAX_FORMATTABLE / 2).count() > 400);
+    }
+
+
+    #[test]
+    fn test_random_header_parses() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let imf = random_header(&mut rng, Format::ImfFixdate);
+            assert_eq!(imf.len(), FORMATTED_LEN);
+            assert!(parse(&imf).is_ok(), "{:?}", String::from_utf8_lossy(&imf));
+
+            let rfc850 = random_header(&mut rng, Format::Rfc850);
+            assert!(parse(&rfc850).is_ok_and(|timestamp| timestamp < RFC850_END), "{:?}", String::from_utf8_lossy(&rfc850));
+
+            let asctime = random_header(&mut rng, Format::Asctime);
+            assert_eq!(asctime.len(), 24);
+            assert!(parse(&asctime).is_ok(), "{:?}", String::from_utf8_lossy(&asctime));
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{format, parse, InvalidDate, TooFuturistic};
+
+
+
+
+/// An inclusive window of validity between two unix timestamps.
+///
+/// ```rust
+/// use date_header::DateRange;
+///
+/// let range = DateRange::parse(b"Fri, 15 May 2015 00:00:00 GMT", b"Sat, 16 May 2015 00:00:00 GMT").unwrap();
+/// assert!(range.contains(1431704061));
+/// assert_eq!(Some(30339), range.remaining(1431704061));
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct DateRange {
+    /// The first second of the window.
+    pub not_before: u64,
+    /// The last second of the window.
+    pub not_after: u64,
+}
+
+impl DateRange {
+    /// Parse both endpoints from HTTP date headers in any of the formats accepted by [parse].
+    pub fn parse(not_before: &[u8], not_after: &[u8]) -> Result<DateRange, InvalidDate> {
+        Ok(DateRange {
+            not_before: parse(not_before)?,
+            not_after: parse(not_after)?,
+        })
+    }
+
+    /// Format both endpoints as IMF-fixdates, as [format] does.
+    pub fn format(self, not_before: &mut [u8; 29], not_after: &mut [u8; 29]) -> Result<(), TooFuturistic> {
+        format(self.not_before, not_before)?;
+        format(self.not_after, not_after)
+    }
+
+    /// Check whether the window contains `now`.
+    pub fn contains(self, now: u64) -> bool {
+        self.not_before <= now && now <= self.not_after
+    }
+
+    /// Number of seconds from `now` until the end of the window, or `None` if the window doesn't contain `now`.
+    pub fn remaining(self, now: u64) -> Option<u64> {
+        if self.contains(now) {
+            Some(self.not_after - now)
+        } else {
+            None
+        }
+    }
+
+    /// The window contained in both ranges, or `None` if they don't overlap.
+    pub fn intersection(self, other: DateRange) -> Option<DateRange> {
+        let range = DateRange {
+            not_before: self.not_before.max(other.not_before),
+            not_after: self.not_after.min(other.not_after),
+        };
+
+        if range.not_before <= range.not_after {
+            Some(range)
+        } else {
+            None
+        }
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+
+
+    #[test]
+    fn test_date_range() {
+        let range = DateRange { not_before: 100, not_after: 200 };
+
+        assert!(!range.contains(99));
+        assert!(range.contains(100));
+        assert!(range.contains(200));
+        assert!(!range.contains(201));
+
+        assert_eq!(range.remaining(99), None);
+        assert_eq!(range.remaining(100), Some(100));
+        assert_eq!(range.remaining(200), Some(0));
+        assert_eq!(range.remaining(201), None);
+
+        // Empty ranges contain nothing
+        let empty = DateRange { not_before: 200, not_after: 100 };
+        assert!(!empty.contains(150));
+        assert_eq!(empty.remaining(150), None);
+    }
+
+
+    #[test]
+    fn test_intersection() {
+        let range = DateRange { not_before: 100, not_after: 200 };
+
+        assert_eq!(range.intersection(range), Some(range));
+        assert_eq!(range.intersection(DateRange { not_before: 150, not_after: 250 }), Some(DateRange { not_before: 150, not_after: 200 }));
+        assert_eq!(range.intersection(DateRange { not_before: 0, not_after: 150 }), Some(DateRange { not_before: 100, not_after: 150 }));
+        assert_eq!(range.intersection(DateRange { not_before: 120, not_after: 130 }), Some(DateRange { not_before: 120, not_after: 130 }));
+        assert_eq!(range.intersection(DateRange { not_before: 200, not_after: 300 }), Some(DateRange { not_before: 200, not_after: 200 })); // Single second
+        assert_eq!(range.intersection(DateRange { not_before: 201, not_after: 300 }), None);
+        assert_eq!(range.intersection(DateRange { not_before: 0, not_after: 99 }), None);
+    }
+
+
+    #[test]
+    fn test_parse_format() {
+        let range = DateRange::parse(b"Sun, 06 Nov 1994 08:49:37 GMT", b"Sun Nov  6 08:49:38 1994").unwrap();
+        assert_eq!(range, DateRange { not_before: 784111777, not_after: 784111778 });
+        assert_eq!(DateRange::parse(b"0", b"Sun Nov  6 08:49:38 1994"), Err(InvalidDate));
+        assert_eq!(DateRange::parse(b"Sun Nov  6 08:49:38 1994", b"0"), Err(InvalidDate));
+
+        let mut not_before = [0u8; 29];
+        let mut not_after = [0u8; 29];
+        assert_eq!(range.format(&mut not_before, &mut not_after), Ok(()));
+        assert_eq!(&not_before, b"Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(&not_after, b"Sun, 06 Nov 1994 08:49:38 GMT");
+
+        let unbounded = DateRange { not_before: 0, not_after: u64::MAX };
+        assert_eq!(unbounded.format(&mut not_before, &mut not_after), Err(TooFuturistic));
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{delta_seconds, parse, InvalidDate, YEAR_1971};
+
+
+
+
+/// Parse the reset time of a rate limit header into a unix timestamp.
+///
+/// APIs report the reset time in `X-RateLimit-Reset`, `RateLimit-Reset`, and similar headers in several ways,
+/// all of which are accepted:
+///
+/// * An HTTP date in any of the formats accepted by [parse]
+/// * Unix epoch seconds, such as `1431704061`
+/// * Seconds relative to `now`, such as `60`
+///
+/// A number is treated as epoch seconds if it is at least one year's worth of seconds,
+/// since a reset delay that long is implausible and an epoch timestamp that early is impossible.
+/// Fractional seconds are rounded up so that a client never retries too early.
+/// Surrounding whitespace is ignored, and relative values saturate rather than overflow.
+///
+/// ```rust
+/// let now = 1431704061;
+/// assert_eq!(Ok(now + 60), date_header::parse_rate_limit_reset(b"60", now));
+/// assert_eq!(Ok(now + 2), date_header::parse_rate_limit_reset(b"1.5", now));
+/// assert_eq!(Ok(1431704100), date_header::parse_rate_limit_reset(b"1431704100", now));
+/// assert_eq!(Ok(1431704100), date_header::parse_rate_limit_reset(b"Fri, 15 May 2015 15:35:00 GMT", now));
+/// ```
+pub fn parse_rate_limit_reset(value: &[u8], now: u64) -> Result<u64, InvalidDate> {
+    let value = value.trim_ascii();
+
+    if let Ok(date) = parse(value) {
+        return Ok(date);
+    }
+
+    let (whole, fraction) = match value.iter().position(|&byte| byte == b'.') {
+        Some(index) if index + 1 < value.len() => (&value[..index], &value[index + 1..]),
+        Some(_) => return Err(InvalidDate),
+        None => (value, &b""[..]),
+    };
+
+    if whole.is_empty() || !whole.iter().chain(fraction).all(u8::is_ascii_digit) {
+        return Err(InvalidDate);
+    }
+
+    // Epoch timestamps can be past the delta-seconds cap, so only the saturation is shared
+    let mut seconds = delta_seconds::saturating_digits(whole);
+
+    if fraction.iter().any(|&digit| digit != b'0') {
+        seconds = seconds.saturating_add(1);
+    }
+
+    if seconds >= YEAR_1971 {
+        Ok(seconds)
+    } else {
+        Ok(now.saturating_add(seconds))
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+
+
+    #[test]
+    fn test_parse_rate_limit_reset() {
+        let now = 784111777;
+
+        let success = [
+            ("0", now),
+            ("60", now + 60),
+            (" 60\t", now + 60),
+            ("60.0", now + 60),
+            ("59.001", now + 60),
+            ("31535999", now + 31535999), // Longest relative delay
+            ("31536000", 31536000), // Shortest epoch timestamp
+            ("784111800", 784111800),
+            ("99999999999999999999999", u64::MAX), // Saturates
+            ("Sun, 06 Nov 1994 08:49:38 GMT", now + 1),
+            ("Sunday, 06-Nov-94 08:49:38 GMT", now + 1),
+        ];
+
+        for (value, expected) in success {
+            assert_eq!(parse_rate_limit_reset(value.as_bytes(), now), Ok(expected), "{value}");
+        }
+
+        assert_eq!(parse_rate_limit_reset(b"10", u64::MAX), Ok(u64::MAX)); // Relative values saturate
+
+        let fail = ["", " ", "-1", "+60", ".5", "60.", "6 0", "1e3", "60s", "0x10", "Sun, 06 Nov 1994"];
+
+        for value in fail {
+            assert_eq!(parse_rate_limit_reset(value.as_bytes(), now), Err(InvalidDate), "{value}");
+        }
+    }
+}
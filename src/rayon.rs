@@ -0,0 +1,88 @@
+//! Parallel parsing and formatting of many dates at once with [rayon](::rayon),
+//! for offline work like log ingestion and archive normalization.
+//!
+//! Results are always in the same order as the input, no matter how the work was split between threads.
+//!
+//! ```rust
+//! let headers: [&[u8]; 3] = [b"Fri, 15 May 2015 15:34:21 GMT", b"yesterday", b"Sunday, 06-Nov-94 08:49:37 GMT"];
+//! let timestamps = date_header::rayon::par_parse(&headers);
+//! assert_eq!(timestamps, [Ok(1431704061), Err(date_header::InvalidDate), Ok(784111777)]);
+//! ```
+
+use ::rayon::prelude::*;
+use crate::{format, normalize, parse, InvalidDate, TooFuturistic, FORMATTED_LEN};
+
+
+
+
+/// Parse every header in parallel, as [parse] does.
+pub fn par_parse(headers: &[&[u8]]) -> Vec<Result<u64, InvalidDate>> {
+    headers.par_iter().map(|header| parse(header)).collect()
+}
+
+
+/// Format every timestamp in parallel as an IMF-fixdate, as [format] does.
+pub fn par_format(timestamps: &[u64]) -> Vec<Result<[u8; FORMATTED_LEN], TooFuturistic>> {
+    timestamps.par_iter().map(|&timestamp| {
+        let mut buffer = [0; FORMATTED_LEN];
+        format(timestamp, &mut buffer).map(|()| buffer)
+    }).collect()
+}
+
+
+/// Rewrite every header in parallel as a canonical IMF-fixdate, as [normalize] does.
+pub fn par_normalize(headers: &[&[u8]]) -> Vec<Result<[u8; FORMATTED_LEN], InvalidDate>> {
+    headers.par_iter().map(|header| {
+        let mut buffer = [0; FORMATTED_LEN];
+        normalize(header, &mut buffer).map(|()| buffer)
+    }).collect()
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::{MAX_FORMATTABLE, TooFuturistic};
+    use super::*;
+
+
+
+    #[test]
+    fn test_par_parse() {
+        assert_eq!(par_parse(&[]), []);
+
+        // Enough headers to be split across threads
+        let headers: Vec<Vec<u8>> = (0..10_000u64).map(|i| {
+            let mut buffer = [0; FORMATTED_LEN];
+            format(i * 86401, &mut buffer).unwrap();
+            buffer.to_vec()
+        }).collect();
+        let headers: Vec<&[u8]> = headers.iter().map(Vec::as_slice).collect();
+
+        let expected: Vec<_> = (0..10_000u64).map(|i| Ok(i * 86401)).collect();
+        assert_eq!(par_parse(&headers), expected);
+    }
+
+
+    #[test]
+    fn test_par_format() {
+        let results = par_format(&[784111777, MAX_FORMATTABLE + 1, 0]);
+        assert_eq!(results, [
+            Ok(*b"Sun, 06 Nov 1994 08:49:37 GMT"),
+            Err(TooFuturistic),
+            Ok(*b"Thu, 01 Jan 1970 00:00:00 GMT"),
+        ]);
+    }
+
+
+    #[test]
+    fn test_par_normalize() {
+        let headers: [&[u8]; 3] = [b"Sun Nov  6 08:49:37 1994", b"", b"Sunday, 06-Nov-94 08:49:37 GMT"];
+        assert_eq!(par_normalize(&headers), [
+            Ok(*b"Sun, 06 Nov 1994 08:49:37 GMT"),
+            Err(InvalidDate),
+            Ok(*b"Sun, 06 Nov 1994 08:49:37 GMT"),
+        ]);
+    }
+}
@@ -0,0 +1,141 @@
+//! [rkyv](::rkyv) support, archiving dates as their unix timestamp in a little-endian `u64`.
+//!
+//! An archived `Timestamp` or `HttpDate` is just an `Archived<u64>`, so it can be compared and read
+//! in place without deserializing. Deserializing checks that the timestamp is still formattable,
+//! so a corrupted archive can't produce a date after the year 9999.
+//!
+//! ```rust
+//! use date_header::{HttpDate, Timestamp};
+//!
+//! let date = HttpDate::parse(b"Fri, 15 May 2015 15:34:21 GMT").unwrap();
+//! let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&date).unwrap();
+//!
+//! let archived = rkyv::access::<rkyv::Archived<HttpDate>, rkyv::rancor::Error>(&bytes).unwrap();
+//! assert_eq!(1431704061, archived.to_native());
+//! assert_eq!(date, rkyv::deserialize::<HttpDate, rkyv::rancor::Error>(archived).unwrap());
+//! ```
+
+use core::fmt;
+use ::rkyv::rancor::{Fallible, Source};
+use ::rkyv::primitive::ArchivedU64;
+use ::rkyv::{Archive, Deserialize, Place, Serialize};
+use crate::{HttpDate, Timestamp};
+
+
+
+
+impl Archive for Timestamp {
+    type Archived = ArchivedU64;
+    type Resolver = ();
+
+    fn resolve(&self, resolver: (), out: Place<ArchivedU64>) {
+        self.as_secs().resolve(resolver, out);
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for Timestamp {
+    fn serialize(&self, _: &mut S) -> Result<(), S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<Timestamp, D> for ArchivedU64 where D::Error: Source {
+    fn deserialize(&self, _: &mut D) -> Result<Timestamp, D::Error> {
+        Timestamp::new(self.to_native()).map_err(|_| D::Error::new(OutOfRange))
+    }
+}
+
+
+impl Archive for HttpDate {
+    type Archived = ArchivedU64;
+    type Resolver = ();
+
+    fn resolve(&self, resolver: (), out: Place<ArchivedU64>) {
+        self.timestamp().resolve(resolver, out);
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for HttpDate {
+    fn serialize(&self, _: &mut S) -> Result<(), S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<HttpDate, D> for ArchivedU64 where D::Error: Source {
+    fn deserialize(&self, deserializer: &mut D) -> Result<HttpDate, D::Error> {
+        Deserialize::<Timestamp, D>::deserialize(self, deserializer).map(HttpDate::from)
+    }
+}
+
+
+
+
+/// The error deserializing reports for an archived timestamp after the year 9999
+#[derive(Debug)]
+struct OutOfRange;
+
+impl fmt::Display for OutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("archived timestamp is after the year 9999")
+    }
+}
+
+impl core::error::Error for OutOfRange {}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use ::rkyv::rancor::Error;
+    use crate::MAX_FORMATTABLE;
+    use super::*;
+
+
+
+    #[test]
+    fn test_round_trip() {
+        for secs in [0, 784111777, MAX_FORMATTABLE] {
+            let timestamp = Timestamp::new(secs).unwrap();
+            let bytes = ::rkyv::to_bytes::<Error>(&timestamp).unwrap();
+            assert_eq!(bytes.as_slice(), secs.to_le_bytes());
+            assert_eq!(::rkyv::from_bytes::<Timestamp, Error>(&bytes).unwrap(), timestamp);
+
+            let date = HttpDate::from(timestamp);
+            let bytes = ::rkyv::to_bytes::<Error>(&date).unwrap();
+            assert_eq!(bytes.as_slice(), secs.to_le_bytes());
+            assert_eq!(::rkyv::from_bytes::<HttpDate, Error>(&bytes).unwrap(), date);
+        }
+    }
+
+
+    #[test]
+    fn test_out_of_range() {
+        let bytes = ::rkyv::to_bytes::<Error>(&(MAX_FORMATTABLE + 1)).unwrap();
+        let error = ::rkyv::from_bytes::<Timestamp, Error>(&bytes).unwrap_err();
+        assert_eq!(error.to_string(), "archived timestamp is after the year 9999");
+        assert!(::rkyv::from_bytes::<HttpDate, Error>(&bytes).is_err());
+    }
+
+
+    #[test]
+    fn test_in_a_struct() {
+        #[derive(Debug, PartialEq, ::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize)]
+        #[rkyv(crate = ::rkyv)]
+        struct Metadata {
+            etag: u32,
+            last_modified: HttpDate,
+            expires: Option<Timestamp>,
+        }
+
+        let metadata = Metadata {
+            etag: 7,
+            last_modified: HttpDate::from_timestamp(784111777).unwrap(),
+            expires: Some(Timestamp::MAX),
+        };
+        let bytes = ::rkyv::to_bytes::<Error>(&metadata).unwrap();
+        let archived = ::rkyv::access::<ArchivedMetadata, Error>(&bytes).unwrap();
+        assert_eq!(archived.last_modified, 784111777);
+        assert_eq!(::rkyv::deserialize::<Metadata, Error>(archived).unwrap(), metadata);
+    }
+}
@@ -0,0 +1,139 @@
+//! [schemars](::schemars) support, so OpenAPI and JSON Schema generation describes dates
+//! the way [serde](crate::serde) writes them to JSON: as IMF-fixdate strings.
+//!
+//! Plain `u64` fields serialized with the [serde](crate::serde) modules can pick the matching
+//! schema with `#[schemars(schema_with = ...)]`.
+//!
+//! ```rust
+//! use schemars::JsonSchema;
+//!
+//! #[derive(JsonSchema)]
+//! struct CacheEntry {
+//!     last_modified: date_header::HttpDate,
+//!     #[schemars(schema_with = "date_header::schemars::epoch_seconds")]
+//!     fetched: u64,
+//! }
+//!
+//! let schema = schemars::schema_for!(CacheEntry);
+//! let properties = &schema.as_value()["properties"];
+//! assert_eq!("#/$defs/HttpDate", properties["last_modified"]["$ref"]);
+//! assert_eq!("string", schema.as_value()["$defs"]["HttpDate"]["type"]);
+//! assert_eq!("integer", properties["fetched"]["type"]);
+//! ```
+
+use std::borrow::Cow;
+use ::schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use crate::{HttpDate, Timestamp, MAX_FORMATTABLE};
+
+
+
+
+/// Matches exactly the IMF-fixdates [format](crate::format) can produce.
+pub const IMF_FIXDATE_PATTERN: &str = "^(Mon|Tue|Wed|Thu|Fri|Sat|Sun), (0[1-9]|[12][0-9]|3[01]) (Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sep|Oct|Nov|Dec) (19[7-9][0-9]|[2-9][0-9]{3}) ([01][0-9]|2[0-3]):[0-5][0-9]:[0-5][0-9] GMT$";
+
+
+/// The schema of an IMF-fixdate string, for `#[schemars(schema_with = ...)]` on fields using
+/// [serde::imf_fixdate](crate::serde::imf_fixdate) or [serde::option_imf_fixdate](crate::serde::option_imf_fixdate).
+pub fn imf_fixdate(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "string",
+        "pattern": IMF_FIXDATE_PATTERN,
+        "description": "An HTTP date in IMF-fixdate format, as defined by RFC 9110",
+        "examples": ["Sun, 06 Nov 1994 08:49:37 GMT"],
+    })
+}
+
+
+/// The schema of a unix timestamp in seconds, for `#[schemars(schema_with = ...)]` on fields using
+/// [serde::epoch_seconds](crate::serde::epoch_seconds).
+pub fn epoch_seconds(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "integer",
+        "format": "uint64",
+        "minimum": 0,
+        "maximum": MAX_FORMATTABLE,
+        "description": "A unix timestamp in seconds, no later than the year 9999",
+    })
+}
+
+
+
+
+impl JsonSchema for Timestamp {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("HttpDate")
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed("date_header::HttpDate")
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        imf_fixdate(generator)
+    }
+}
+
+
+// The same schema as Timestamp, since they serialize identically
+impl JsonSchema for HttpDate {
+    fn schema_name() -> Cow<'static, str> {
+        Timestamp::schema_name()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Timestamp::schema_id()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        imf_fixdate(generator)
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use ::schemars::schema_for;
+    use regex::Regex;
+    use crate::*;
+    use super::*;
+
+
+
+    #[test]
+    fn test_schemas() {
+        let schema = schema_for!(Timestamp);
+        assert_eq!(schema.as_value()["type"], "string");
+        assert_eq!(schema.as_value()["pattern"], IMF_FIXDATE_PATTERN);
+        assert_eq!(schema.as_value()["title"], "HttpDate");
+        assert_eq!(schema_for!(HttpDate).as_value()["pattern"], IMF_FIXDATE_PATTERN);
+
+        let schema = epoch_seconds(&mut SchemaGenerator::default());
+        assert_eq!(schema.as_value()["type"], "integer");
+        assert_eq!(schema.as_value()["maximum"], MAX_FORMATTABLE);
+    }
+
+
+    #[test]
+    fn test_pattern() {
+        let pattern = Regex::new(IMF_FIXDATE_PATTERN).unwrap();
+        let mut buffer = [0; FORMATTED_LEN];
+        for timestamp in [0, 68169600, 784111777, 951825600, 4107542400, MAX_FORMATTABLE] {
+            format(timestamp, &mut buffer).unwrap();
+            assert!(pattern.is_match(std::str::from_utf8(&buffer).unwrap()), "{timestamp}");
+        }
+
+        for header in [
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "Sun Nov  6 08:49:37 1994",
+            "Sun, 06 Nov 1969 08:49:37 GMT",
+            "Sun, 00 Nov 1994 08:49:37 GMT",
+            "Sun, 06 Nov 1994 24:49:37 GMT",
+            "Sun, 06 Nov 1994 08:49:60 GMT",
+            "Sun, 06 Nov 1994 08:49:37 GMT ",
+        ] {
+            assert!(!pattern.is_match(header), "{header:?}");
+        }
+    }
+}
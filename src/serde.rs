@@ -0,0 +1,377 @@
+//! [serde](::serde) support, representing dates as IMF-fixdate strings like `"Sun, 06 Nov 1994 08:49:37 GMT"`,
+//! the way config files and JSON APIs mirroring HTTP semantics expect.
+//!
+//! Deserializing accepts any of the three formats [parse](crate::parse) does.
+//!
+//! Compact formats like bincode and postcard, which aren't [human-readable](::serde::Serializer::is_human_readable),
+//! get the unix timestamp as a `u64` instead, saving the 29 bytes and the parsing.
+//!
+//! `Timestamp` and `HttpDate` implement `Serialize` and `Deserialize` directly. For plain `u64` fields,
+//! the modules here pick the representation with `#[serde(with = ...)]` instead.
+//!
+//! ```rust
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct CacheEntry {
+//!     #[serde(with = "date_header::serde::imf_fixdate")]
+//!     last_modified: u64,
+//!     #[serde(with = "date_header::serde::option_imf_fixdate")]
+//!     expires: Option<u64>,
+//!     #[serde(with = "date_header::serde::epoch_seconds")]
+//!     fetched: u64,
+//! }
+//!
+//! let entry = CacheEntry { last_modified: 784111777, expires: None, fetched: 1431704061 };
+//! assert_eq!(
+//!     serde_json::to_string(&entry).unwrap(),
+//!     r#"{"last_modified":"Sun, 06 Nov 1994 08:49:37 GMT","expires":null,"fetched":1431704061}"#,
+//! );
+//! ```
+
+use core::fmt;
+use ::serde::de::{self, Deserialize, Deserializer, Unexpected, Visitor};
+use ::serde::{Serialize, Serializer};
+use crate::{HttpDate, Timestamp, FORMATTED_LEN};
+
+
+
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serialize_imf_fixdate(*self, serializer)
+        } else {
+            serializer.serialize_u64(self.as_secs())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Timestamp, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(DateVisitor)
+        } else {
+            deserializer.deserialize_u64(SecondsVisitor)
+        }
+    }
+}
+
+
+impl Serialize for HttpDate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Timestamp::from(*self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for HttpDate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<HttpDate, D::Error> {
+        Timestamp::deserialize(deserializer).map(HttpDate::from)
+    }
+}
+
+
+
+
+/// Represent a `u64` unix timestamp as an IMF-fixdate string, even in compact formats.
+pub mod imf_fixdate {
+    use ::serde::ser::Error as _;
+    use ::serde::{Deserializer, Serializer};
+    use crate::Timestamp;
+    use super::DateVisitor;
+
+
+    /// Serialize a unix timestamp as an IMF-fixdate, failing for times after the year 9999.
+    pub fn serialize<S: Serializer>(secs_since_epoch: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        let timestamp = Timestamp::new(*secs_since_epoch).map_err(|_| S::Error::custom(super::TOO_FUTURISTIC))?;
+        super::serialize_imf_fixdate(timestamp, serializer)
+    }
+
+    /// Deserialize a unix timestamp from a date in any of the three formats.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        deserializer.deserialize_str(DateVisitor).map(u64::from)
+    }
+}
+
+
+/// Represent an `Option<u64>` unix timestamp as an IMF-fixdate string or nothing, even in compact formats.
+pub mod option_imf_fixdate {
+    use core::fmt;
+    use ::serde::de::{self, Visitor};
+    use ::serde::ser::Error as _;
+    use ::serde::{Deserializer, Serialize, Serializer};
+    use crate::Timestamp;
+
+
+    /// Serialize a unix timestamp as an IMF-fixdate, failing for times after the year 9999.
+    pub fn serialize<S: Serializer>(secs_since_epoch: &Option<u64>, serializer: S) -> Result<S::Ok, S::Error> {
+        match secs_since_epoch {
+            Some(secs) => {
+                let timestamp = Timestamp::new(*secs).map_err(|_| S::Error::custom(super::TOO_FUTURISTIC))?;
+                serializer.serialize_some(&ImfFixdate(timestamp))
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize a unix timestamp from a date in any of the three formats, or nothing.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u64>, D::Error> {
+        deserializer.deserialize_option(OptionVisitor)
+    }
+
+
+    struct ImfFixdate(Timestamp);
+
+    impl Serialize for ImfFixdate {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            super::serialize_imf_fixdate(self.0, serializer)
+        }
+    }
+
+
+    struct OptionVisitor;
+
+    impl<'de> Visitor<'de> for OptionVisitor {
+        type Value = Option<u64>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("an optional HTTP date")
+        }
+
+        fn visit_none<E: de::Error>(self) -> Result<Option<u64>, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Option<u64>, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<Option<u64>, D::Error> {
+            super::imf_fixdate::deserialize(deserializer).map(Some)
+        }
+    }
+}
+
+
+/// Represent a `u64` unix timestamp as a plain number of seconds, even in human-readable formats,
+/// still limited to the year 9999.
+pub mod epoch_seconds {
+    use ::serde::ser::Error as _;
+    use ::serde::{Deserializer, Serializer};
+    use crate::in_formattable_range;
+    use super::SecondsVisitor;
+
+
+    /// Serialize a unix timestamp as a number, failing for times after the year 9999.
+    pub fn serialize<S: Serializer>(secs_since_epoch: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        if !in_formattable_range(*secs_since_epoch) {
+            return Err(S::Error::custom(super::TOO_FUTURISTIC));
+        }
+        serializer.serialize_u64(*secs_since_epoch)
+    }
+
+    /// Deserialize a unix timestamp from a number, failing for times after the year 9999.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        deserializer.deserialize_u64(SecondsVisitor).map(u64::from)
+    }
+}
+
+
+const TOO_FUTURISTIC: &str = "timestamp is after the year 9999";
+
+
+
+
+fn serialize_imf_fixdate<S: Serializer>(timestamp: Timestamp, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut buffer = [0; FORMATTED_LEN];
+    timestamp.format(&mut buffer);
+    serializer.serialize_str(core::str::from_utf8(&buffer).expect("IMF-fixdate is ASCII"))
+}
+
+
+struct DateVisitor;
+
+impl Visitor<'_> for DateVisitor {
+    type Value = Timestamp;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an HTTP date")
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<Timestamp, E> {
+        Timestamp::parse(text.as_bytes()).map_err(|_| E::invalid_value(Unexpected::Str(text), &self))
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Timestamp, E> {
+        Timestamp::parse(bytes).map_err(|_| E::invalid_value(Unexpected::Bytes(bytes), &self))
+    }
+}
+
+
+struct SecondsVisitor;
+
+impl Visitor<'_> for SecondsVisitor {
+    type Value = Timestamp;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a unix timestamp no later than the year 9999")
+    }
+
+    fn visit_u64<E: de::Error>(self, secs: u64) -> Result<Timestamp, E> {
+        Timestamp::new(secs).map_err(|_| E::invalid_value(Unexpected::Unsigned(secs), &self))
+    }
+
+    // Some formats, such as TOML, report every integer as signed
+    fn visit_i64<E: de::Error>(self, secs: i64) -> Result<Timestamp, E> {
+        match u64::try_from(secs) {
+            Ok(secs) => self.visit_u64(secs),
+            Err(_) => Err(E::invalid_value(Unexpected::Signed(secs), &self)),
+        }
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_ser_tokens_error, assert_tokens, Compact, Configure, Readable, Token};
+    use crate::*;
+
+
+
+    #[test]
+    fn test_timestamp() {
+        let timestamp = Timestamp::new(784111777).unwrap();
+        assert_tokens(&timestamp.readable(), &[Token::Str("Sun, 06 Nov 1994 08:49:37 GMT")]);
+        assert_tokens(&Timestamp::MAX.readable(), &[Token::Str("Fri, 31 Dec 9999 23:59:59 GMT")]);
+
+        // Any format is accepted, but always written back as IMF-fixdate
+        assert_de_tokens(&timestamp.readable(), &[Token::Str("Sunday, 06-Nov-94 08:49:37 GMT")]);
+        assert_de_tokens(&timestamp.readable(), &[Token::BorrowedStr("Sun Nov  6 08:49:37 1994")]);
+        assert_de_tokens(&timestamp.readable(), &[Token::Bytes(b"Sun, 06 Nov 1994 08:49:37 GMT")]);
+
+        assert_de_tokens_error::<Readable<Timestamp>>(
+            &[Token::Str("Sun, 06 Nov 1994")],
+            "invalid value: string \"Sun, 06 Nov 1994\", expected an HTTP date",
+        );
+        assert_de_tokens_error::<Readable<Timestamp>>(
+            &[Token::U64(784111777)],
+            "invalid type: integer `784111777`, expected an HTTP date",
+        );
+    }
+
+
+    #[test]
+    fn test_http_date() {
+        let date = HttpDate::from_timestamp(784111777).unwrap();
+        assert_tokens(&date.readable(), &[Token::Str("Sun, 06 Nov 1994 08:49:37 GMT")]);
+        assert_de_tokens(&date.readable(), &[Token::Str("Sun Nov  6 08:49:37 1994")]);
+        assert_de_tokens_error::<Readable<HttpDate>>(
+            &[Token::Str("Mon, 06 Nov 1994 08:49:37 GMT")],
+            "invalid value: string \"Mon, 06 Nov 1994 08:49:37 GMT\", expected an HTTP date",
+        );
+    }
+
+
+    #[test]
+    fn test_compact() {
+        let timestamp = Timestamp::new(784111777).unwrap();
+        assert_tokens(&timestamp.compact(), &[Token::U64(784111777)]);
+        assert_tokens(&timestamp.readable(), &[Token::Str("Sun, 06 Nov 1994 08:49:37 GMT")]);
+        assert_tokens(&HttpDate::from(timestamp).compact(), &[Token::U64(784111777)]);
+        assert_tokens(&Timestamp::MAX.compact(), &[Token::U64(MAX_FORMATTABLE)]);
+
+        // Formats that only have signed integers still work
+        assert_de_tokens(&timestamp.compact(), &[Token::I64(784111777)]);
+        assert_de_tokens(&Timestamp::MAX.compact(), &[Token::I64(MAX_FORMATTABLE as i64)]);
+        assert_de_tokens_error::<Compact<Timestamp>>(
+            &[Token::I64(-1)],
+            "invalid value: integer `-1`, expected a unix timestamp no later than the year 9999",
+        );
+        assert_de_tokens_error::<Compact<Timestamp>>(
+            &[Token::I64(MAX_FORMATTABLE as i64 + 1)],
+            "invalid value: integer `253402300800`, expected a unix timestamp no later than the year 9999",
+        );
+
+        assert_de_tokens_error::<Compact<Timestamp>>(
+            &[Token::U64(MAX_FORMATTABLE + 1)],
+            "invalid value: integer `253402300800`, expected a unix timestamp no later than the year 9999",
+        );
+        assert_de_tokens_error::<Compact<Timestamp>>(
+            &[Token::Str("Sun, 06 Nov 1994 08:49:37 GMT")],
+            "invalid type: string \"Sun, 06 Nov 1994 08:49:37 GMT\", expected a unix timestamp no later than the year 9999",
+        );
+    }
+
+
+    #[derive(Debug, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+    struct Fields {
+        #[serde(with = "crate::serde::imf_fixdate")]
+        imf: u64,
+        #[serde(with = "crate::serde::option_imf_fixdate")]
+        option: Option<u64>,
+        #[serde(with = "crate::serde::epoch_seconds")]
+        seconds: u64,
+    }
+
+    fn fields_tokens(option: &[Token]) -> Vec<Token> {
+        let mut tokens = vec![
+            Token::Struct { name: "Fields", len: 3 },
+            Token::Str("imf"),
+            Token::Str("Sun, 06 Nov 1994 08:49:37 GMT"),
+            Token::Str("option"),
+        ];
+        tokens.extend_from_slice(option);
+        tokens.extend([Token::Str("seconds"), Token::U64(784111777), Token::StructEnd]);
+        tokens
+    }
+
+
+    #[test]
+    fn test_with_modules() {
+        let fields = Fields { imf: 784111777, option: Some(0), seconds: 784111777 };
+        assert_tokens(&fields, &fields_tokens(&[Token::Some, Token::Str("Thu, 01 Jan 1970 00:00:00 GMT")]));
+
+        let fields = Fields { imf: 784111777, option: None, seconds: 784111777 };
+        assert_tokens(&fields, &fields_tokens(&[Token::None]));
+
+        // The modules pick the representation no matter how human-readable the format is
+        let fields = Fields { imf: 784111777, option: Some(0), seconds: 784111777 };
+        assert_tokens(&fields.compact(), &fields_tokens(&[Token::Some, Token::Str("Thu, 01 Jan 1970 00:00:00 GMT")]));
+
+        let too_futuristic = Fields { imf: MAX_FORMATTABLE + 1, option: None, seconds: 0 };
+        assert_ser_tokens_error(&too_futuristic, &[Token::Struct { name: "Fields", len: 3 }, Token::Str("imf")], "timestamp is after the year 9999");
+
+        let too_futuristic = Fields { imf: 0, option: Some(MAX_FORMATTABLE + 1), seconds: 0 };
+        assert_ser_tokens_error(&too_futuristic, &[
+            Token::Struct { name: "Fields", len: 3 },
+            Token::Str("imf"),
+            Token::Str("Thu, 01 Jan 1970 00:00:00 GMT"),
+            Token::Str("option"),
+        ], "timestamp is after the year 9999");
+
+        // As deserialized from TOML, which only has signed integers
+        assert_de_tokens(&Fields { imf: 784111777, option: None, seconds: 784111777 }, &[
+            Token::Struct { name: "Fields", len: 3 },
+            Token::Str("imf"),
+            Token::Str("Sun, 06 Nov 1994 08:49:37 GMT"),
+            Token::Str("option"),
+            Token::None,
+            Token::Str("seconds"),
+            Token::I64(784111777),
+            Token::StructEnd,
+        ]);
+
+        assert_de_tokens_error::<Fields>(
+            &[
+                Token::Struct { name: "Fields", len: 3 },
+                Token::Str("imf"),
+                Token::Str("Sun, 06 Nov 1994 08:49:37 GMT"),
+                Token::Str("option"),
+                Token::None,
+                Token::Str("seconds"),
+                Token::U64(MAX_FORMATTABLE + 1),
+            ],
+            "invalid value: integer `253402300800`, expected a unix timestamp no later than the year 9999",
+        );
+    }
+}
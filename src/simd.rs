@@ -0,0 +1,168 @@
+//! IMF-fixdate parsing with SSE2, used in place of the word-at-a-time parser when the `simd` feature
+//! is enabled on x86 targets with SSE2. Other targets always use the word-at-a-time parser.
+//!
+//! The 29 bytes are loaded as two overlapping vectors, bytes 0 through 15 and 13 through 28.
+//! One compare per vector checks the punctuation, and one subtract, min, and compare checks every digit.
+//! Each vector is then converted into the values of every pair of adjacent digits at once,
+//! and the six fields are extracted from those.
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+use crate::{field, invalid, month_from_word, weekday_from_word, HttpDate, InvalidDate};
+
+
+
+
+// Example: `Sun, 06 Nov 1994 08:49:37 GMT`
+#[inline]
+pub(crate) fn parse_imf_fixdate(s: &[u8; 29]) -> Result<(HttpDate, u8), InvalidDate> {
+    // SAFETY: this module is only compiled when SSE2 is enabled for the whole build
+    #[allow(unsafe_code)]
+    let fields = unsafe { digit_fields(s) };
+    let [day, century, year_of_century, hour, min, sec] = fields.ok_or_else(invalid)?;
+
+    let weekday = weekday_from_word(field(s, 0), b',')?;
+
+    let date = HttpDate {
+        sec,
+        min,
+        hour,
+        day,
+        mon: month_from_word(field(s, 8), b' ')?,
+        year: u16::from(century) * 100 + u16::from(year_of_century),
+    };
+
+    Ok((date, weekday))
+}
+
+
+// The punctuation and digit positions of one vector of a pattern, where `0` is any digit,
+// `_` is anything, and every other byte must match exactly
+struct Half {
+    literals: [u8; 16],
+    literal_mask: i32,
+    digit_mask: i32,
+}
+
+impl Half {
+    const fn new(pattern: &[u8; 16]) -> Half {
+        let mut half = Half { literals: [0; 16], literal_mask: 0, digit_mask: 0 };
+
+        let mut i = 0;
+        while i < 16 {
+            match pattern[i] {
+                b'_' => (),
+                b'0' => half.digit_mask |= 1 << i,
+                literal => {
+                    half.literals[i] = literal;
+                    half.literal_mask |= 1 << i;
+                }
+            }
+            i += 1;
+        }
+
+        half
+    }
+}
+
+
+// Validate the date's shape, returning its digit fields as
+// `[day, century, year of century, hour, minute, second]`
+#[target_feature(enable = "sse2")]
+#[inline]
+fn digit_fields(s: &[u8; 29]) -> Option<[u8; 6]> {
+    const LOW: Half = Half::new(b"___, 00 ___ 0000");
+    const HIGH: Half = Half::new(b"000 00:00:00 GMT");
+
+    let low = load(field(s, 0));
+    let high = load(field(s, 13));
+    let low_digits = _mm_sub_epi8(low, _mm_set1_epi8(b'0' as i8));
+    let high_digits = _mm_sub_epi8(high, _mm_set1_epi8(b'0' as i8));
+
+    if !matches(low, low_digits, &LOW) || !matches(high, high_digits, &HIGH) {
+        return None;
+    }
+
+    // Pairs starting at even positions, and at odd positions after shifting the vector down a byte
+    let low_even = pairs(low_digits);
+    let low_odd = pairs(_mm_srli_si128::<1>(low_digits));
+    let high_even = pairs(high_digits);
+    let high_odd = pairs(_mm_srli_si128::<1>(high_digits));
+
+    // Every pair is two validated digits, so it fits in a byte
+    Some([
+        _mm_extract_epi16::<2>(low_odd) as u8, // Bytes 5 and 6
+        _mm_extract_epi16::<6>(low_even) as u8, // Bytes 12 and 13
+        _mm_extract_epi16::<7>(low_even) as u8, // Bytes 14 and 15
+        _mm_extract_epi16::<2>(high_even) as u8, // Bytes 17 and 18
+        _mm_extract_epi16::<3>(high_odd) as u8, // Bytes 20 and 21
+        _mm_extract_epi16::<5>(high_even) as u8, // Bytes 23 and 24
+    ])
+}
+
+
+#[target_feature(enable = "sse2")]
+#[inline]
+fn load(bytes: &[u8; 16]) -> __m128i {
+    let low = i64::from_le_bytes(*field(bytes, 0));
+    let high = i64::from_le_bytes(*field(bytes, 8));
+    _mm_set_epi64x(high, low)
+}
+
+
+// Check the literal bytes, and that every digit position holds a value from 0 through 9 after subtracting `0`
+#[target_feature(enable = "sse2")]
+#[inline]
+fn matches(bytes: __m128i, digits: __m128i, half: &Half) -> bool {
+    let literals = _mm_movemask_epi8(_mm_cmpeq_epi8(bytes, load(&half.literals)));
+    let in_range = _mm_movemask_epi8(_mm_cmpeq_epi8(_mm_min_epu8(digits, _mm_set1_epi8(9)), digits));
+
+    (literals & half.literal_mask == half.literal_mask) & (in_range & half.digit_mask == half.digit_mask)
+}
+
+
+// Each 16-bit lane becomes ten times its first byte plus its second
+#[target_feature(enable = "sse2")]
+#[inline]
+fn pairs(digits: __m128i) -> __m128i {
+    let tens = _mm_mullo_epi16(_mm_and_si128(digits, _mm_set1_epi16(0xFF)), _mm_set1_epi16(10));
+    _mm_add_epi16(tens, _mm_srli_epi16::<8>(digits))
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+
+
+    #[test]
+    fn test_digit_fields() {
+        assert_eq!(parse_imf_fixdate(b"Sun, 06 Nov 1994 08:49:37 GMT").map(|(date, weekday)| (date.timestamp(), weekday)), Ok((784111777, 0)));
+        assert_eq!(parse_imf_fixdate(b"Fri, 31 Dec 9999 23:59:59 GMT").map(|(date, _)| date.timestamp()), Ok(253402300799));
+        assert_eq!(parse_imf_fixdate(b"Thu, 01 Jan 1970 00:00:00 GMT").map(|(date, _)| date.timestamp()), Ok(0));
+    }
+
+
+    #[test]
+    fn test_every_position() {
+        let valid = *b"Sun, 06 Nov 1994 08:49:37 GMT";
+
+        // Every byte but the names is checked here, so breaking any of them must fail
+        for position in (3..8).chain(11..29) {
+            for replacement in [b'a', b'/', b':', b' ', 0, 0xFF, b'0' - 1, b'9' + 1] {
+                let mut date = valid;
+                if date[position] == replacement || (date[position].is_ascii_digit() && replacement.is_ascii_digit()) {
+                    continue;
+                }
+
+                date[position] = replacement;
+                assert_eq!(parse_imf_fixdate(&date), Err(InvalidDate), "{}", date.escape_ascii());
+            }
+        }
+    }
+}
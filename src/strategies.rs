@@ -0,0 +1,115 @@
+//! [proptest](mod@::proptest) strategies for HTTP dates, for testing your own HTTP code with valid and almost-valid headers.
+//!
+//! ```rust
+//! use proptest::test_runner::TestRunner;
+//! use date_header::strategies::arb_http_date;
+//!
+//! TestRunner::default().run(&arb_http_date(), |header| {
+//!     assert!(date_header::parse(&header).is_ok());
+//!     Ok(())
+//! }).unwrap();
+//! ```
+
+use ::proptest::prelude::*;
+use crate::{format, HttpDate, FORMATTED_LEN, MAX_FORMATTABLE};
+use crate::obsolete::{format_asctime, format_rfc850, RFC850_END};
+
+
+
+
+/// Any unix timestamp that can be formatted, from the epoch through the end of the year 9999.
+pub fn arb_timestamp() -> impl Strategy<Value = u64> {
+    0..=MAX_FORMATTABLE
+}
+
+
+/// Any valid IMF-fixdate, like `Sun, 06 Nov 1994 08:49:37 GMT`.
+pub fn arb_imf_fixdate_bytes() -> impl Strategy<Value = Vec<u8>> {
+    arb_timestamp().prop_map(|timestamp| {
+        let mut buffer = [0; FORMATTED_LEN];
+        format(timestamp, &mut buffer).expect("arb_timestamp is always formattable");
+        buffer.to_vec()
+    })
+}
+
+
+/// Any valid RFC 850 date, like `Sunday, 06-Nov-94 08:49:37 GMT`.
+///
+/// Two digit years only cover 1970 through 2069, so the timestamps are limited to that range.
+pub fn arb_rfc850_bytes() -> impl Strategy<Value = Vec<u8>> {
+    (0..RFC850_END).prop_map(|timestamp| {
+        let date = HttpDate::from_timestamp(timestamp).expect("Before the year 9999");
+        format_rfc850(date).into_bytes()
+    })
+}
+
+
+/// Any valid asctime date, like `Sun Nov  6 08:49:37 1994`.
+pub fn arb_asctime_bytes() -> impl Strategy<Value = Vec<u8>> {
+    arb_timestamp().prop_map(|timestamp| {
+        let date = HttpDate::from_timestamp(timestamp).expect("arb_timestamp is always formattable");
+        format_asctime(date).into_bytes()
+    })
+}
+
+
+/// Any valid date in any of the three formats [parse](crate::parse) accepts.
+pub fn arb_http_date() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        arb_imf_fixdate_bytes(),
+        arb_rfc850_bytes(),
+        arb_asctime_bytes(),
+    ]
+}
+
+
+/// A valid date in any format with one byte replaced, which is usually but not always invalid.
+///
+/// These find the code that trusts a header to be well-formed just because it has the right shape.
+pub fn arb_near_http_date() -> impl Strategy<Value = Vec<u8>> {
+    (arb_http_date(), any::<prop::sample::Index>(), any::<u8>()).prop_map(|(mut header, index, byte)| {
+        let index = index.index(header.len());
+        header[index] = byte;
+        header
+    })
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use ::proptest::prelude::*;
+    use crate::{parse, MAX_FORMATTABLE};
+    use super::*;
+
+
+
+    proptest! {
+        #[test]
+        fn test_every_strategy_parses(
+            imf in arb_imf_fixdate_bytes(),
+            rfc850 in arb_rfc850_bytes(),
+            asctime in arb_asctime_bytes(),
+        ) {
+            assert_eq!(imf.len(), 29);
+            assert!(parse(&imf).is_ok(), "{:?}", String::from_utf8_lossy(&imf));
+            assert!((30..=33).contains(&rfc850.len()));
+            assert!(parse(&rfc850).is_ok(), "{:?}", String::from_utf8_lossy(&rfc850));
+            assert_eq!(asctime.len(), 24);
+            assert!(parse(&asctime).is_ok(), "{:?}", String::from_utf8_lossy(&asctime));
+        }
+
+
+        #[test]
+        fn test_http_date(header in arb_http_date()) {
+            assert!(parse(&header).unwrap() <= MAX_FORMATTABLE);
+        }
+
+
+        #[test]
+        fn test_near_http_date_never_panics(header in arb_near_http_date()) {
+            let _ = parse(&header);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{format, parse, InvalidDate, TooFuturistic};
+
+
+
+
+/// Value of a `Sunset` header, announcing when a resource is expected to become unresponsive.
+///
+/// A sunset in the past means the resource may become unresponsive at any time.
+/// See [RFC8594](https://datatracker.ietf.org/doc/html/rfc8594) for more information.
+///
+/// ```rust
+/// use date_header::Sunset;
+///
+/// let sunset = Sunset::parse(b"Sat, 31 Dec 2016 23:59:59 GMT").unwrap();
+/// assert_eq!(Some(86400), sunset.time_until_sunset(1483142399));
+/// assert_eq!(None, sunset.time_until_sunset(1483228800));
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Sunset(pub u64);
+
+impl Sunset {
+    /// Parse a `Sunset` header, which holds an HTTP date in any of the formats accepted by [parse].
+    pub fn parse(header: &[u8]) -> Result<Sunset, InvalidDate> {
+        parse(header).map(Sunset)
+    }
+
+    /// Format the sunset as an IMF-fixdate, as [format] does.
+    pub fn format(self, buffer: &mut [u8; 29]) -> Result<(), TooFuturistic> {
+        format(self.0, buffer)
+    }
+
+    /// Number of seconds from `now` until the sunset, or `None` if the sunset has passed.
+    pub fn time_until_sunset(self, now: u64) -> Option<u64> {
+        self.0.checked_sub(now).filter(|&remaining| remaining > 0)
+    }
+
+    /// Check whether the sunset has passed, meaning the resource may stop responding at any time.
+    pub fn has_passed(self, now: u64) -> bool {
+        self.0 <= now
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+
+
+    #[test]
+    fn test_sunset() {
+        let sunset = Sunset::parse(b"Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+        assert_eq!(sunset, Sunset(784111777));
+        assert_eq!(Sunset::parse(b"Sunday, 06-Nov-94 08:49:37 GMT"), Ok(sunset));
+        assert_eq!(Sunset::parse(b"soon"), Err(InvalidDate));
+
+        let mut buffer = [0u8; 29];
+        assert_eq!(sunset.format(&mut buffer), Ok(()));
+        assert_eq!(&buffer, b"Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(Sunset(u64::MAX).format(&mut buffer), Err(TooFuturistic));
+
+        assert_eq!(sunset.time_until_sunset(0), Some(784111777));
+        assert_eq!(sunset.time_until_sunset(784111776), Some(1));
+        assert_eq!(sunset.time_until_sunset(784111777), None);
+        assert_eq!(sunset.time_until_sunset(u64::MAX), None);
+
+        assert!(!sunset.has_passed(784111776));
+        assert!(sunset.has_passed(784111777));
+    }
+}
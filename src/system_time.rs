@@ -0,0 +1,183 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::{format, parse, InvalidDate, FORMATTED_LEN};
+
+
+
+
+/// How to turn the sub-second part of a [SystemTime] into whole seconds.
+///
+/// Use [Rounding::Floor] for `Last-Modified` and other validators. Rounding up could produce
+/// a validator newer than the representation it describes, or even a date in the future,
+/// and a client revalidating with `If-Modified-Since` would then miss a change made later in the same second.
+/// [Rounding::Nearest] is fine for informational fields such as `Date` where the smallest error matters most.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Rounding {
+    /// Discard the fractional second.
+    Floor,
+    /// Round to the closest second, with half a second rounding up.
+    Nearest,
+}
+
+
+/// Convert a [SystemTime] into a unix timestamp with an explicit rounding policy.
+///
+/// Times before the unix epoch become `0`. The result is not clamped to the year 9999,
+/// so [format](crate::format) will report [TooFuturistic](crate::TooFuturistic) for those.
+///
+/// ```rust
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use date_header::{from_system_time, Rounding};
+///
+/// let modified = UNIX_EPOCH + Duration::from_millis(1431704061_700);
+/// assert_eq!(1431704061, from_system_time(modified, Rounding::Floor));
+/// assert_eq!(1431704062, from_system_time(modified, Rounding::Nearest));
+/// ```
+pub fn from_system_time(time: SystemTime, rounding: Rounding) -> u64 {
+    let Ok(duration) = time.duration_since(UNIX_EPOCH) else {
+        return 0;
+    };
+
+    match rounding {
+        Rounding::Floor => duration.as_secs(),
+        Rounding::Nearest if duration.subsec_nanos() >= 500_000_000 => duration.as_secs().saturating_add(1),
+        Rounding::Nearest => duration.as_secs(),
+    }
+}
+
+
+/// Convert a unix timestamp into a [SystemTime].
+///
+/// Returns `None` if the platform's [SystemTime] can't represent the time,
+/// which for most platforms is only the case for timestamps far past the year 9999.
+///
+/// ```rust
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// assert_eq!(Some(UNIX_EPOCH + Duration::from_secs(1431704061)), date_header::to_system_time(1431704061));
+/// assert_eq!(None, date_header::to_system_time(u64::MAX));
+/// ```
+pub fn to_system_time(secs_since_epoch: u64) -> Option<SystemTime> {
+    UNIX_EPOCH.checked_add(Duration::from_secs(secs_since_epoch))
+}
+
+
+
+/// Error returned from [format_system_time] when the time can't be written as an HTTP date.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum OutOfRange {
+    /// The time was before the unix epoch.
+    BeforeEpoch,
+    /// The time was after the year 9999, like [TooFuturistic](crate::TooFuturistic).
+    TooFuturistic,
+}
+
+
+/// Format a [SystemTime] as an IMF-fixdate into the provided buffer, discarding any fractional second.
+///
+/// Unlike [from_system_time], times before the epoch are an error rather than becoming the epoch.
+///
+/// ```rust
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use date_header::{format_system_time, OutOfRange};
+///
+/// let mut header = [0u8; 29];
+/// assert_eq!(Ok(()), format_system_time(UNIX_EPOCH + Duration::from_millis(1431704061_700), &mut header));
+/// assert_eq!(&header, b"Fri, 15 May 2015 15:34:21 GMT");
+/// assert_eq!(Err(OutOfRange::BeforeEpoch), format_system_time(UNIX_EPOCH - Duration::from_secs(1), &mut header));
+/// ```
+pub fn format_system_time(time: SystemTime, buffer: &mut [u8; FORMATTED_LEN]) -> Result<(), OutOfRange> {
+    let duration = time.duration_since(UNIX_EPOCH).map_err(|_| OutOfRange::BeforeEpoch)?;
+    format(duration.as_secs(), buffer).map_err(|_| OutOfRange::TooFuturistic)
+}
+
+
+/// Parse an HTTP date header into a [SystemTime], as [parse] does.
+///
+/// Dates the platform's [SystemTime] can't represent are invalid.
+///
+/// ```rust
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let time = date_header::parse_system_time(b"Sunday, 06-Nov-94 08:49:37 GMT");
+/// assert_eq!(Ok(UNIX_EPOCH + Duration::from_secs(784111777)), time);
+/// ```
+pub fn parse_system_time(header: &[u8]) -> Result<SystemTime, InvalidDate> {
+    parse(header).and_then(|secs| to_system_time(secs).ok_or(InvalidDate))
+}
+
+
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, UNIX_EPOCH};
+    use crate::*;
+
+
+
+    #[test]
+    fn test_from_system_time() {
+        let cases = [
+            (Duration::ZERO, 0, 0),
+            (Duration::from_millis(499), 0, 0),
+            (Duration::from_millis(500), 0, 1), // Half a second rounds up
+            (Duration::from_nanos(999_999_999), 0, 1),
+            (Duration::from_secs(784111777), 784111777, 784111777),
+            (Duration::new(784111777, 499_999_999), 784111777, 784111777),
+            (Duration::new(784111777, 500_000_000), 784111777, 784111778),
+        ];
+
+        for (since_epoch, floor, nearest) in cases {
+            let time = UNIX_EPOCH + since_epoch;
+            assert_eq!(from_system_time(time, Rounding::Floor), floor, "{since_epoch:?}");
+            assert_eq!(from_system_time(time, Rounding::Nearest), nearest, "{since_epoch:?}");
+        }
+
+        // Before the epoch
+        assert_eq!(from_system_time(UNIX_EPOCH - Duration::from_millis(1), Rounding::Floor), 0);
+        assert_eq!(from_system_time(UNIX_EPOCH - Duration::from_secs(86400), Rounding::Nearest), 0);
+    }
+
+
+    #[test]
+    fn test_round_trip() {
+        for secs in [0, 784111777, MAX_FORMATTABLE] {
+            let time = to_system_time(secs).unwrap();
+            assert_eq!(from_system_time(time, Rounding::Floor), secs);
+            assert_eq!(from_system_time(time, Rounding::Nearest), secs);
+        }
+    }
+
+
+    #[test]
+    fn test_to_system_time() {
+        assert_eq!(to_system_time(0), Some(UNIX_EPOCH));
+        assert_eq!(to_system_time(784111777), Some(UNIX_EPOCH + Duration::from_secs(784111777)));
+        assert_eq!(to_system_time(MAX_FORMATTABLE + 1), Some(UNIX_EPOCH + Duration::from_secs(MAX_FORMATTABLE + 1)));
+        assert_eq!(to_system_time(u64::MAX), None);
+    }
+
+
+    #[test]
+    fn test_format_system_time() {
+        let mut buffer = [0; FORMATTED_LEN];
+        assert_eq!(format_system_time(UNIX_EPOCH + Duration::new(784111777, 999_999_999), &mut buffer), Ok(()));
+        assert_eq!(&buffer, b"Sun, 06 Nov 1994 08:49:37 GMT"); // Truncated
+
+        assert_eq!(format_system_time(UNIX_EPOCH, &mut buffer), Ok(()));
+        assert_eq!(&buffer, b"Thu, 01 Jan 1970 00:00:00 GMT");
+
+        assert_eq!(format_system_time(UNIX_EPOCH - Duration::from_nanos(1), &mut buffer), Err(OutOfRange::BeforeEpoch));
+        assert_eq!(format_system_time(to_system_time(MAX_FORMATTABLE).unwrap() + Duration::from_millis(999), &mut buffer), Ok(()));
+        assert_eq!(&buffer, b"Fri, 31 Dec 9999 23:59:59 GMT");
+        assert_eq!(format_system_time(to_system_time(MAX_FORMATTABLE + 1).unwrap(), &mut buffer), Err(OutOfRange::TooFuturistic));
+    }
+
+
+    #[test]
+    fn test_parse_system_time() {
+        assert_eq!(parse_system_time(b"Sun, 06 Nov 1994 08:49:37 GMT"), Ok(UNIX_EPOCH + Duration::from_secs(784111777)));
+        assert_eq!(parse_system_time(b"Sun Nov  6 08:49:37 1994"), Ok(UNIX_EPOCH + Duration::from_secs(784111777)));
+        assert_eq!(parse_system_time(b"Fri, 31 Dec 9999 23:59:59 GMT"), Ok(UNIX_EPOCH + Duration::from_secs(MAX_FORMATTABLE)));
+        assert_eq!(parse_system_time(b"Wed, 31 Dec 1969 23:59:59 GMT"), Err(InvalidDate));
+    }
+}
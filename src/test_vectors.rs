@@ -0,0 +1,153 @@
+//! Curated dates that HTTP date implementations tend to get wrong, for reuse in other conformance tests.
+//!
+//! The vectors cover leap-year and century boundaries, the edges of the supported range,
+//! each of the three formats, and near-misses that must be rejected. Every vector is checked
+//! against this crate's own [parse](crate::parse) and [format](crate::format) in its test suite.
+//!
+//! ```rust
+//! use date_header::test_vectors::PARSE_VECTORS;
+//!
+//! for vector in PARSE_VECTORS {
+//!     assert_eq!(date_header::parse(vector.header.as_bytes()).ok(), vector.expected, "{}", vector.description);
+//! }
+//! ```
+
+
+
+
+/// A header value and the unix timestamp it must parse to, or `None` if it must be rejected.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ParseVector {
+    /// The header value, exactly as it would appear on the wire
+    pub header: &'static str,
+    /// The unix timestamp it parses to, or `None` if it is invalid
+    pub expected: Option<u64>,
+    /// What makes this vector interesting
+    pub description: &'static str,
+}
+
+
+/// A unix timestamp and the IMF-fixdate it must format as.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct FormatVector {
+    /// The unix timestamp to format
+    pub timestamp: u64,
+    /// The only correct IMF-fixdate for it
+    pub formatted: &'static str,
+    /// What makes this vector interesting
+    pub description: &'static str,
+}
+
+
+
+
+const fn valid(header: &'static str, expected: u64, description: &'static str) -> ParseVector {
+    ParseVector { header, expected: Some(expected), description }
+}
+
+const fn invalid(header: &'static str, description: &'static str) -> ParseVector {
+    ParseVector { header, expected: None, description }
+}
+
+
+/// Headers to parse, both valid and invalid.
+pub static PARSE_VECTORS: &[ParseVector] = &[
+    // The three formats
+    valid("Sun, 06 Nov 1994 08:49:37 GMT", 784111777, "IMF-fixdate example from RFC 9110"),
+    valid("Sunday, 06-Nov-94 08:49:37 GMT", 784111777, "RFC 850 example from RFC 9110"),
+    valid("Sun Nov  6 08:49:37 1994", 784111777, "asctime example from RFC 9110"),
+    valid("Wednesday, 09-Nov-94 08:49:37 GMT", 784370977, "RFC 850 with the longest weekday name"),
+    valid("Fri May 15 15:34:21 2015", 1431704061, "asctime with a two digit day"),
+    valid("Sun Nov 06 08:49:37 1994", 784111777, "asctime with a zero padded day, which is accepted like httpdate does"),
+
+    // The edges of the supported range
+    valid("Thu, 01 Jan 1970 00:00:00 GMT", 0, "The unix epoch"),
+    valid("Thu, 01 Jan 1970 23:59:59 GMT", 86399, "The last second of the first day"),
+    valid("Thu Jan  1 00:00:00 1970", 0, "The unix epoch in asctime"),
+    valid("Thursday, 01-Jan-70 00:00:00 GMT", 0, "RFC 850 two digit years from 70 are in the 1900s"),
+    valid("Tuesday, 31-Dec-69 23:59:59 GMT", 3155759999, "RFC 850 two digit years before 70 are in the 2000s"),
+    valid("Tue, 19 Jan 2038 03:14:07 GMT", 2147483647, "The last second of a signed 32-bit time_t"),
+    valid("Tue, 19 Jan 2038 03:14:08 GMT", 2147483648, "The first second past a signed 32-bit time_t"),
+    valid("Sun, 07 Feb 2106 06:28:15 GMT", 4294967295, "The last second of an unsigned 32-bit time_t"),
+    valid("Fri, 31 Dec 9999 23:59:59 GMT", 253402300799, "The last second with a four digit year"),
+    invalid("Wed, 31 Dec 1969 23:59:59 GMT", "One second before the unix epoch"),
+    invalid("Sat, 01 Jan 10000 00:00:00 GMT", "A five digit year"),
+
+    // Leap years and century rules
+    valid("Tue, 29 Feb 1972 00:00:00 GMT", 68169600, "The first leap day after the epoch"),
+    valid("Sun, 31 Dec 1972 23:59:59 GMT", 94694399, "The last second of a leap year"),
+    valid("Tue, 29 Feb 2000 12:00:00 GMT", 951825600, "A century divisible by 400 is a leap year"),
+    valid("Sun, 31 Dec 2000 23:59:59 GMT", 978307199, "The last second of a leap century"),
+    valid("Sun, 28 Feb 2100 23:59:59 GMT", 4107542399, "The last second of February in a common century"),
+    valid("Mon, 01 Mar 2100 00:00:00 GMT", 4107542400, "A common century goes straight from February 28th to March 1st"),
+    valid("Tue, 29 Feb 2400 00:00:00 GMT", 13574563200, "The next century divisible by 400"),
+    valid("Mon Feb 29 00:00:00 2016", 1456704000, "A leap day in asctime"),
+    invalid("Mon, 29 Feb 2100 00:00:00 GMT", "A century not divisible by 400 is not a leap year"),
+    invalid("Sat, 29 Feb 2015 00:00:00 GMT", "A leap day in a common year"),
+    invalid("Fri, 31 Apr 2015 00:00:00 GMT", "April 31st, even though the weekday matches May 1st"),
+
+    // Fields out of range
+    invalid("Sun, 06 Nov 1994 24:00:00 GMT", "Hour 24"),
+    invalid("Sun, 06 Nov 1994 08:60:00 GMT", "Minute 60"),
+    invalid("Sat, 31 Dec 2016 23:59:60 GMT", "A leap second, which unix time can't represent"),
+    invalid("Sun, 00 Nov 1994 08:49:37 GMT", "Day 0"),
+
+    // Near-misses of each format
+    invalid("Mon, 06 Nov 1994 08:49:37 GMT", "The wrong weekday"),
+    invalid("Sun, 06 Nov 1994 08:49:37 UTC", "A zone other than GMT"),
+    invalid("Sun, 06 Nov 1994 08:49:37 gmt", "A lowercase zone"),
+    invalid("sun, 06 Nov 1994 08:49:37 GMT", "A lowercase weekday"),
+    invalid("Sun, 06 nov 1994 08:49:37 GMT", "A lowercase month"),
+    invalid("Sun, 6 Nov 1994 08:49:37 GMT", "A day without its leading zero"),
+    invalid("Sun, 06 Nov 94 08:49:37 GMT", "A two digit year in IMF-fixdate"),
+    invalid("Sun, 06 Nov 1994 08:49:37 +0000", "A numeric zone"),
+    invalid("Sun, 06 Nov 1994 08:49:37", "Missing the zone"),
+    invalid(" Sun, 06 Nov 1994 08:49:37 GMT", "Leading whitespace"),
+    invalid("Sun, 06 Nov 1994 08:49:37 GMT ", "Trailing whitespace"),
+    invalid("Sun, 06-Nov-94 08:49:37 GMT", "RFC 850 with an abbreviated weekday"),
+    invalid("Sunday, 06-Nov-1994 08:49:37 GMT", "RFC 850 with a four digit year"),
+    invalid("Sun Nov 6 08:49:37 1994", "asctime without padding on the day"),
+    invalid("", "Empty"),
+    invalid("0", "The common `Expires: 0`, which means already expired but isn't a date"),
+];
+
+
+/// Timestamps to format as IMF-fixdate.
+pub static FORMAT_VECTORS: &[FormatVector] = &[
+    FormatVector { timestamp: 0, formatted: "Thu, 01 Jan 1970 00:00:00 GMT", description: "The unix epoch" },
+    FormatVector { timestamp: 784111777, formatted: "Sun, 06 Nov 1994 08:49:37 GMT", description: "The example from RFC 9110" },
+    FormatVector { timestamp: 946684799, formatted: "Fri, 31 Dec 1999 23:59:59 GMT", description: "The last second of the 1900s" },
+    FormatVector { timestamp: 951825600, formatted: "Tue, 29 Feb 2000 12:00:00 GMT", description: "A leap day in a century divisible by 400" },
+    FormatVector { timestamp: 4107542400, formatted: "Mon, 01 Mar 2100 00:00:00 GMT", description: "The day after February 28th in a common century" },
+    FormatVector { timestamp: 2147483648, formatted: "Tue, 19 Jan 2038 03:14:08 GMT", description: "The first second past a signed 32-bit time_t" },
+    FormatVector { timestamp: 253402300799, formatted: "Fri, 31 Dec 9999 23:59:59 GMT", description: "The last formattable second" },
+];
+
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use super::*;
+
+
+
+    #[test]
+    fn test_parse_vectors() {
+        for vector in PARSE_VECTORS {
+            assert_eq!(parse(vector.header.as_bytes()).ok(), vector.expected, "{}: {:?}", vector.description, vector.header);
+        }
+    }
+
+
+    #[test]
+    fn test_format_vectors() {
+        let mut buffer = [0; FORMATTED_LEN];
+        for vector in FORMAT_VECTORS {
+            format(vector.timestamp, &mut buffer).unwrap();
+            assert_eq!(&buffer, vector.formatted.as_bytes(), "{}", vector.description);
+            assert_eq!(parse(&buffer), Ok(vector.timestamp), "{}", vector.description);
+        }
+    }
+}
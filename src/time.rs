@@ -0,0 +1,190 @@
+//! Conversions between the [time](::time) crate's `OffsetDateTime` and `UtcDateTime` and unix timestamps,
+//! for codebases standardized on the time crate.
+//!
+//! HTTP dates only have whole seconds, so converting from time truncates toward the past,
+//! which is the right direction for `Last-Modified` and other validators.
+//! An `OffsetDateTime` in any offset is converted to the same instant in GMT.
+//!
+//! ```rust
+//! use time::macros::datetime;
+//!
+//! let modified = datetime!(2015-05-15 17:34:21.7 +02:00);
+//! assert_eq!(Ok(1431704061), date_header::time::from_offset_date_time(&modified));
+//!
+//! let mut header = [0u8; 29];
+//! date_header::time::format_offset_date_time(&modified, &mut header).unwrap();
+//! assert_eq!(&header, b"Fri, 15 May 2015 15:34:21 GMT");
+//! ```
+
+use ::time::{OffsetDateTime, UtcDateTime};
+use crate::{format, in_formattable_range, Timestamp, TooFuturistic, FORMATTED_LEN};
+
+
+
+
+/// Convert an `OffsetDateTime` in any offset into a unix timestamp, discarding any fractional second.
+///
+/// Fails for times before the unix epoch or after the year 9999.
+pub fn from_offset_date_time(time: &OffsetDateTime) -> Result<u64, OutOfRange> {
+    from_unix_timestamp(time.unix_timestamp())
+}
+
+
+/// Convert a `UtcDateTime` into a unix timestamp, discarding any fractional second.
+///
+/// Fails for times before the unix epoch or after the year 9999.
+pub fn from_utc_date_time(time: &UtcDateTime) -> Result<u64, OutOfRange> {
+    from_unix_timestamp(time.unix_timestamp())
+}
+
+
+/// Convert a unix timestamp into an `OffsetDateTime` in UTC.
+///
+/// Fails for times after the year 9999.
+pub fn to_offset_date_time(secs_since_epoch: u64) -> Result<OffsetDateTime, TooFuturistic> {
+    to_utc_date_time(secs_since_epoch).map(OffsetDateTime::from)
+}
+
+
+/// Convert a unix timestamp into a `UtcDateTime`.
+///
+/// Fails for times after the year 9999.
+pub fn to_utc_date_time(secs_since_epoch: u64) -> Result<UtcDateTime, TooFuturistic> {
+    if !in_formattable_range(secs_since_epoch) {
+        return Err(TooFuturistic);
+    }
+
+    Ok(UtcDateTime::from_unix_timestamp(secs_since_epoch as i64).expect("The year 9999 is within time's range"))
+}
+
+
+/// Format an `OffsetDateTime` as an IMF-fixdate into the provided buffer, as [format] does.
+///
+/// Fails for times before the unix epoch or after the year 9999.
+pub fn format_offset_date_time(time: &OffsetDateTime, buffer: &mut [u8; FORMATTED_LEN]) -> Result<(), OutOfRange> {
+    let secs = from_offset_date_time(time)?;
+    format(secs, buffer).map_err(|_| OutOfRange)
+}
+
+
+/// Format a `UtcDateTime` as an IMF-fixdate into the provided buffer, as [format] does.
+///
+/// Fails for times before the unix epoch or after the year 9999.
+pub fn format_utc_date_time(time: &UtcDateTime, buffer: &mut [u8; FORMATTED_LEN]) -> Result<(), OutOfRange> {
+    let secs = from_utc_date_time(time)?;
+    format(secs, buffer).map_err(|_| OutOfRange)
+}
+
+
+/// Error returned when a time crate value can't be an HTTP date.
+///
+/// Only times before the epoch are rejected, unless the time crate's `large-dates` feature allows years past 9999.
+#[derive(Debug, Eq, PartialEq)]
+pub struct OutOfRange;
+
+
+
+
+fn from_unix_timestamp(secs: i64) -> Result<u64, OutOfRange> {
+    match u64::try_from(secs) {
+        Ok(secs) if in_formattable_range(secs) => Ok(secs),
+        _ => Err(OutOfRange),
+    }
+}
+
+
+
+
+impl TryFrom<OffsetDateTime> for Timestamp {
+    type Error = OutOfRange;
+
+    fn try_from(time: OffsetDateTime) -> Result<Timestamp, OutOfRange> {
+        from_offset_date_time(&time).map(|secs| Timestamp::new(secs).expect("from_offset_date_time is always in the formattable range"))
+    }
+}
+
+impl TryFrom<UtcDateTime> for Timestamp {
+    type Error = OutOfRange;
+
+    fn try_from(time: UtcDateTime) -> Result<Timestamp, OutOfRange> {
+        from_utc_date_time(&time).map(|secs| Timestamp::new(secs).expect("from_utc_date_time is always in the formattable range"))
+    }
+}
+
+impl From<Timestamp> for OffsetDateTime {
+    fn from(timestamp: Timestamp) -> OffsetDateTime {
+        to_offset_date_time(timestamp.as_secs()).expect("Timestamp is always in the formattable range")
+    }
+}
+
+impl From<Timestamp> for UtcDateTime {
+    fn from(timestamp: Timestamp) -> UtcDateTime {
+        to_utc_date_time(timestamp.as_secs()).expect("Timestamp is always in the formattable range")
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use ::time::{Duration, UtcOffset};
+    use crate::MAX_FORMATTABLE;
+    use super::*;
+
+
+
+    fn utc(seconds: i64, nanos: i64) -> UtcDateTime {
+        UtcDateTime::from_unix_timestamp(seconds).unwrap() + Duration::nanoseconds(nanos)
+    }
+
+
+    #[test]
+    fn test_from_time() {
+        assert_eq!(from_utc_date_time(&utc(0, 0)), Ok(0));
+        assert_eq!(from_utc_date_time(&utc(784111777, 999_999_999)), Ok(784111777)); // Truncated, not rounded
+        assert_eq!(from_utc_date_time(&utc(MAX_FORMATTABLE as i64, 999_999_999)), Ok(MAX_FORMATTABLE));
+        assert_eq!(from_utc_date_time(&utc(-1, 999_999_999)), Err(OutOfRange)); // Still before the epoch
+
+        // The same instant in any offset
+        let offset = OffsetDateTime::from(utc(784111777, 0)).to_offset(UtcOffset::from_hms(-5, 0, 0).unwrap());
+        assert_eq!(from_offset_date_time(&offset), Ok(784111777));
+
+        // Local midnight after the epoch in an offset ahead of UTC is still before the epoch in UTC
+        let ahead = OffsetDateTime::from(utc(0, 0)).to_offset(UtcOffset::from_hms(1, 0, 0).unwrap()) - Duration::seconds(1);
+        assert_eq!(from_offset_date_time(&ahead), Err(OutOfRange));
+    }
+
+
+    #[test]
+    fn test_to_time() {
+        assert_eq!(to_utc_date_time(0), Ok(utc(0, 0)));
+        assert_eq!(to_utc_date_time(MAX_FORMATTABLE), Ok(utc(MAX_FORMATTABLE as i64, 0)));
+        assert_eq!(to_utc_date_time(MAX_FORMATTABLE + 1), Err(TooFuturistic));
+        assert_eq!(to_offset_date_time(784111777).map(|time| (time.unix_timestamp(), time.offset())), Ok((784111777, UtcOffset::UTC)));
+        assert_eq!(to_offset_date_time(u64::MAX), Err(TooFuturistic));
+    }
+
+
+    #[test]
+    fn test_format_time() {
+        let mut buffer = [0; FORMATTED_LEN];
+        assert_eq!(format_utc_date_time(&utc(784111777, 500_000_000), &mut buffer), Ok(()));
+        assert_eq!(&buffer, b"Sun, 06 Nov 1994 08:49:37 GMT");
+
+        let offset = OffsetDateTime::from(utc(784111777, 0)).to_offset(UtcOffset::from_hms(9, 30, 0).unwrap());
+        assert_eq!(format_offset_date_time(&offset, &mut buffer), Ok(()));
+        assert_eq!(&buffer, b"Sun, 06 Nov 1994 08:49:37 GMT");
+
+        assert_eq!(format_utc_date_time(&utc(-1, 0), &mut buffer), Err(OutOfRange));
+    }
+
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Timestamp::try_from(utc(784111777, 500_000_000)), Ok(Timestamp::new(784111777).unwrap()));
+        assert_eq!(Timestamp::try_from(OffsetDateTime::from(utc(-1, 0))), Err(OutOfRange));
+        assert_eq!(UtcDateTime::from(Timestamp::MAX), utc(MAX_FORMATTABLE as i64, 0));
+        assert_eq!(OffsetDateTime::from(Timestamp::MIN), OffsetDateTime::UNIX_EPOCH);
+    }
+}
@@ -0,0 +1,276 @@
+use core::fmt;
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+use core::str::FromStr;
+use core::time::Duration;
+use crate::{format, in_formattable_range, parse, InvalidDate, TooFuturistic, MAX_FORMATTABLE, MIN};
+
+
+
+
+/// A unix timestamp that is guaranteed to be representable as an IMF-fixdate.
+///
+/// Arithmetic on a `Timestamp` is checked against the year 9999 boundary,
+/// so it can always be formatted without an error. Timestamps are ordered chronologically.
+///
+/// ```rust
+/// use date_header::Timestamp;
+///
+/// let timestamp: Timestamp = "Fri, 15 May 2015 15:34:21 GMT".parse().unwrap();
+/// assert_eq!(1431704061, timestamp.as_secs());
+///
+/// let expires = timestamp.checked_add_secs(3600).unwrap();
+/// assert_eq!("Fri, 15 May 2015 16:34:21 GMT", expires.to_string());
+///
+/// assert_eq!(None, Timestamp::MAX.checked_add_secs(1));
+///
+/// let ttl = std::time::Duration::from_secs(60);
+/// assert_eq!(timestamp + ttl - timestamp, ttl);
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Timestamp(u64);
+
+impl Timestamp {
+    /// The earliest representable timestamp: `Thu, 01 Jan 1970 00:00:00 GMT`
+    pub const MIN: Timestamp = Timestamp(MIN);
+
+    /// The latest representable timestamp: `Fri, 31 Dec 9999 23:59:59 GMT`
+    pub const MAX: Timestamp = Timestamp(MAX_FORMATTABLE);
+
+    /// Create a timestamp from seconds since the unix epoch, failing if it is after the year 9999.
+    pub const fn new(secs_since_epoch: u64) -> Result<Timestamp, TooFuturistic> {
+        if in_formattable_range(secs_since_epoch) {
+            Ok(Timestamp(secs_since_epoch))
+        } else {
+            Err(TooFuturistic)
+        }
+    }
+
+    /// Parse an HTTP date header in any of the formats accepted by [parse].
+    pub fn parse(header: &[u8]) -> Result<Timestamp, InvalidDate> {
+        parse(header).map(Timestamp)
+    }
+
+    /// Seconds since the unix epoch.
+    pub const fn as_secs(self) -> u64 {
+        self.0
+    }
+
+    /// Add seconds, returning `None` if the result would be after the year 9999.
+    pub fn checked_add_secs(self, secs: u64) -> Option<Timestamp> {
+        self.0.checked_add(secs).and_then(|secs| Timestamp::new(secs).ok())
+    }
+
+    /// Subtract seconds, returning `None` if the result would be before the unix epoch.
+    pub fn checked_sub_secs(self, secs: u64) -> Option<Timestamp> {
+        self.0.checked_sub(secs).map(Timestamp)
+    }
+
+    /// Add a duration, returning `None` if the result would be after the year 9999.
+    ///
+    /// Sub-second precision is discarded.
+    pub fn checked_add(self, duration: Duration) -> Option<Timestamp> {
+        self.checked_add_secs(duration.as_secs())
+    }
+
+    /// Subtract a duration, returning `None` if the result would be before the unix epoch.
+    ///
+    /// Sub-second precision is discarded.
+    pub fn checked_sub(self, duration: Duration) -> Option<Timestamp> {
+        self.checked_sub_secs(duration.as_secs())
+    }
+
+    /// Add seconds, clamping to [Timestamp::MAX].
+    pub fn saturating_add(self, secs: u64) -> Timestamp {
+        Timestamp(self.0.saturating_add(secs).min(Timestamp::MAX.0))
+    }
+
+    /// Subtract seconds, clamping to [Timestamp::MIN].
+    pub fn saturating_sub(self, secs: u64) -> Timestamp {
+        Timestamp(self.0.saturating_sub(secs))
+    }
+
+    /// Time elapsed from `earlier` to this timestamp, or zero if `earlier` is later.
+    pub fn duration_since(self, earlier: Timestamp) -> Duration {
+        Duration::from_secs(self.0.saturating_sub(earlier.0))
+    }
+
+    /// Format this timestamp as an IMF-fixdate, as [format] does.
+    ///
+    /// Unlike [format], this cannot fail.
+    pub fn format(self, buffer: &mut [u8; 29]) {
+        format(self.0, buffer).expect("Timestamp is always in the formattable range");
+    }
+}
+
+/// Panics if the result would be after the year 9999. See [Timestamp::checked_add].
+impl Add<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn add(self, duration: Duration) -> Timestamp {
+        self.checked_add(duration).expect("overflow when adding duration to timestamp")
+    }
+}
+
+impl AddAssign<Duration> for Timestamp {
+    fn add_assign(&mut self, duration: Duration) {
+        *self = *self + duration;
+    }
+}
+
+/// Panics if the result would be before the unix epoch. See [Timestamp::checked_sub].
+impl Sub<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn sub(self, duration: Duration) -> Timestamp {
+        self.checked_sub(duration).expect("overflow when subtracting duration from timestamp")
+    }
+}
+
+impl SubAssign<Duration> for Timestamp {
+    fn sub_assign(&mut self, duration: Duration) {
+        *self = *self - duration;
+    }
+}
+
+/// Saturates to zero if `earlier` is later, as [Timestamp::duration_since] does.
+impl Sub<Timestamp> for Timestamp {
+    type Output = Duration;
+
+    fn sub(self, earlier: Timestamp) -> Duration {
+        self.duration_since(earlier)
+    }
+}
+
+impl TryFrom<u64> for Timestamp {
+    type Error = TooFuturistic;
+
+    fn try_from(secs_since_epoch: u64) -> Result<Timestamp, TooFuturistic> {
+        Timestamp::new(secs_since_epoch)
+    }
+}
+
+impl TryFrom<&[u8]> for Timestamp {
+    type Error = InvalidDate;
+
+    fn try_from(header: &[u8]) -> Result<Timestamp, InvalidDate> {
+        Timestamp::parse(header)
+    }
+}
+
+impl TryFrom<&str> for Timestamp {
+    type Error = InvalidDate;
+
+    fn try_from(header: &str) -> Result<Timestamp, InvalidDate> {
+        Timestamp::parse(header.as_bytes())
+    }
+}
+
+impl From<Timestamp> for u64 {
+    fn from(timestamp: Timestamp) -> u64 {
+        timestamp.0
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buffer = [0u8; 29];
+        self.format(&mut buffer);
+        f.pad(core::str::from_utf8(&buffer).expect("IMF-fixdate is ASCII"))
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = InvalidDate;
+
+    fn from_str(text: &str) -> Result<Timestamp, InvalidDate> {
+        Timestamp::parse(text.as_bytes())
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use core::time::Duration;
+    use crate::*;
+
+
+
+    #[test]
+    fn test_timestamp() {
+        assert_eq!(Timestamp::new(0), Ok(Timestamp::MIN));
+        assert_eq!(Timestamp::new(253402300799), Ok(Timestamp::MAX));
+        assert_eq!(Timestamp::new(253402300800), Err(TooFuturistic));
+        assert_eq!(Timestamp::new(u64::MAX), Err(TooFuturistic));
+
+        let timestamp = Timestamp::new(784111777).unwrap();
+        assert_eq!(timestamp.as_secs(), 784111777);
+        assert_eq!(timestamp.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(format!("{timestamp:^31}"), " Sun, 06 Nov 1994 08:49:37 GMT ");
+        assert_eq!("Sunday, 06-Nov-94 08:49:37 GMT".parse(), Ok(timestamp));
+        assert_eq!("Sun, 06 Nov 1994".parse::<Timestamp>(), Err(InvalidDate));
+        assert_eq!(Timestamp::MIN.to_string(), "Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(Timestamp::MAX.to_string(), "Fri, 31 Dec 9999 23:59:59 GMT");
+    }
+
+
+    #[test]
+    fn test_timestamp_arithmetic() {
+        let timestamp = Timestamp::new(784111777).unwrap();
+
+        assert_eq!(timestamp.checked_add_secs(1), Timestamp::new(784111778).ok());
+        assert_eq!(Timestamp::MAX.checked_add_secs(0), Some(Timestamp::MAX));
+        assert_eq!(Timestamp::MAX.checked_add_secs(1), None);
+        assert_eq!(timestamp.checked_add_secs(u64::MAX), None); // Overflows u64, not just the year 9999
+
+        assert_eq!(timestamp.checked_sub_secs(784111777), Some(Timestamp::MIN));
+        assert_eq!(timestamp.checked_sub_secs(784111778), None);
+
+        assert_eq!(timestamp.saturating_add(1), Timestamp::new(784111778).unwrap());
+        assert_eq!(timestamp.saturating_add(u64::MAX), Timestamp::MAX);
+        assert_eq!(timestamp.saturating_sub(1), Timestamp::new(784111776).unwrap());
+        assert_eq!(timestamp.saturating_sub(u64::MAX), Timestamp::MIN);
+
+        assert_eq!(timestamp.duration_since(Timestamp::MIN), Duration::from_secs(784111777));
+        assert_eq!(timestamp.duration_since(timestamp), Duration::ZERO);
+        assert_eq!(Timestamp::MIN.duration_since(timestamp), Duration::ZERO);
+    }
+
+
+    #[test]
+    fn test_timestamp_operators() {
+        let mut timestamp = Timestamp::new(784111777).unwrap();
+
+        assert_eq!(timestamp + Duration::from_secs(1), Timestamp::new(784111778).unwrap());
+        assert_eq!(timestamp + Duration::from_millis(1999), Timestamp::new(784111778).unwrap()); // Sub-second part is discarded
+        assert_eq!(timestamp - Duration::from_secs(1), Timestamp::new(784111776).unwrap());
+        assert_eq!(timestamp - Duration::from_secs(784111777), Timestamp::MIN);
+        assert_eq!(Timestamp::MAX.checked_add(Duration::from_secs(1)), None);
+        assert_eq!(Timestamp::MAX.checked_add(Duration::from_nanos(999_999_999)), Some(Timestamp::MAX));
+        assert_eq!(Timestamp::MIN.checked_sub(Duration::from_secs(1)), None);
+        assert_eq!(timestamp.checked_add(Duration::MAX), None);
+
+        assert_eq!(Timestamp::MAX - Timestamp::MIN, Duration::from_secs(253402300799));
+        assert_eq!(Timestamp::MIN - Timestamp::MAX, Duration::ZERO);
+
+        timestamp += Duration::from_secs(3);
+        assert_eq!(timestamp, Timestamp::new(784111780).unwrap());
+        timestamp -= Duration::from_secs(2);
+        assert_eq!(timestamp, Timestamp::new(784111778).unwrap());
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_timestamp_add_overflow() {
+        let _ = Timestamp::MAX + Duration::from_secs(1);
+    }
+
+
+    #[test]
+    #[should_panic]
+    fn test_timestamp_sub_overflow() {
+        let _ = Timestamp::MIN - Duration::from_secs(1);
+    }
+}
@@ -0,0 +1,131 @@
+//! Timers for [tracing-subscriber](::tracing_subscriber)'s formatter that reuse a per-second cached timestamp.
+//!
+//! Log lines within the same second share one formatted timestamp per thread, so formatting
+//! the time costs a clock read and a copy rather than calendar math on every event.
+//!
+//! ```rust
+//! tracing_subscriber::fmt()
+//!     .with_timer(date_header::tracing::Rfc3339)
+//!     .init();
+//! ```
+
+use core::fmt;
+use std::cell::Cell;
+use ::tracing_subscriber::fmt::format::Writer;
+use ::tracing_subscriber::fmt::time::FormatTime;
+use crate::{cached::unix_now, civil_from_days, format_now, split_days, split_time, FORMATTED_LEN};
+
+
+// Length of an RFC 3339 timestamp in UTC with whole seconds, such as `2015-05-15T15:34:21Z`
+const RFC3339_LEN: usize = 20;
+
+thread_local! {
+    static CACHE: Cell<(u64, [u8; RFC3339_LEN])> = const { Cell::new((u64::MAX, [0u8; RFC3339_LEN])) };
+}
+
+
+
+
+/// Timer writing the current time as an IMF-fixdate: `Fri, 15 May 2015 15:34:21 GMT`.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct ImfFixdate;
+
+impl FormatTime for ImfFixdate {
+    fn format_time(&self, w: &mut Writer<'_>) -> fmt::Result {
+        let mut buffer = [0u8; FORMATTED_LEN];
+        format_now(&mut buffer);
+        w.write_str(core::str::from_utf8(&buffer).expect("IMF-fixdate is ASCII"))
+    }
+}
+
+
+/// Timer writing the current time in RFC 3339 form, in UTC to the second: `2015-05-15T15:34:21Z`.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct Rfc3339;
+
+impl FormatTime for Rfc3339 {
+    fn format_time(&self, w: &mut Writer<'_>) -> fmt::Result {
+        let now = unix_now();
+
+        let buffer = CACHE.with(|cache| {
+            let (cached_secs, cached_buffer) = cache.get();
+            if cached_secs == now {
+                cached_buffer
+            } else {
+                let buffer = format_rfc3339(now);
+                cache.set((now, buffer));
+                buffer
+            }
+        });
+
+        w.write_str(core::str::from_utf8(&buffer).expect("RFC 3339 is ASCII"))
+    }
+}
+
+
+
+
+// Format a timestamp no later than MAX_FORMATTABLE as `YYYY-MM-DDTHH:MM:SSZ`
+fn format_rfc3339(secs_since_epoch: u64) -> [u8; RFC3339_LEN] {
+    let (days, secs_of_day) = split_days(secs_since_epoch);
+    let (year, mon, mday) = civil_from_days(days);
+    let (hour, min, sec) = split_time(secs_of_day);
+
+    let mut buffer = *b"0000-00-00T00:00:00Z";
+    let digits = |n: u8| [b'0' + n / 10, b'0' + n % 10];
+
+    buffer[0..2].copy_from_slice(&digits((year / 100) as u8));
+    buffer[2..4].copy_from_slice(&digits((year % 100) as u8));
+    buffer[5..7].copy_from_slice(&digits(mon));
+    buffer[8..10].copy_from_slice(&digits(mday));
+    buffer[11..13].copy_from_slice(&digits(hour));
+    buffer[14..16].copy_from_slice(&digits(min));
+    buffer[17..19].copy_from_slice(&digits(sec));
+    buffer
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::{cached::unix_now, parse, MAX_FORMATTABLE};
+    use super::*;
+
+
+
+    #[test]
+    fn test_format_rfc3339() {
+        assert_eq!(&format_rfc3339(0), b"1970-01-01T00:00:00Z");
+        assert_eq!(&format_rfc3339(784111777), b"1994-11-06T08:49:37Z");
+        assert_eq!(&format_rfc3339(951782400), b"2000-02-29T00:00:00Z");
+        assert_eq!(&format_rfc3339(MAX_FORMATTABLE), b"9999-12-31T23:59:59Z");
+    }
+
+
+    #[test]
+    fn test_imf_fixdate() {
+        let before = unix_now();
+        let mut text = String::new();
+        ImfFixdate.format_time(&mut Writer::new(&mut text)).unwrap();
+        let after = unix_now();
+
+        let secs = parse(text.as_bytes()).unwrap();
+        assert!(before <= secs && secs <= after);
+    }
+
+
+    #[test]
+    fn test_rfc3339() {
+        let before = unix_now();
+        let mut first = String::new();
+        let mut second = String::new();
+        Rfc3339.format_time(&mut Writer::new(&mut first)).unwrap();
+        Rfc3339.format_time(&mut Writer::new(&mut second)).unwrap();
+        let after = unix_now();
+
+        assert_eq!(first.len(), RFC3339_LEN);
+        assert!(first.as_bytes() >= &format_rfc3339(before)[..] && first <= second);
+        assert!(second.as_bytes() <= &format_rfc3339(after)[..]);
+    }
+}
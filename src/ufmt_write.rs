@@ -0,0 +1,90 @@
+//! Writing dates to a [`ufmt`](https://docs.rs/ufmt) writer, for projects that avoid `core::fmt` entirely.
+//!
+//! This only needs `ufmt-write`, so the date types don't implement `ufmt::uDisplay`.
+//!
+//! Any `ufmt::uWrite` implementor works, since it is the same trait as `ufmt_write::uWrite`.
+//! The functions have the same shape as `uwrite!`, so they slot in next to it:
+//!
+//! ```rust
+//! use date_header::Timestamp;
+//!
+//! let mut log = String::new();
+//! date_header::ufmt_write::write_timestamp(&mut log, Timestamp::new(1431704061).unwrap()).unwrap();
+//! assert_eq!(log, "Fri, 15 May 2015 15:34:21 GMT");
+//! ```
+
+use ::ufmt_write::uWrite;
+use crate::{HttpDate, Timestamp, FORMATTED_LEN};
+
+
+
+
+/// Write a timestamp to the writer as an IMF-fixdate.
+pub fn write_timestamp<W: uWrite + ?Sized>(writer: &mut W, timestamp: Timestamp) -> Result<(), W::Error> {
+    let mut buffer = [0u8; FORMATTED_LEN];
+    timestamp.format(&mut buffer);
+    writer.write_str(ascii_str(&buffer))
+}
+
+
+/// Write a date to the writer as an IMF-fixdate.
+pub fn write_http_date<W: uWrite + ?Sized>(writer: &mut W, date: HttpDate) -> Result<(), W::Error> {
+    let mut buffer = [0u8; FORMATTED_LEN];
+    date.format(&mut buffer);
+    writer.write_str(ascii_str(&buffer))
+}
+
+
+// View a formatted date as a string without bringing in from_utf8's error formatting
+fn ascii_str(buffer: &[u8; FORMATTED_LEN]) -> &str {
+    match core::str::from_utf8(buffer) {
+        Ok(text) => text,
+        Err(_) => unreachable!(),
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::MAX_FORMATTABLE;
+    use super::*;
+
+
+
+    #[test]
+    fn test_write_timestamp() {
+        let mut text = String::from("Date: ");
+        assert_eq!(write_timestamp(&mut text, Timestamp::new(784111777).unwrap()), Ok(()));
+        assert_eq!(text, "Date: Sun, 06 Nov 1994 08:49:37 GMT");
+
+        let mut text = String::new();
+        assert_eq!(write_timestamp(&mut text, Timestamp::new(MAX_FORMATTABLE).unwrap()), Ok(()));
+        assert_eq!(text, "Fri, 31 Dec 9999 23:59:59 GMT");
+    }
+
+
+    #[test]
+    fn test_write_http_date() {
+        let mut text = String::new();
+        assert_eq!(write_http_date(&mut text, HttpDate::from_timestamp(0).unwrap()), Ok(()));
+        assert_eq!(text, "Thu, 01 Jan 1970 00:00:00 GMT");
+    }
+
+
+    #[test]
+    fn test_writer_error() {
+        struct Full;
+
+        impl uWrite for Full {
+            type Error = ();
+
+            fn write_str(&mut self, _: &str) -> Result<(), ()> {
+                Err(())
+            }
+        }
+
+        assert_eq!(write_timestamp(&mut Full, Timestamp::MAX), Err(()));
+    }
+}
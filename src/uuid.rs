@@ -0,0 +1,171 @@
+//! Conversions between [UUIDv7](::uuid::Uuid) and unix timestamps, for resources named by time-ordered IDs.
+//!
+//! A UUIDv7 starts with the unix time in milliseconds when it was generated, which is enough
+//! to derive a `Last-Modified` for an immutable resource without storing one. HTTP dates only
+//! have whole seconds, so the milliseconds are truncated toward the past.
+//!
+//! Going the other way, [to_uuid] gives the lowest UUIDv7 of a second, as an anchor for
+//! range queries over IDs: every ID generated during or after that second compares at least as large.
+//!
+//! ```rust
+//! let id = uuid::Uuid::parse_str("017f22e2-79b0-7cc3-98c4-dc0c0c07398f").unwrap();
+//!
+//! let mut header = [0u8; 29];
+//! date_header::uuid::format_uuid(&id, &mut header).unwrap();
+//! assert_eq!(&header, b"Tue, 22 Feb 2022 19:22:22 GMT");
+//!
+//! let anchor = date_header::uuid::to_uuid(1645557742).unwrap();
+//! assert!(anchor <= id);
+//! ```
+
+use ::uuid::{Uuid, Variant};
+use crate::{format, in_formattable_range, Timestamp, TooFuturistic, FORMATTED_LEN};
+
+
+
+
+/// Read the unix timestamp from a UUIDv7, discarding the milliseconds.
+///
+/// Fails for any other version of UUID, and for times after the year 9999,
+/// which the 48 bit millisecond field can hold until the year 10889.
+pub fn from_uuid(uuid: &Uuid) -> Result<u64, InvalidUuid> {
+    if uuid.get_version_num() != 7 || uuid.get_variant() != Variant::RFC4122 {
+        return Err(InvalidUuid::NotVersion7);
+    }
+
+    let bytes = uuid.as_bytes();
+    let millis = u64::from_be_bytes([0, 0, bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5]]);
+    let secs = millis / 1000;
+
+    if !in_formattable_range(secs) {
+        return Err(InvalidUuid::TooFuturistic);
+    }
+
+    Ok(secs)
+}
+
+
+/// The lowest UUIDv7 generated during a second, with every random bit zero.
+///
+/// Fails for times after the year 9999.
+pub fn to_uuid(secs_since_epoch: u64) -> Result<Uuid, TooFuturistic> {
+    if !in_formattable_range(secs_since_epoch) {
+        return Err(TooFuturistic);
+    }
+
+    // Milliseconds to the year 10000 still fit in the 48 bit field
+    let millis = (secs_since_epoch * 1000).to_be_bytes();
+
+    let mut bytes = [0u8; 16];
+    bytes[..6].copy_from_slice(&millis[2..]);
+    bytes[6] = 0x70;
+    bytes[8] = 0x80;
+    Ok(Uuid::from_bytes(bytes))
+}
+
+
+/// Format the time of a UUIDv7 as an IMF-fixdate into the provided buffer, as [format] does.
+///
+/// Fails as [from_uuid] does.
+pub fn format_uuid(uuid: &Uuid, buffer: &mut [u8; FORMATTED_LEN]) -> Result<(), InvalidUuid> {
+    let secs = from_uuid(uuid)?;
+    format(secs, buffer).map_err(|TooFuturistic| InvalidUuid::TooFuturistic)
+}
+
+
+/// Error returned from [from_uuid] and [format_uuid] when the UUID has no time that can be an HTTP date.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum InvalidUuid {
+    /// The UUID is not a UUIDv7, so has no time to read.
+    NotVersion7,
+    /// The time was after the year 9999, like [TooFuturistic].
+    TooFuturistic,
+}
+
+
+
+
+impl TryFrom<Uuid> for Timestamp {
+    type Error = InvalidUuid;
+
+    fn try_from(uuid: Uuid) -> Result<Timestamp, InvalidUuid> {
+        from_uuid(&uuid).and_then(|secs| Timestamp::new(secs).map_err(|TooFuturistic| InvalidUuid::TooFuturistic))
+    }
+}
+
+impl From<Timestamp> for Uuid {
+    fn from(timestamp: Timestamp) -> Uuid {
+        to_uuid(timestamp.as_secs()).expect("Timestamp is always in the formattable range")
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::MAX_FORMATTABLE;
+    use super::*;
+
+
+
+    // The UUIDv7 example from RFC 9562, generated at 1645557742000 milliseconds
+    const EXAMPLE: &str = "017f22e2-79b0-7cc3-98c4-dc0c0c07398f";
+
+    // The UUIDv7 with the latest possible time, in the year 10889
+    const LATEST: &str = "ffffffff-ffff-7fff-bfff-ffffffffffff";
+
+
+    #[test]
+    fn test_from_uuid() {
+        assert_eq!(from_uuid(&Uuid::parse_str(EXAMPLE).unwrap()), Ok(1645557742));
+        assert_eq!(from_uuid(&Uuid::parse_str("017f22e2-7d97-7cc3-98c4-dc0c0c07398f").unwrap()), Ok(1645557742)); // 999 milliseconds, truncated
+        assert_eq!(from_uuid(&Uuid::parse_str("00000000-0000-7000-8000-000000000000").unwrap()), Ok(0));
+
+        // The last millisecond of the year 9999, and the first after it
+        assert_eq!(from_uuid(&Uuid::parse_str("e677d21f-dbff-7fff-bfff-ffffffffffff").unwrap()), Ok(MAX_FORMATTABLE));
+        assert_eq!(from_uuid(&Uuid::parse_str("e677d21f-dc00-7000-8000-000000000000").unwrap()), Err(InvalidUuid::TooFuturistic));
+        assert_eq!(from_uuid(&Uuid::parse_str(LATEST).unwrap()), Err(InvalidUuid::TooFuturistic));
+
+        // Version 4 from RFC 9562, version 1, the nil UUID, and a version 7 with the wrong variant
+        assert_eq!(from_uuid(&Uuid::parse_str("919108f7-52d1-4320-9bac-f847db4148a8").unwrap()), Err(InvalidUuid::NotVersion7));
+        assert_eq!(from_uuid(&Uuid::parse_str("c232ab00-9414-11ec-b3c8-9f6bdeced846").unwrap()), Err(InvalidUuid::NotVersion7));
+        assert_eq!(from_uuid(&Uuid::nil()), Err(InvalidUuid::NotVersion7));
+        assert_eq!(from_uuid(&Uuid::parse_str("017f22e2-79b0-7cc3-18c4-dc0c0c07398f").unwrap()), Err(InvalidUuid::NotVersion7));
+    }
+
+
+    #[test]
+    fn test_to_uuid() {
+        assert_eq!(to_uuid(1645557742), Ok(Uuid::parse_str("017f22e2-79b0-7000-8000-000000000000").unwrap()));
+        assert_eq!(to_uuid(0), Ok(Uuid::parse_str("00000000-0000-7000-8000-000000000000").unwrap()));
+        assert_eq!(to_uuid(MAX_FORMATTABLE + 1), Err(TooFuturistic));
+
+        // The anchor is the lowest ID of its second, and round trips
+        let example = Uuid::parse_str(EXAMPLE).unwrap();
+        assert!(to_uuid(1645557742).unwrap() <= example && example < to_uuid(1645557743).unwrap());
+        for secs in [0, 784111777, 1645557742, MAX_FORMATTABLE] {
+            assert_eq!(from_uuid(&to_uuid(secs).unwrap()), Ok(secs));
+        }
+    }
+
+
+    #[test]
+    fn test_format_uuid() {
+        let mut buffer = [0; FORMATTED_LEN];
+        assert_eq!(format_uuid(&Uuid::parse_str(EXAMPLE).unwrap(), &mut buffer), Ok(()));
+        assert_eq!(&buffer, b"Tue, 22 Feb 2022 19:22:22 GMT");
+        assert_eq!(format_uuid(&Uuid::nil(), &mut buffer), Err(InvalidUuid::NotVersion7));
+        assert_eq!(format_uuid(&Uuid::parse_str(LATEST).unwrap(), &mut buffer), Err(InvalidUuid::TooFuturistic));
+    }
+
+
+    #[test]
+    fn test_conversions() {
+        let example = Uuid::parse_str(EXAMPLE).unwrap();
+        assert_eq!(Timestamp::try_from(example), Ok(Timestamp::new(1645557742).unwrap()));
+        assert_eq!(Timestamp::try_from(Uuid::from_u128(u128::MAX)), Err(InvalidUuid::NotVersion7));
+        assert_eq!(Timestamp::try_from(Uuid::parse_str(LATEST).unwrap()), Err(InvalidUuid::TooFuturistic));
+        assert_eq!(Uuid::from(Timestamp::MIN), Uuid::parse_str("00000000-0000-7000-8000-000000000000").unwrap());
+    }
+}
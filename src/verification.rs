@@ -0,0 +1,71 @@
+//! Kani proof harnesses for the core invariants, checked for every possible input
+//! rather than a random sample. Run them with `cargo kani`.
+//!
+//! Parsing looks names up with a loop over the twelve months, so the parse harnesses
+//! unwind loops up to 13 times: one more than the longest loop, so Kani can check that it ends.
+
+use crate::*;
+
+
+
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn format_never_panics() {
+    let timestamp: u64 = kani::any();
+    let mut buffer = [0; FORMATTED_LEN];
+
+    let result = format(timestamp, &mut buffer);
+    assert_eq!(result.is_ok(), timestamp <= MAX_FORMATTABLE);
+}
+
+
+#[kani::proof]
+#[kani::unwind(13)]
+fn parse_inverts_format() {
+    let timestamp: u64 = kani::any();
+    kani::assume(timestamp <= MAX_FORMATTABLE);
+
+    let mut buffer = [0; FORMATTED_LEN];
+    format(timestamp, &mut buffer).unwrap();
+    assert_eq!(parse(&buffer), Ok(timestamp));
+}
+
+
+#[kani::proof]
+#[kani::unwind(13)]
+fn imf_fixdate_never_panics() {
+    let header: [u8; FORMATTED_LEN] = kani::any();
+
+    if let Ok(timestamp) = parse(&header) {
+        assert!(timestamp <= MAX_FORMATTABLE);
+    }
+}
+
+
+#[kani::proof]
+fn digit_converters_stay_in_range() {
+    if let Ok(n) = toint_2(&kani::any()) {
+        assert!(n < 100);
+    }
+
+    if let Ok(n) = toint_4(&kani::any()) {
+        assert!(n < 10000);
+    }
+
+    if let Ok((hour, min, sec)) = toint_time(&kani::any()) {
+        assert!(hour < 100 && min < 100 && sec < 100);
+    }
+}
+
+
+#[kani::proof]
+fn two_digits_are_ascii() {
+    let n: u8 = kani::any();
+    kani::assume(n < 100);
+
+    let [tens, ones, rest @ ..] = two_digits(n).to_le_bytes();
+    assert!(tens.is_ascii_digit() && ones.is_ascii_digit());
+    assert_eq!(rest, [0; 6]);
+    assert_eq!((tens - b'0') * 10 + (ones - b'0'), n);
+}
@@ -0,0 +1,119 @@
+//! Conversions between [js_sys::Date](::js_sys::Date) and unix timestamps, plus `format` and `parse`
+//! exported to JavaScript with [wasm-bindgen](::wasm_bindgen), for service workers and WASM proxies.
+//!
+//! A `Date` counts milliseconds, so converting one truncates toward the past, which is the right
+//! direction for `Last-Modified` and other validators. An invalid `Date` is out of range.
+//!
+//! From JavaScript, the exported functions take and return `Date` objects:
+//!
+//! ```js
+//! import { format, parse } from "date_header";
+//!
+//! format(new Date(1431704061000));              // "Fri, 15 May 2015 15:34:21 GMT"
+//! parse("Sunday, 06-Nov-94 08:49:37 GMT");      // Date for 1994-11-06T08:49:37Z
+//! parse("yesterday");                           // undefined
+//! ```
+
+use std::string::String;
+use ::js_sys::Date;
+use ::wasm_bindgen::prelude::*;
+use crate::{format, in_formattable_range, parse, TooFuturistic, FORMATTED_LEN, MAX_FORMATTABLE};
+
+
+
+
+/// Convert a JavaScript `Date` into a unix timestamp, discarding the milliseconds.
+///
+/// Fails for invalid dates, and for times before the unix epoch or after the year 9999.
+pub fn from_js_date(date: &Date) -> Result<u64, OutOfRange> {
+    from_epoch_millis(date.get_time())
+}
+
+
+/// Convert a unix timestamp into a JavaScript `Date`.
+///
+/// Fails for times after the year 9999.
+pub fn to_js_date(secs_since_epoch: u64) -> Result<Date, TooFuturistic> {
+    let millis = to_epoch_millis(secs_since_epoch)?;
+    Ok(Date::new(&JsValue::from_f64(millis)))
+}
+
+
+/// Error returned from [from_js_date] indicating that the `Date` can't be an HTTP date.
+#[derive(Debug, Eq, PartialEq)]
+pub struct OutOfRange;
+
+
+
+
+/// Format a `Date` as an IMF-fixdate, or `undefined` if it is invalid or out of range.
+#[wasm_bindgen(js_name = format)]
+pub fn format_js(date: &Date) -> Option<String> {
+    let secs = from_js_date(date).ok()?;
+    let mut buffer = [0; FORMATTED_LEN];
+    format(secs, &mut buffer).ok()?;
+    Some(String::from_utf8(buffer.to_vec()).expect("IMF-fixdate is ASCII"))
+}
+
+
+/// Parse an HTTP date in any of the three formats into a `Date`, or `undefined` if it is invalid.
+#[wasm_bindgen(js_name = parse)]
+pub fn parse_js(header: &str) -> Option<Date> {
+    let secs = parse(header.as_bytes()).ok()?;
+    to_js_date(secs).ok()
+}
+
+
+
+
+fn from_epoch_millis(millis: f64) -> Result<u64, OutOfRange> {
+    // NaN, which is what an invalid Date holds, fails both comparisons
+    let secs = (millis / 1000.0).floor();
+    if !(secs >= 0.0 && secs <= MAX_FORMATTABLE as f64) {
+        return Err(OutOfRange);
+    }
+    Ok(secs as u64)
+}
+
+
+fn to_epoch_millis(secs_since_epoch: u64) -> Result<f64, TooFuturistic> {
+    if !in_formattable_range(secs_since_epoch) {
+        return Err(TooFuturistic);
+    }
+
+    // Exact, since the year 9999 in milliseconds is well under 2^53
+    Ok(secs_since_epoch as f64 * 1000.0)
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::MAX_FORMATTABLE;
+    use super::*;
+
+
+
+    #[test]
+    fn test_from_epoch_millis() {
+        assert_eq!(from_epoch_millis(0.0), Ok(0));
+        assert_eq!(from_epoch_millis(1431704061999.0), Ok(1431704061)); // Truncated, not rounded
+        assert_eq!(from_epoch_millis(MAX_FORMATTABLE as f64 * 1000.0 + 999.0), Ok(MAX_FORMATTABLE));
+
+        assert_eq!(from_epoch_millis(-1.0), Err(OutOfRange)); // Still before the epoch
+        assert_eq!(from_epoch_millis((MAX_FORMATTABLE + 1) as f64 * 1000.0), Err(OutOfRange));
+        assert_eq!(from_epoch_millis(f64::NAN), Err(OutOfRange));
+        assert_eq!(from_epoch_millis(f64::INFINITY), Err(OutOfRange));
+        assert_eq!(from_epoch_millis(8.64e15), Err(OutOfRange)); // The largest valid Date
+    }
+
+
+    #[test]
+    fn test_to_epoch_millis() {
+        assert_eq!(to_epoch_millis(0), Ok(0.0));
+        assert_eq!(to_epoch_millis(1431704061), Ok(1431704061000.0));
+        assert_eq!(to_epoch_millis(MAX_FORMATTABLE), Ok(253402300799000.0));
+        assert_eq!(to_epoch_millis(MAX_FORMATTABLE + 1), Err(TooFuturistic));
+    }
+}
@@ -0,0 +1,180 @@
+//! [winnow](::winnow) parsers for HTTP dates, for embedding the date grammar in a combinator-based header parser.
+//!
+//! Like [parse_prefix](crate::parse_prefix), these read a date from the start of the input and leave
+//! the input positioned just after it. They accept any complete or partial stream of bytes or text.
+//!
+//! ```rust
+//! use winnow::{Parser, Result};
+//!
+//! fn expires(input: &mut &str) -> Result<u64> {
+//!     ("Expires: ", date_header::winnow::http_date, "\r\n").map(|(_, secs, _)| secs).parse_next(input)
+//! }
+//!
+//! let mut input = "Expires: Fri, 15 May 2015 15:34:21 GMT\r\nAge: 0";
+//! assert_eq!(Ok(1431704061), expires(&mut input));
+//! assert_eq!(input, "Age: 0");
+//! ```
+
+use ::winnow::error::{Needed, ParserError};
+use ::winnow::stream::{AsBStr, Stream, StreamIsPartial};
+use crate::{parse_prefix, HttpDate, Timestamp, MONTH_NAMES};
+
+
+// The longest date in any format, an RFC850 date on a Wednesday
+const MAX_LEN: usize = 33;
+
+// Each format after its weekday, where `0` is a digit, `?` is a digit or a space,
+// `_` is a letter of the month name, and every other byte must match exactly
+const IMF_FIXDATE: &[u8] = b", 00 ___ 0000 00:00:00 GMT";
+const RFC850: &[u8] = b", 00-___-00 00:00:00 GMT";
+const ASCTIME: &[u8] = b" ___ ?0 00:00:00 0000";
+
+const WEEKDAYS: [&[u8]; 7] = [b"Sunday", b"Monday", b"Tuesday", b"Wednesday", b"Thursday", b"Friday", b"Saturday"];
+
+
+
+
+/// Parse an HTTP date in any format into a unix timestamp.
+pub fn http_date<I, E>(input: &mut I) -> Result<u64, E>
+where
+    I: Stream + StreamIsPartial,
+    I::Slice: AsBStr,
+    E: ParserError<I>,
+{
+    let available = input.eof_offset().min(MAX_LEN);
+    let head = input.peek_slice(available);
+
+    match parse_prefix(head.as_bstr()) {
+        Ok((rest, secs)) => {
+            let len = available - rest.len();
+            input.next_slice(len);
+            Ok(secs)
+        }
+        // A partial stream may just not have the whole date yet
+        Err(_) if input.is_partial() && could_complete(head.as_bstr()) => Err(E::incomplete(input, Needed::Unknown)),
+        Err(_) => Err(E::from_input(input)),
+    }
+}
+
+
+/// Parse an HTTP date in any format into a [Timestamp].
+pub fn timestamp<I, E>(input: &mut I) -> Result<Timestamp, E>
+where
+    I: Stream + StreamIsPartial,
+    I::Slice: AsBStr,
+    E: ParserError<I>,
+{
+    http_date(input).map(|secs| Timestamp::new(secs).expect("Parsed dates are always in the formattable range"))
+}
+
+
+/// Parse an HTTP date in any format into an [HttpDate].
+pub fn date<I, E>(input: &mut I) -> Result<HttpDate, E>
+where
+    I: Stream + StreamIsPartial,
+    I::Slice: AsBStr,
+    E: ParserError<I>,
+{
+    timestamp(input).map(HttpDate::from)
+}
+
+
+
+
+// Whether the bytes stop short of a date in some format without breaking its shape,
+// so that a partial stream could still go on to hold a valid date
+fn could_complete(head: &[u8]) -> bool {
+    WEEKDAYS.iter().any(|weekday| {
+        could_match(head, weekday, RFC850) || could_match(head, &weekday[..3], IMF_FIXDATE) || could_match(head, &weekday[..3], ASCTIME)
+    })
+}
+
+
+fn could_match(head: &[u8], weekday: &[u8], pattern: &[u8]) -> bool {
+    let (name, rest) = head.split_at(head.len().min(weekday.len()));
+    if !weekday.starts_with(name) || rest.len() >= pattern.len() {
+        return false;
+    }
+
+    let shape = rest.iter().zip(pattern).all(|(&byte, &expected)| match expected {
+        b'0' => byte.is_ascii_digit(),
+        b'?' => byte == b' ' || byte.is_ascii_digit(),
+        b'_' => true,
+        _ => byte == expected,
+    });
+
+    let month_start = pattern.iter().position(|&byte| byte == b'_').unwrap_or(pattern.len());
+    let month = rest.get(month_start..).unwrap_or_default();
+    let month = &month[..month.len().min(3)];
+
+    shape && MONTH_NAMES.iter().any(|name| name.starts_with(month))
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use ::winnow::error::{ContextError, ErrMode, ModalResult};
+    use ::winnow::{Parser, Partial};
+    use super::*;
+
+
+
+    #[test]
+    fn test_http_date() {
+        let mut input: &[u8] = b"Sun, 06 Nov 1994 08:49:37 GMT\r\n";
+        assert_eq!(http_date::<_, ContextError>(&mut input), Ok(784111777));
+        assert_eq!(input, b"\r\n");
+
+        let mut input = "Sunday, 06-Nov-94 08:49:37 GMT";
+        assert_eq!(http_date::<_, ContextError>(&mut input), Ok(784111777));
+        assert_eq!(input, "");
+
+        // Failing leaves the input where it was
+        let mut input = "Sun, 06 Nov 1994 08:49:37 UTC\r\n";
+        assert!(http_date::<_, ContextError>(&mut input).is_err());
+        assert_eq!(input, "Sun, 06 Nov 1994 08:49:37 UTC\r\n");
+
+        assert!(http_date::<_, ContextError>.parse("Sun Nov  6 08:49:37 1994").is_ok());
+        assert!(http_date::<_, ContextError>.parse("Sun Nov  6 08:49:37 1994 ").is_err());
+    }
+
+
+    #[test]
+    fn test_partial() {
+        let mut input = Partial::new(&b"Sun, 06 Nov 1994 08:4"[..]);
+        let result: ModalResult<u64> = http_date(&mut input);
+        assert_eq!(result, Err(ErrMode::Incomplete(Needed::Unknown)));
+
+        let mut input = Partial::new(&b"Sun, 06 Nov 1994 08:49:37 GMT"[..]);
+        let result: ModalResult<u64> = http_date(&mut input);
+        assert_eq!(result, Ok(784111777));
+
+        let mut input = Partial::new(&b"Sun, 06 Nov 1994 08:49:37 UTC and more"[..]);
+        let result: ModalResult<u64> = http_date(&mut input);
+        assert!(matches!(result, Err(ErrMode::Backtrack(_))));
+
+        // Every format waits for more input while what it has so far could still be a date
+        for head in [&b""[..], b"S", b"Wednes", b"Sun,", b"Sun, 0", b"Sun N", b"Sun Nov  ", b"Sunday, 06-No", b"Sun Nov  6 08:49:37 199"] {
+            let result: ModalResult<u64> = http_date(&mut Partial::new(head));
+            assert_eq!(result, Err(ErrMode::Incomplete(Needed::Unknown)), "{}", head.escape_ascii());
+        }
+
+        // Short input that can never become a date fails without waiting
+        for head in [&b"0\r\n"[..], b"Sun, 06 Nov 1994 08:49:37 UTC\r\n", b"Mun", b"Sun, 06 Nob", b"Sunday 06", b"Sun Nov 066", b"Mon, 06 Nov 1994 08:49:37 GMT\r\n"] {
+            let result: ModalResult<u64> = http_date(&mut Partial::new(head));
+            assert!(matches!(result, Err(ErrMode::Backtrack(_))), "{}", head.escape_ascii());
+        }
+    }
+
+
+    #[test]
+    fn test_conversions() {
+        let mut input = "Fri, 31 Dec 9999 23:59:59 GMT";
+        assert_eq!(timestamp::<_, ContextError>(&mut input), Ok(Timestamp::MAX));
+
+        let mut input = "Thu, 01 Jan 1970 00:00:00 GMT";
+        assert_eq!(date::<_, ContextError>(&mut input).map(HttpDate::timestamp), Ok(0));
+    }
+}
@@ -0,0 +1,29 @@
+//! With the `no-panic` feature in a release build, this only links if `format` and `parse` can't panic.
+//! Run it with `cargo test --release --features no-panic --test no_panic`.
+
+use core::hint::black_box;
+use date_header::*;
+
+
+
+
+#[test]
+fn test_format() {
+    let mut buffer = [0; FORMATTED_LEN];
+    assert_eq!(format(black_box(784111777), &mut buffer), Ok(()));
+    assert_eq!(&buffer, b"Sun, 06 Nov 1994 08:49:37 GMT");
+
+    assert_eq!(format(black_box(MAX_FORMATTABLE), &mut buffer), Ok(()));
+    assert_eq!(format(black_box(u64::MAX), &mut buffer), Err(TooFuturistic));
+}
+
+
+#[test]
+fn test_parse() {
+    assert_eq!(parse(black_box(b"Sun, 06 Nov 1994 08:49:37 GMT")), Ok(784111777));
+    assert_eq!(parse(black_box(b"Sunday, 06-Nov-94 08:49:37 GMT")), Ok(784111777));
+    assert_eq!(parse(black_box(b"Sun Nov  6 08:49:37 1994")), Ok(784111777));
+
+    assert_eq!(parse(black_box(b"Sun, 06 Nov 1994 08:49:37 UTC")), Err(InvalidDate));
+    assert_eq!(parse(black_box(b"")), Err(InvalidDate));
+}